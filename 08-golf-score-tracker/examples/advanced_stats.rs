@@ -1,15 +1,18 @@
 //! Advanced Statistics Example
 //! 
 //! This example demonstrates:
-//! - Creating multiple rounds for a player
+//! - Playing a four-round tournament and reading its leaderboard
 //! - Using closures and iterators
 //! - Calculating comprehensive statistics
 //! - Pattern matching and functional programming
+//! - Storing rounds in an in-memory repository
 //!
 //! Run with: cargo run --example advanced_stats
 
 use std::collections::BTreeMap;
-use golf_score_tracker::{Player, Scorecard, PlayerStatistics};
+use golf_score_tracker::models::{Course, Hole, TeeSet, Tournament};
+use golf_score_tracker::services::Leaderboard;
+use golf_score_tracker::{InMemoryRepository, Player, PlayerStatistics, Repository, Scorecard};
 
 
 fn main() -> anyhow::Result<()> {
@@ -18,20 +21,26 @@ fn main() -> anyhow::Result<()> {
     let player_jordan = Player::new("Jordan Spieth", Some(1.2))?;
     println!("Player: {} (Handicap: {:?})\n", player_jordan.name, player_jordan.handicap);
 
-    let pars = create_tournament_course();
-    let mut scorecards = Vec::new();
+    let mut repo = InMemoryRepository::new();
+    repo.save_player(&player_jordan)?;
 
-     // Round 1: Excellent round
-    scorecards.push(create_round(&player_jordan, &pars, generate_scores(-4))?);
-    
-    // Round 2: Good round
-    scorecards.push(create_round(&player_jordan, &pars, generate_scores(-2))?);
-    
-    // Round 3: Struggled
-    scorecards.push(create_round(&player_jordan, &pars, generate_scores(4))?);
-    
-    // Round 4: Back to form for comeback victory
-    scorecards.push(create_round(&player_jordan, &pars, generate_scores(-3))?);
+    let course = create_tournament_course()?;
+    let mut tournament = Tournament::new("Colonial Invitational", &[&course, &course, &course, &course])?;
+    tournament.join(player_jordan.id)?;
+
+    // Excellent, good, a struggle, then back to form for a comeback victory
+    for (round, target) in (1..).zip([-4, -2, 4, -3]) {
+        let card = create_round(&mut tournament, round, &player_jordan, &course, generate_scores(target))?;
+        repo.save_scorecard(&card)?;
+    }
+    repo.save_tournament(&tournament)?;
+
+    let scorecards = repo.get_scorecards_by_player(&player_jordan.id)?;
+    let leaderboard = Leaderboard::new(&tournament, &scorecards);
+    if let Some(row) = leaderboard.row(&player_jordan.id) {
+        let rounds: Vec<String> = row.rounds.iter().flatten().map(u16::to_string).collect();
+        println!("{}: {} ({:+}) after rounds of {}", tournament.name, row.total_strokes, row.to_par, rounds.join(", "));
+    }
 
     println!("\n═══════════════════════════════════════");
     println!("📈 Tournament Statistics");
@@ -98,7 +107,7 @@ fn main() -> anyhow::Result<()> {
         for hole in 1..=18 {
             if let (Some(strokes), Some(par)) = (card.get_score(hole), card.get_par(hole)) {
                 let diff = strokes as i8 - par as i8;
-                hole_performance.entry(hole).or_insert_with(Vec::new).push(diff);
+                hole_performance.entry(hole).or_default().push(diff);
             }
         }
     }
@@ -136,11 +145,13 @@ fn main() -> anyhow::Result<()> {
 }
 
 /// Create a standard 18-hole course layout
-fn create_tournament_course() -> BTreeMap<u8, u8> {
+fn create_tournament_course() -> anyhow::Result<Course> {
     let layout = vec![4, 5, 4, 3, 4, 4, 3, 5, 4, 4, 4, 5, 3, 4, 4, 3, 5, 4];
-    layout.iter().enumerate()
-        .map(|(i, &par)| ((i + 1) as u8, par))
-        .collect()
+    let holes = layout.iter().enumerate()
+        .map(|(i, &par)| Hole::new((i + 1) as u8, par, 18))
+        .collect::<Result<Vec<_>, _>>()?;
+    let tee = TeeSet::unrated("Championship", layout.iter().map(|&par| u16::from(par)).sum());
+    Ok(Course::new("Colonial", holes, vec![tee])?)
 }
 
 
@@ -153,12 +164,12 @@ fn generate_scores(target_relative: i16) -> Vec<u8> {
     let adjustment = target_total as i16 - 72;
     let mut remaining = adjustment;
         
-    for i in 0..18 {
+    for (i, score) in scores.iter_mut().enumerate() {
         if remaining < 0 && i % 3 == 0 {
-            scores[i] = 3;
+            *score = 3;
             remaining += 1;
         } else if remaining > 0 && i % 4 == 0 {
-            scores[i] = 5;
+            *score = 5;
             remaining -= 1;
         }
     }
@@ -166,11 +177,11 @@ fn generate_scores(target_relative: i16) -> Vec<u8> {
     scores
 }
 
-/// Create a round for a player with given scores
-fn create_round(player: &Player, pars: &BTreeMap<u8, u8>, scores: Vec<u8>) -> anyhow::Result<Scorecard> {
-    let mut card = Scorecard::new(player.id, 18, pars.clone())?;
+/// Create a tournament round for a player with given scores
+fn create_round(tournament: &mut Tournament, round: u8, player: &Player, course: &Course, scores: Vec<u8>) -> anyhow::Result<Scorecard> {
+    let mut card = tournament.start_round(round, player.id, course, course.default_tee())?;
     for (hole, &strokes) in scores.iter().enumerate() {
         card.record_score((hole + 1) as u8, strokes)?;
     }
     Ok(card)
-}
\ No newline at end of file
+}
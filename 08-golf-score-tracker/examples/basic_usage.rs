@@ -8,16 +8,14 @@
 //!
 //! Run with: cargo run --example basic_usage
 
-use std::collections::BTreeMap;
-use golf_score_tracker::{Player, Scorecard, FileRepository, Repository};
-use std::path::PathBuf;
+use golf_score_tracker::models::{Course, Hole, TeeSet};
+use golf_score_tracker::{InMemoryRepository, Player, Repository, Scorecard};
 
 fn main() -> anyhow::Result<()> {
     println!("🏌️ Golf Score Tracker - Basic Usage Example\n");
     
-    let temp_dir = PathBuf::from("./examples_data");
-    std::fs::create_dir_all(&temp_dir)?;
-    let mut repo = FileRepository::new(temp_dir)?;
+    // Keep everything in memory so the example leaves nothing behind on disk
+    let mut repo = InMemoryRepository::new();
         
     println!("Step 1: Creating players...");
     let player_rory = Player::new("Rory McIlroy", Some(0.0))?;
@@ -30,21 +28,23 @@ fn main() -> anyhow::Result<()> {
     
     // Step 2: Create a 9-hole course
     println!("Step 2: Creating a 9-hole course...");
-    let mut pars = BTreeMap::new();
-    for hole in 1..=9 {
-        let par = match hole {
-            2 | 5 | 8 => 5,  // Par 5s
-            3 | 7 => 3,      // Par 3s
-            _ => 4,          // Par 4s
+    let mut holes = Vec::new();
+    for number in 1..=9 {
+        let (par, yardage) = match number {
+            2 | 5 | 8 => (5, 510),  // Par 5s
+            3 | 7 => (3, 165),      // Par 3s
+            _ => (4, 390),          // Par 4s
         };
-        pars.insert(hole, par);
+        holes.push(Hole::new(number, par, 9)?.with_yardage(yardage));
     }
-    println!("✅ Course layout: {:?}\n", pars);
+    let course = Course::new("Riverside Nine", holes, vec![TeeSet::new("White", 36.2, 124)?])?;
+    println!("✅ {}: par {}, {} yards\n", course.name, course.total_par(), course.total_yardage().unwrap_or(0));
     
     // Step 3: Create scorecards
     println!("Step 3: Creating scorecards...");
-    let mut rory_card = Scorecard::new(player_rory.id, 9, pars.clone())?;
-    let mut scottie_card = Scorecard::new(player_scottie.id, 9, pars)?;
+    let tee = course.tee("White")?;
+    let mut rory_card = Scorecard::new(player_rory.id, &course, tee)?;
+    let mut scottie_card = Scorecard::new(player_scottie.id, &course, tee)?;
     println!("✅ Created scorecards\n");
     
     // Step 4: Record Rory's round (excellent!)
@@ -74,7 +74,8 @@ fn main() -> anyhow::Result<()> {
     println!("\n💾 Saving to repository...");
     repo.save_scorecard(&rory_card)?;
     repo.save_scorecard(&scottie_card)?;
-    println!("✅ All data saved!\n");
+    println!("✅ Saved {} players and {} scorecards!\n",
+        repo.list_players()?.len(), repo.list_scorecards()?.len());
     
     Ok(())
 }
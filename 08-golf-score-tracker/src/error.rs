@@ -18,8 +18,12 @@
 //! assert!(validate_score(4, 1, 4).is_ok());
 //! assert!(validate_score(0, 1, 4).is_err());
 //! ```
+use std::path::PathBuf;
+
 use thiserror::Error;
 
+use crate::storage::Format;
+
 /// Result type alias for golf tracker operations.
 ///
 /// This type alias simplifies function signatures by defaulting the error
@@ -40,9 +44,22 @@ pub type Result<T> = std::result::Result<T, GolfError>;
 /// * `InvalidHole` - Hole number outside valid range (1 to max_holes)
 /// * `InvalidScore` - Score value unrealistic (0 or > 15)
 /// * `InvalidPar` - Par value outside valid range (3-5)
+/// * `InvalidHoleResult` - Putts, fairway or penalty detail inconsistent with the hole
 /// * `ScorecardComplete` - Attempted to modify completed scorecard
 /// * `Io` - File system or I/O operation failed
 /// * `SerdeJson` - JSON serialization/deserialization failed
+/// * `Encoding` - Non-JSON storage format failed to encode/decode
+/// * `FormatMismatch` - Repository on disk uses a different storage format
+/// * `Database` - SQLite query or migration failed
+/// * `RepositoryLocked` - Data directory is in use by another process
+/// * `CorruptFile` - A stored entity file could not be decoded
+/// * `InvalidCourseFile` - A course definition file is unreadable or invalid
+/// * `CourseNotFound` - No course matches a name, with close matches if any
+/// * `PlayerNotFound` - No player matches a name or ID
+/// * `AmbiguousPlayer` - Several players match a name or ID prefix
+/// * `PlayerInUse` - Player still has scorecards or tournament entries
+/// * `Csv` - Reading or writing CSV data failed
+/// * `DuplicateRecords` - Imported records already exist and no policy was chosen
 ///
 /// # Examples
 ///
@@ -80,6 +97,34 @@ pub enum GolfError {
     #[error("Player '{0}' not found")]
     PlayerNotFound(String),
 
+    /// A player name or ID prefix matches more than one player.
+    ///
+    /// `candidates` describes each match as "Name (ID)".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::GolfError;
+    ///
+    /// let error = GolfError::AmbiguousPlayer {
+    ///     query: "Rory".to_string(),
+    ///     candidates: vec!["Rory McIlroy (1f0c...)".to_string(), "Rory Sabbatini (8a2d...)".to_string()],
+    /// };
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Player 'Rory' is ambiguous. Candidates:\n  Rory McIlroy (1f0c...)\n  Rory Sabbatini (8a2d...)"
+    /// );
+    /// ```
+    #[error("Player '{query}' is ambiguous. Candidates:\n  {}", candidates.join("\n  "))]
+    AmbiguousPlayer { query: String, candidates: Vec<String> },
+
+    /// A player was not deleted because other records still refer to them.
+    #[error(
+        "{name} has {scorecards} scorecards and is entered in {tournaments} tournaments. \
+         Delete them as well to remove the player."
+    )]
+    PlayerInUse { name: String, scorecards: usize, tournaments: usize },
+
     /// Round not found
     #[error("Round with ID {0} not found")]
     RoundNotFound(uuid::Uuid),
@@ -100,6 +145,11 @@ pub enum GolfError {
     #[error("Par {0} is invalid. Must be 3, 4, or 5.")]
     InvalidPar(u8),
 
+    /// Shot detail recorded for a hole does not fit the hole or the
+    /// strokes taken, e.g. more putts than strokes.
+    #[error("Invalid detail for hole {hole}: {reason}")]
+    InvalidHoleResult { hole: u8, reason: String },
+
     /// Attempted to modify a scorecard that is already complete.
     ///
     /// Once all holes are recorded, the scorecard is considered complete
@@ -119,6 +169,84 @@ pub enum GolfError {
     #[error("File operation failed")]
     IoError(#[from] std::io::Error),
 
+    /// Encoding or decoding data in a non-JSON storage format failed.
+    ///
+    /// JSON errors keep using `SerializationError`; TOML, CBOR and
+    /// MessagePack errors are reported here with the format that failed.
+    #[error("Failed to encode/decode {format} data: {message}")]
+    Encoding { format: Format, message: String },
+
+    /// The repository manifest records a different storage format than the
+    /// one requested.
+    ///
+    /// Mixing formats in one data directory would leave files that can no
+    /// longer be read back, so opening the repository is refused instead.
+    #[error("Repository at {} stores {found} data, but {expected} was requested", path.display())]
+    FormatMismatch { path: PathBuf, expected: Format, found: Format },
+
+    /// Another repository instance holds the lock on the data directory.
+    #[error("Repository at {} is in use by another process", .0.display())]
+    RepositoryLocked(PathBuf),
+
+    /// A stored entity file exists but cannot be decoded, typically because
+    /// a write was interrupted. `FileRepository::repair` quarantines such
+    /// files.
+    #[error("Could not read {}: {reason}. Run `repair` to quarantine unreadable files.", path.display())]
+    CorruptFile { path: PathBuf, reason: String },
+
+    /// A course definition in the catalog directory cannot be read or does
+    /// not describe a valid course.
+    #[error("Course file {} is invalid: {reason}", path.display())]
+    InvalidCourseFile { path: PathBuf, reason: String },
+
+    /// The profiles config file cannot be read.
+    #[error("Config file {} is invalid: {reason}", path.display())]
+    InvalidConfig { path: PathBuf, reason: String },
+
+    /// No profile with the requested name is defined in the config file.
+    #[error("Profile '{name}' not found. {}", if available.is_empty() {
+        "No profiles are configured; add one with `profile add`.".to_string()
+    } else {
+        format!("Available profiles: {}", available.join(", "))
+    })]
+    UnknownProfile { name: String, available: Vec<String> },
+
+    /// No course in the catalog matches the requested name.
+    ///
+    /// `suggestions` holds the keys of close matches, closest first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::GolfError;
+    ///
+    /// let error = GolfError::CourseNotFound {
+    ///     name: "Torrey".to_string(),
+    ///     suggestions: vec!["Torrey_Pines_North".to_string(), "Torrey_Pines_South".to_string()],
+    /// };
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Course 'Torrey' not found. Did you mean Torrey_Pines_North or Torrey_Pines_South?"
+    /// );
+    /// ```
+    #[error("Course '{name}' not found{}", did_you_mean(suggestions))]
+    CourseNotFound { name: String, suggestions: Vec<String> },
+
+    /// SQLite query, transaction or schema migration failed.
+    #[error("Database operation failed")]
+    Database(#[from] rusqlite::Error),
+
+    /// Reading or writing CSV export data failed.
+    #[error("Failed to read/write CSV data: {0}")]
+    Csv(#[from] csv::Error),
+
+    /// Records being imported share IDs with records already stored.
+    ///
+    /// Nothing is imported until the caller decides whether duplicates
+    /// are merged or skipped.
+    #[error("{0} imported records already exist. Choose to merge or skip them (--on-duplicate merge|skip)")]
+    DuplicateRecords(usize),
+
     #[error("{0}")]
     Custom(String),
 }
@@ -129,18 +257,37 @@ impl GolfError {
     }
 
     pub fn is_not_found(&self) -> bool {
-        matches!(self, GolfError::PlayerNotFound(_) | GolfError::RoundNotFound(_))
+        matches!(
+            self,
+            GolfError::PlayerNotFound(_)
+                | GolfError::RoundNotFound(_)
+                | GolfError::CourseNotFound { .. }
+                | GolfError::UnknownProfile { .. }
+        )
     }
 
     pub fn is_validation_error(&self) -> bool {
         matches!(
             self,
-            GolfError::InvalidScore { .. } | GolfError::InvalidHole { .. } | GolfError::InvalidPar(_)
+            GolfError::InvalidScore { .. }
+                | GolfError::InvalidHole { .. }
+                | GolfError::InvalidPar(_)
+                | GolfError::InvalidHoleResult { .. }
         )
     }
 }
 
 
+/// Formats course suggestions as `. Did you mean A, B or C?`, or nothing
+/// when there are none.
+fn did_you_mean(suggestions: &[String]) -> String {
+    match suggestions {
+        [] => String::new(),
+        [only] => format!(". Did you mean {}?", only),
+        [rest @ .., last] => format!(". Did you mean {} or {}?", rest.join(", "), last),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
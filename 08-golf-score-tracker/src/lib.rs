@@ -1,9 +1,16 @@
 pub mod error;
 pub mod models;
+pub mod services;
 pub mod storage;
 pub mod ui;
 pub mod utils;
 
+/// The integration tests' fixtures, shared with the unit tests.
+#[cfg(test)]
+#[path = "../tests/common/mod.rs"]
+#[allow(dead_code)]
+mod test_support;
+
 pub use error::{GolfError, Result};
 pub use models::{Hole, Player, Round, Scorecard, PlayerStatistics};
-pub use storage::{FileRepository, Repository};
\ No newline at end of file
+pub use storage::{FileRepository, InMemoryRepository, Repository};
\ No newline at end of file
@@ -1,16 +1,58 @@
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use clap::Parser;
+use uuid::Uuid;
 
-use golf_score_tracker::{FileRepository, Player, PlayerStatistics, Repository, Scorecard};
-use golf_score_tracker::ui::{Cli, Commands};
-use golf_score_tracker::utils::{get_course_pars, list_available_courses};
+use golf_score_tracker::models::{Course, CourseRating, CutRule, Hole, HoleResult, Round, TeeSet, Tournament};
+use golf_score_tracker::services::{Competitor, FormatResult, HandicapCalculation, Leaderboard, Thru};
+use golf_score_tracker::{GolfError, Player, PlayerStatistics, Repository, Scorecard};
+use golf_score_tracker::storage::{
+    Bundle, ColumnMapping, Config, CourseCatalog, CourseSource, DataDir, DataDirSource, DeleteMode, ExportFormat,
+    SqliteRepository, import_app_rounds, open_file_repository, read_course_file, repair_file_repository, resolve_data_dir,
+};
+use golf_score_tracker::storage::config::{CONFIG_ENV, LEGACY_DATA_DIR};
+use golf_score_tracker::ui::{Backend, Cli, Commands, ProfileCommand, TournamentCommand};
+use golf_score_tracker::ui::display::{CardFormat, render_scorecard, round_grid, scorecard_grid, sparkline};
+use golf_score_tracker::ui::play::play_round;
+use golf_score_tracker::ui::server::{self, Api};
+use golf_score_tracker::ui::tui;
+use golf_score_tracker::utils::create_standard_course;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
-    let data_dir = PathBuf::from("./golf_data");
-    let mut repo = FileRepository::new(data_dir)
-        .context("Failed to initialize repository")?;
+    let config_path = Config::default_path();
+    let config = match &config_path {
+        Some(path) => Config::load(path)?,
+        None => Config::default(),
+    };
+    let data_dir = resolve_data_dir(cli.data_dir.as_deref(), cli.profile.as_deref(), &config, |name| std::env::var_os(name));
+
+    if let Commands::Profile { command } = cli.command {
+        return profile(config, config_path, data_dir.ok(), command);
+    }
+
+    let data_dir = data_dir?;
+    if data_dir.source == DataDirSource::Legacy {
+        eprintln!("Note: using ./{} from an older version. Move it to the platform data directory, or pass \
+            --data-dir or a profile, to use it from anywhere.", LEGACY_DATA_DIR);
+    }
+    let data_dir = data_dir.path;
+
+    if let Commands::Repair = cli.command {
+        return repair(data_dir, &cli);
+    }
+
+    let mut repo: Box<dyn Repository> = match cli.backend {
+        Backend::File => open_file_repository(data_dir.clone(), cli.storage_format),
+        Backend::Sqlite => {
+            if cli.storage_format.is_some() {
+                anyhow::bail!("--storage-format only applies to the file backend");
+            }
+            SqliteRepository::open(data_dir.join("golf.db")).map(|repo| Box::new(repo) as Box<dyn Repository>)
+        }
+    }
+    .context("Failed to initialize repository")?;
 
     match cli.command {
         Commands::AddPlayer { name, handicap } => {
@@ -33,36 +75,121 @@ fn main() -> Result<()> {
             }
         }
 
-        Commands::RecordScore { player_id, hole, strokes } => {
-            println!("Recording score: player={}, hole={}, strokes={}", 
-                player_id, hole, strokes);
-            println!("(Full scorecard implementation coming next)");
+        Commands::EditPlayer { player, name, handicap, clear_handicap } => {
+            let player = repo.find_player(&player)?;
+            let handicap = if clear_handicap { None } else { handicap.or(player.handicap) };
+            let edited = Player::new(name.as_deref().unwrap_or(&player.name), handicap)
+                .context("Invalid player details")?;
+            let edited = Player { id: player.id, ..edited };
+            repo.update_player(&edited)
+                .context("Failed to update player")?;
+            println!("Player updated: {} (handicap: {:?})", edited.name, edited.handicap);
         }
 
-        Commands::CreateScorecard { player_id, holes, course } => {
-            let player = repo.get_player(&player_id)
-                .context("Failed to get player")?
-                .ok_or_else(|| anyhow::anyhow!("Player {} not found", player_id))?;
+        Commands::DeletePlayer { player, cascade } => {
+            let player = repo.find_player(&player)?;
+            let mode = if cascade { DeleteMode::Cascade } else { DeleteMode::Refuse };
+            let deleted = repo.delete_player(&player.id, mode)
+                .context("Failed to delete player")?;
+            println!("🗑️  Deleted {} and {} scorecards", player.name, deleted);
+        }
+
+        Commands::RecordScore { player, hole, strokes, round, putts, fairway, sand_save, penalties } => {
+            let player = repo.find_player(&player)?;
+            let player_id = player.id;
+
+            let mut scorecard = find_open_scorecard(repo.as_ref(), &player_id, round)?;
+            let result = HoleResult { putts, fairway, sand_save, penalties };
+            scorecard.record_hole(hole, strokes, result)
+                .context("Failed to record score")?;
+            repo.save_scorecard(&scorecard)
+                .context("Failed to save scorecard")?;
+
+            println!("✅ Recorded {} strokes on hole {} for {} (Round ID: {})",
+                strokes, hole, player.name, scorecard.round_id);
+
+            if let (Some(total), Some(relative)) = (scorecard.total_strokes(), scorecard.score_relative_to_par()) {
+                println!("🏁 Round complete: {} strokes ({:+})", total, relative);
+            }
+        }
+
+        Commands::CreateScorecard { player, holes, course, tee, course_rating, slope } => {
+            let player = repo.find_player(&player)?;
+            let player_id = player.id;
             
-            let pars = match &course {
-                Some(course_name) => get_course_pars(&course_name, holes),
-                None => get_course_pars("standard", holes),
+            let catalog = CourseCatalog::load(&data_dir).context("Failed to load course catalog")?;
+            let course = match course.as_deref() {
+                Some(name) => catalog.find(name)?.course.clone(),
+                None => create_standard_course(holes),
+            };
+            let tee = match &tee {
+                Some(name) => course.tee(name)?,
+                None => course.default_tee(),
             };
             
-            let scorecard = Scorecard::new(player_id, holes, pars)
+            let mut scorecard = Scorecard::new(player_id, &course, tee)
                 .context("Failed to create scorecard")?;
+            if let (Some(course_rating), Some(slope)) = (course_rating, slope) {
+                let rating = CourseRating::new(course_rating, slope)
+                    .context("Invalid course rating")?;
+                scorecard = scorecard.with_rating(rating);
+            }
+            if let Some(index) = player.handicap {
+                scorecard = scorecard.with_handicap_index(index);
+            }
             
             repo.save_scorecard(&scorecard)
                 .context("Failed to save scorecard")?;
             
-            let course_display = course.unwrap_or_else(|| "Standard".to_string());
-            println!("✅ Scorecard created for {} on {} course (Round ID: {})", 
-                player.name, course_display, scorecard.round_id);
+            println!("✅ Scorecard created for {} on {} course from the {} tees (Round ID: {})", 
+                player.name, course.name, tee.name, scorecard.round_id);
+        }
+
+        Commands::Play { round_id } => {
+            let scorecard = repo.get_scorecard(&round_id)
+                .context("Failed to get scorecard")?
+                .ok_or_else(|| anyhow::anyhow!("Scorecard {} not found", round_id))?;
+            play_round(repo.as_mut(), scorecard, std::io::stdin().lock(), std::io::stdout().lock())?;
+        }
+
+        Commands::FixScore { round_id, hole, strokes, reason } => {
+            let mut scorecard = repo.get_scorecard(&round_id)
+                .context("Failed to get scorecard")?
+                .ok_or_else(|| anyhow::anyhow!("Scorecard {} not found", round_id))?;
+            let previous = scorecard.get_score(hole);
+            scorecard.correct_score(hole, strokes, reason.as_deref())
+                .context("Failed to correct score")?;
+            repo.save_scorecard(&scorecard)
+                .context("Failed to save scorecard")?;
+            match previous {
+                Some(previous) if previous != strokes =>
+                    println!("✏️  Hole {} corrected from {} to {} strokes", hole, previous, strokes),
+                _ => println!("Hole {} already has {} strokes", hole, strokes),
+            }
+        }
+
+        Commands::Undo { round_id, reason } => {
+            let mut scorecard = repo.get_scorecard(&round_id)
+                .context("Failed to get scorecard")?
+                .ok_or_else(|| anyhow::anyhow!("Scorecard {} not found", round_id))?;
+            let undone = scorecard.undo(reason.as_deref())?;
+            repo.save_scorecard(&scorecard)
+                .context("Failed to save scorecard")?;
+            let show = |strokes: Option<u8>| strokes.map_or_else(|| "no score".to_string(), |s| format!("{} strokes", s));
+            println!("↩️  Hole {} on round {} is back to {} (was {})",
+                undone.hole, scorecard.round_id, show(undone.previous), show(undone.strokes));
+        }
+
+        Commands::History { round_id } => {
+            let scorecard = repo.get_scorecard(&round_id)
+                .context("Failed to get scorecard")?
+                .ok_or_else(|| anyhow::anyhow!("Scorecard {} not found", round_id))?;
+            print_history(&scorecard);
         }
 
-        Commands::ListScorecards { player_id } => {
-            let scorecards = if let Some(pid) = player_id {
-                repo.get_scorecards_by_player(&pid)
+        Commands::ListScorecards { player } => {
+            let scorecards = if let Some(player) = player {
+                repo.get_scorecards_by_player(&repo.find_player(&player)?.id)
                     .context("Failed to get scorecards for player")?
             } else {
                 repo.list_scorecards()
@@ -81,36 +208,167 @@ fn main() -> Result<()> {
             }
         }
 
-        Commands::ShowScorecard { round_id } => {
+        Commands::ShowScorecard { round_id, format } => {
             let scorecard = repo.get_scorecard(&round_id)
                 .context("Failed to get scorecard")?
                 .ok_or_else(|| anyhow::anyhow!("Scorecard {} not found", round_id))?;
-            
-            println!("📊 Scorecard for round {}", round_id);
-            println!("   Player: {}", scorecard.player_id);
-            println!("   Complete: {}", scorecard.is_complete());
-            
-            if let Some(total) = scorecard.total_strokes() {
-                println!("   Total strokes: {}", total);
+            let player = repo.get_player(&scorecard.player_id)
+                .context("Failed to get player")?
+                .map_or_else(|| scorecard.player_id.to_string(), |player| player.name);
+
+            if format == CardFormat::Text {
+                println!("📊 Scorecard for round {}\n", round_id);
+                let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+                println!("{}", scorecard_grid(&player, &scorecard, color));
+                if let Some(rating) = scorecard.rating {
+                    println!("   Rating/Slope: {:.1}/{}", rating.course_rating, rating.slope_rating);
+                }
+                if let Some(course_handicap) = scorecard.course_handicap {
+                    println!("   Course handicap: {}", course_handicap);
+                }
+                let changes = scorecard.history().iter().filter(|event| event.previous.is_some()).count();
+                if changes > 0 {
+                    println!("   Scores changed after entry: {} (see `history {}`)", changes, round_id);
+                }
+            } else {
+                print!("{}", render_scorecard(&player, &scorecard, format));
             }
-            if let Some(relative) = scorecard.score_relative_to_par() {
-                println!("   Relative to par: {:+}", relative);
+        }
+
+        Commands::CreateRound { players, holes, course, tee } => {
+            let catalog = CourseCatalog::load(&data_dir).context("Failed to load course catalog")?;
+            let course = match course.as_deref() {
+                Some(name) => catalog.find(name)?.course.clone(),
+                None => create_standard_course(holes),
+            };
+            let tee = match &tee {
+                Some(name) => course.tee(name)?,
+                None => course.default_tee(),
+            };
+
+            let mut round = Round::new(&course);
+            let mut group = Vec::with_capacity(players.len());
+            for name in &players {
+                let player = repo.find_player(name)?;
+                let mut scorecard = round.add_player(player.id, &course, tee)
+                    .context("Failed to add player to round")?;
+                if let Some(index) = player.handicap {
+                    scorecard = scorecard.with_handicap_index(index);
+                }
+                group.push((player.name, scorecard));
             }
-        }        
+
+            for (_, scorecard) in &group {
+                repo.save_scorecard(scorecard)
+                    .context("Failed to save scorecard")?;
+            }
+            repo.save_round(&round)
+                .context("Failed to save round")?;
+
+            println!("✅ Round created on {} course from the {} tees (Round ID: {})",
+                course.name, tee.name, round.id);
+            for (name, scorecard) in &group {
+                println!("   {} - Scorecard {}", name, scorecard.round_id);
+            }
+        }
+
+        Commands::ShowRound { round_id } => {
+            let (round, group) = load_group(repo.as_ref(), &round_id)?;
+            let group: Vec<(String, Scorecard)> = group.into_iter()
+                .map(|(player, scorecard)| (player.name, scorecard))
+                .collect();
+            print!("{}", round_grid(&round, &group));
+        }
+
+        Commands::ScoreRound { round_id, format } => {
+            let (round, group) = load_group(repo.as_ref(), &round_id)?;
+            let competitors: Vec<Competitor> = group.iter()
+                .map(|(player, scorecard)| match scorecard.course_handicap {
+                    Some(course_handicap) => Competitor::new(&player.name, scorecard, course_handicap),
+                    None => Competitor::with_index(&player.name, scorecard, player.handicap),
+                })
+                .collect();
+            let result = format.score(&competitors)
+                .with_context(|| format!("Failed to score round as {}", format))?;
+
+            println!("🏆 {} - {} ({})", round.course, format, round.date.format("%Y-%m-%d"));
+            print_format_result(&result);
+        }
+
+        Commands::ListRounds { player } => {
+            let rounds = if let Some(player) = player {
+                repo.get_rounds_by_player(&repo.find_player(&player)?.id)
+                    .context("Failed to get rounds for player")?
+            } else {
+                repo.list_rounds()
+                    .context("Failed to list rounds")?
+            };
+
+            if rounds.is_empty() {
+                println!("No rounds found");
+            } else {
+                println!("📋 Rounds:");
+                for round in rounds {
+                    println!("  {} {} - {} ({} players)",
+                        round.date.format("%Y-%m-%d"), round.id, round.course, round.entries.len());
+                }
+            }
+        }
+
+        Commands::Tournament { command } => tournament(repo.as_mut(), &data_dir, command)?,
 
         Commands::ListCourses => {
-            let courses = list_available_courses();
+            let catalog = CourseCatalog::load(&data_dir).context("Failed to load course catalog")?;
             println!("🏌️ Available courses:");
-            for course in courses {
-                println!("  • {}", course);
+            for entry in catalog.entries() {
+                let course = &entry.course;
+                let local = if entry.source == CourseSource::BuiltIn { "" } else { " (local)" };
+                println!("  • {} - {}, par {}, {} yards{}", entry.key, course.name, course.total_par(),
+                    course.total_yardage().map_or_else(|| "?".to_string(), |yards| yards.to_string()), local);
+                for tee in &course.tees {
+                    println!("      {} tees: {:.1}/{}", tee.name, tee.course_rating, tee.slope_rating);
+                }
             }
             println!("\nUse --course <name> when creating a scorecard");
         }
+
+        Commands::AddCourse { name, pars, yardages, stroke_indexes, tees, file, replace } => {
+            let course = match (file, name) {
+                (Some(path), _) => read_course_file(&path)
+                    .with_context(|| format!("Failed to read course from {}", path.display()))?,
+                (None, Some(name)) => course_from_args(&name, &pars, &yardages, &stroke_indexes, tees)
+                    .context("Invalid course definition")?,
+                (None, None) => unreachable!("clap requires a name or --file"),
+            };
+
+            let mut catalog = CourseCatalog::load(&data_dir).context("Failed to load course catalog")?;
+            let entry = catalog.add(course, replace).context("Failed to add course")?;
+            println!("✅ Course {} added as '{}' ({} holes, par {})",
+                entry.course.name, entry.key, entry.course.hole_count(), entry.course.total_par());
+        }
+
+        Commands::ShowCourse { course } => {
+            let catalog = CourseCatalog::load(&data_dir).context("Failed to load course catalog")?;
+            let entry = catalog.find(&course)?;
+            print_course(&entry.course);
+            if let CourseSource::File(path) = &entry.source {
+                println!("\n   Defined in {}", path.display());
+            }
+        }
+
+        Commands::RemoveCourse { course } => {
+            let mut catalog = CourseCatalog::load(&data_dir).context("Failed to load course catalog")?;
+            let key = catalog.find(&course)?.key.clone();
+            let removed = catalog.remove(&key).context("Failed to remove course")?;
+            println!("🗑️ Removed course {} ('{}')", removed.name, key);
+            if catalog.get(&key).is_some() {
+                println!("   The built-in course '{}' is available again", key);
+            }
+        }
     
-        Commands::ShowPlayerStatistics {player_id} => {
-            let player = repo.get_player(&player_id)
-                .context("Failed to retrieve player")?
-                .ok_or_else(|| anyhow::anyhow!("Player {} not found", player_id))?;
+        Commands::ShowPlayerStatistics { player, trend, last } => {
+            let player = repo.find_player(&player)?;
+            let player_id = player.id;
 
             let scorecards = repo.get_scorecards_by_player(&player_id)
                 .context("Failed to retrieve scorecards")?;
@@ -120,33 +378,691 @@ fn main() -> Result<()> {
                 return Ok(());
             }
             let stats = PlayerStatistics::from_scorecards(&scorecards);
+            if trend {
+                print_trend(&player, &stats, last);
+                return Ok(());
+            }
             
             println!("📊 Statistics for {}", player.name);
             println!("   ⛳️ Total rounds: {}", stats.total_rounds);
             println!("   🏁 Completed rounds: {}", stats.completed_rounds);
 
             if let Some(avg) = stats.average_score {
-                println!("   ⚖️ Average score: {:.2}", avg);
+                match stats.average_net_score {
+                    Some(net) => println!("   ⚖️ Average score: {:.2} (net {:.2})", avg, net),
+                    None => println!("   ⚖️ Average score: {:.2}", avg),
+                }
             }
             
             if let Some(best) = stats.best_score {
-                println!("   🏆 Best score: {}", best);
+                match stats.best_net_score {
+                    Some(net) => println!("   🏆 Best score: {} (net {})", best, net),
+                    None => println!("   🏆 Best score: {}", best),
+                }
             }
             
             if let Some(worst) = stats.worst_score {
                 println!("   🆘 Worst score: {}", worst);
             }
 
+            if let Some(index) = stats.handicap_index {
+                println!("   🎯 Handicap index: {:.1}", index);
+            }
+
+            let has_detail = [stats.putts_per_round, stats.gir_percentage, stats.fairway_percentage,
+                stats.scrambling_percentage, stats.sand_save_percentage].iter().any(Option::is_some);
+            if has_detail || stats.penalty_strokes > 0 {
+                println!("\n   Shot Detail:");
+                if let Some(putts) = stats.putts_per_round {
+                    println!("      Putts per round: {:.1}", putts);
+                }
+                if let Some(gir) = stats.gir_percentage {
+                    println!("      Greens in regulation: {:.1}%", gir);
+                }
+                if let Some(fairways) = stats.fairway_percentage {
+                    println!("      Fairways hit: {:.1}% (missed {} left, {} right)",
+                        fairways, stats.fairways_missed_left, stats.fairways_missed_right);
+                }
+                if let Some(scrambling) = stats.scrambling_percentage {
+                    println!("      Scrambling: {:.1}%", scrambling);
+                }
+                if let Some(sand) = stats.sand_save_percentage {
+                    println!("      Sand saves: {:.1}%", sand);
+                }
+                println!("      Penalty strokes: {}", stats.penalty_strokes);
+                println!();
+            }
+
             println!("   Total under par: {}", stats.total_under_par);
             println!("   Total over par: {}", stats.total_over_par);
-            println!("\n   Hole Performance:");
-            println!("      Eagles: {}", stats.eagles);
-            println!("      Birdies: {}", stats.birdies);
-            println!("      Pars: {}", stats.pars);
-            println!("      Bogeys: {}", stats.bogeys);
-            println!("      Double bogeys+: {}", stats.double_bogeys);
+            if stats.net_rounds > 0 {
+                println!("\n   Hole Performance:   Gross    Net ({} rounds)", stats.net_rounds);
+                println!("      Eagles:         {:>5}  {:>5}", stats.eagles, stats.net_eagles);
+                println!("      Birdies:        {:>5}  {:>5}", stats.birdies, stats.net_birdies);
+                println!("      Pars:           {:>5}  {:>5}", stats.pars, stats.net_pars);
+                println!("      Bogeys:         {:>5}  {:>5}", stats.bogeys, stats.net_bogeys);
+                println!("      Double bogeys+: {:>5}  {:>5}", stats.double_bogeys, stats.net_double_bogeys);
+            } else {
+                println!("\n   Hole Performance:");
+                println!("      Eagles: {}", stats.eagles);
+                println!("      Birdies: {}", stats.birdies);
+                println!("      Pars: {}", stats.pars);
+                println!("      Bogeys: {}", stats.bogeys);
+                println!("      Double bogeys+: {}", stats.double_bogeys);
+            }
+        }
+
+        Commands::Handicap { player, update } => {
+            let mut player = repo.find_player(&player)?;
+            let player_id = player.id;
+            let scorecards = repo.get_scorecards_by_player(&player_id)
+                .context("Failed to retrieve scorecards")?;
+
+            let calculation = HandicapCalculation::from_scorecards(&scorecards);
+            print_handicap(&player, &calculation);
+
+            if update {
+                if let Some(index) = calculation.handicap_index {
+                    player.handicap = Some(index);
+                    repo.save_player(&player).context("Failed to save player")?;
+                    println!("\n💾 Saved handicap {:.1} for {}", index, player.name);
+                } else {
+                    println!("\nNo index to save yet");
+                }
+            }
+        }
+
+        Commands::Export { output, format } => {
+            let format = format
+                .or_else(|| output.as_deref().and_then(ExportFormat::from_path))
+                .unwrap_or_default();
+            let bundle = Bundle::from_repository(repo.as_ref()).context("Failed to read data for export")?;
+            match &output {
+                Some(path) => {
+                    let file = std::fs::File::create(path)
+                        .with_context(|| format!("Failed to create {}", path.display()))?;
+                    bundle.write(format, std::io::BufWriter::new(file)).context("Failed to write export")?;
+                    println!("📦 Exported {} players and {} scorecards to {}",
+                        bundle.players.len(), bundle.scorecards.len(), path.display());
+                }
+                None => bundle.write(format, std::io::stdout().lock()).context("Failed to write export")?,
+            }
+        }
+
+        Commands::Import { file, format, on_duplicate } => {
+            let format = format
+                .or_else(|| ExportFormat::from_path(&file))
+                .ok_or_else(|| anyhow::anyhow!("Cannot tell the format of {}, pass --format json or --format csv", file.display()))?;
+            let reader = std::fs::File::open(&file)
+                .with_context(|| format!("Failed to open {}", file.display()))?;
+            let bundle = Bundle::read(format, std::io::BufReader::new(reader))
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let report = bundle.import_into(repo.as_mut(), on_duplicate)?;
+            println!("📥 Imported {} players and {} scorecards from {}: {} added, {} merged, {} skipped",
+                bundle.players.len(), bundle.scorecards.len(), file.display(),
+                report.added, report.merged, report.skipped);
+        }
+
+        Commands::ImportRounds { file, player, app, mapping } => {
+            let player = repo.find_player(&player)?;
+            let player_id = player.id;
+            let mapping = match &mapping {
+                Some(path) => ColumnMapping::load(path)
+                    .with_context(|| format!("Failed to read column mapping {}", path.display()))?,
+                None => app.mapping(),
+            };
+            let mut catalog = CourseCatalog::load(&data_dir).context("Failed to load course catalog")?;
+            let reader = std::fs::File::open(&file)
+                .with_context(|| format!("Failed to open {}", file.display()))?;
+            let import = import_app_rounds(std::io::BufReader::new(reader), &mapping, player_id, &mut catalog)
+                .with_context(|| format!("Failed to import {}", file.display()))?;
+
+            // Rounds already imported from an earlier copy of the file
+            let existing = repo.get_scorecards_by_player(&player_id)
+                .context("Failed to retrieve scorecards")?;
+            let (mut imported, mut skipped) = (0, 0);
+            for mut scorecard in import.scorecards {
+                let duplicate = existing.iter().any(|card| {
+                    card.played_at == scorecard.played_at
+                        && card.course == scorecard.course
+                        && card.scores() == scorecard.scores()
+                });
+                if duplicate {
+                    skipped += 1;
+                    continue;
+                }
+                if let Some(index) = player.handicap {
+                    scorecard = scorecard.with_handicap_index(index);
+                }
+                repo.save_scorecard(&scorecard).context("Failed to save scorecard")?;
+                imported += 1;
+            }
+
+            for course in &import.created_courses {
+                println!("🆕 Added course {} to the catalog", course);
+            }
+            println!("📥 Imported {} rounds for {} from {}", imported, player.name, file.display());
+            if skipped > 0 {
+                println!("   {} rounds were already imported and skipped", skipped);
+            }
+            if !import.rejected.is_empty() {
+                println!("\n⚠️  {} rows were not imported:", import.rejected.len());
+                for row in &import.rejected {
+                    println!("   Line {}: {}", row.line, row.reason);
+                }
+            }
+        }
+
+        Commands::Tui => tui::run(repo.as_ref()).context("Terminal UI failed")?,
+
+        Commands::Serve { host, port, allow_origin } => {
+            let server = tiny_http::Server::http((host.as_str(), port))
+                .map_err(|error| anyhow::anyhow!("Could not listen on {}:{}: {}", host, port, error))?;
+            println!("🌐 Serving {} on http://{}", data_dir.display(), server.server_addr());
+            println!("   OpenAPI description: http://{}/openapi.json (Ctrl+C to stop)", server.server_addr());
+            let mut api = Api::new(repo.as_mut(), &data_dir);
+            server::serve(&mut api, &server, allow_origin.as_deref());
+        }
+
+        Commands::Repair => unreachable!("repair runs before the repository is opened"),
+        Commands::Profile { .. } => unreachable!("profiles are managed before the repository is opened"),
+    }
+
+    Ok(())
+}
+
+/// Runs a `profile` subcommand against the config file at `config_path`.
+fn profile(mut config: Config, config_path: Option<PathBuf>, active: Option<DataDir>, command: ProfileCommand) -> Result<()> {
+    let config_path = config_path
+        .ok_or_else(|| anyhow::anyhow!("No config directory found. Set {} to the config file to use", CONFIG_ENV))?;
+    match command {
+        ProfileCommand::List => {
+            println!("Config file: {}", config_path.display());
+            if config.profiles.is_empty() {
+                println!("No profiles configured");
+            }
+            for (name, profile) in &config.profiles {
+                let default = if config.default_profile.as_ref() == Some(name) { " (default)" } else { "" };
+                println!("  {}{} - {}", name, default, profile.data_dir.display());
+            }
+            if let Some(active) = active {
+                println!("Data directory in use: {} ({})", active.path.display(), active.source);
+            }
+        }
+
+        ProfileCommand::Add { name, data_dir, default } => {
+            let data_dir = std::path::absolute(&data_dir).context("Failed to resolve data directory")?;
+            config.add_profile(&name, data_dir.clone())?;
+            if default {
+                config.default_profile = Some(name.clone());
+            }
+            config.save(&config_path).context("Failed to save config")?;
+            println!("Profile {} uses {}", name, data_dir.display());
+        }
+
+        ProfileCommand::Remove { name } => {
+            let removed = config.remove_profile(&name)?;
+            config.save(&config_path).context("Failed to save config")?;
+            println!("Profile {} removed; its data is still in {}", name, removed.data_dir.display());
+        }
+    }
+    Ok(())
+}
+
+/// Runs a `tournament` subcommand.
+fn tournament(repo: &mut dyn Repository, data_dir: &Path, command: TournamentCommand) -> Result<()> {
+    match command {
+        TournamentCommand::Create { name, rounds, courses, cut_after, cut_top } => {
+            if courses.len() != 1 && courses.len() != usize::from(rounds) {
+                anyhow::bail!("Give one --course for every round or {} courses, one per round", rounds);
+            }
+            let catalog = CourseCatalog::load(data_dir).context("Failed to load course catalog")?;
+            let mut round_courses = Vec::with_capacity(usize::from(rounds));
+            for round in 0..usize::from(rounds) {
+                let name = &courses[round.min(courses.len() - 1)];
+                round_courses.push(&catalog.find(name)?.course);
+            }
+
+            let mut tournament = Tournament::new(name, &round_courses)?;
+            if let (Some(after_round), Some(top)) = (cut_after, cut_top) {
+                tournament = tournament.with_cut(CutRule { after_round, top })?;
+            }
+            repo.save_tournament(&tournament).context("Failed to save tournament")?;
+
+            println!("🏆 Tournament {} created (ID: {})", tournament.name, tournament.id);
+            for (round, course) in (1..).zip(&tournament.courses) {
+                println!("   Round {}: {}", round, course);
+            }
+            if let Some(cut) = tournament.cut {
+                println!("   Cut after round {}: top {} and ties", cut.after_round, cut.top);
+            }
+        }
+
+        TournamentCommand::Join { tournament_id, players } => {
+            let mut tournament = load_tournament(repo, &tournament_id)?;
+            for name in &players {
+                let player = repo.find_player(name)?;
+                tournament.join(player.id)?;
+                println!("✅ {} joined {}", player.name, tournament.name);
+            }
+            repo.save_tournament(&tournament).context("Failed to save tournament")?;
+        }
+
+        TournamentCommand::StartRound { tournament_id, round, tee } => {
+            let mut tournament = load_tournament(repo, &tournament_id)?;
+            let catalog = CourseCatalog::load(data_dir).context("Failed to load course catalog")?;
+            let course = &catalog.find(tournament.course_for(round)?)?.course;
+            let tee = match &tee {
+                Some(name) => course.tee(name)?,
+                None => course.default_tee(),
+            };
+
+            let leaderboard = Leaderboard::new(&tournament, &tournament_scorecards(repo, &tournament)?);
+            if let Some(cut) = tournament.cut
+                && round > cut.after_round
+                && !leaderboard.cut_applied
+            {
+                anyhow::bail!("Round {} can't start until everyone has finished round {} and the cut is made",
+                    round, cut.after_round);
+            }
+
+            let mut started = Vec::new();
+            for player_id in tournament.field.clone() {
+                if !leaderboard.made_cut(&player_id) || tournament.scorecard_id_for(round, &player_id).is_some() {
+                    continue;
+                }
+                let mut scorecard = tournament.start_round(round, player_id, course, tee)?;
+                let handicap = repo.get_player(&player_id)
+                    .context("Failed to get player")?
+                    .and_then(|player| player.handicap);
+                if let Some(index) = handicap {
+                    scorecard = scorecard.with_handicap_index(index);
+                }
+                repo.save_scorecard(&scorecard).context("Failed to save scorecard")?;
+                started.push(scorecard);
+            }
+            repo.save_tournament(&tournament).context("Failed to save tournament")?;
+
+            println!("⛳️ Round {} of {} on {} from the {} tees", round, tournament.name, course.name, tee.name);
+            for scorecard in &started {
+                let name = repo.get_player(&scorecard.player_id)
+                    .context("Failed to get player")?
+                    .map_or_else(|| scorecard.player_id.to_string(), |player| player.name);
+                println!("   {} - Scorecard {}", name, scorecard.round_id);
+            }
+            if started.is_empty() {
+                println!("   Every player still in the field already has a scorecard");
+            }
+        }
+
+        TournamentCommand::Leaderboard { tournament_id } => {
+            let tournament = load_tournament(repo, &tournament_id)?;
+            let leaderboard = Leaderboard::new(&tournament, &tournament_scorecards(repo, &tournament)?);
+            print_leaderboard(repo, &tournament, &leaderboard)?;
+        }
+
+        TournamentCommand::List => {
+            let tournaments = repo.list_tournaments().context("Failed to list tournaments")?;
+            if tournaments.is_empty() {
+                println!("No tournaments found");
+            } else {
+                println!("🏆 Tournaments:");
+                for tournament in tournaments {
+                    println!("  {} {} - {} rounds, {} players",
+                        tournament.id, tournament.name, tournament.round_count(), tournament.field.len());
+                }
+            }
         }
     }
+    Ok(())
+}
+
+fn load_tournament(repo: &dyn Repository, id: &Uuid) -> Result<Tournament> {
+    repo.get_tournament(id)
+        .context("Failed to get tournament")?
+        .ok_or_else(|| anyhow::anyhow!("Tournament {} not found", id))
+}
+
+/// Loads every scorecard entered in a tournament.
+fn tournament_scorecards(repo: &dyn Repository, tournament: &Tournament) -> Result<Vec<Scorecard>> {
+    let mut scorecards = Vec::with_capacity(tournament.entries.len());
+    for entry in &tournament.entries {
+        let scorecard = repo.get_scorecard(&entry.scorecard_id)
+            .context("Failed to get scorecard")?
+            .ok_or(GolfError::RoundNotFound(entry.scorecard_id))?;
+        scorecards.push(scorecard);
+    }
+    Ok(scorecards)
+}
 
+/// Prints a tournament leaderboard with a column per round.
+fn print_leaderboard(repo: &dyn Repository, tournament: &Tournament, leaderboard: &Leaderboard) -> Result<()> {
+    println!("🏆 {} leaderboard", tournament.name);
+    let rounds: String = (1..=tournament.round_count()).map(|round| format!(" {:>4}", format!("R{}", round))).collect();
+    println!("\n   {:<4} {:<20} {:>5} {:>4}{} {:>5}", "Pos", "Player", "Par", "Thru", rounds, "Tot");
+
+    let mut cut_line_shown = false;
+    for row in &leaderboard.rows {
+        if row.missed_cut && !cut_line_shown {
+            println!("   {}", "-".repeat(45 + rounds.len()));
+            cut_line_shown = true;
+        }
+        let name = repo.get_player(&row.player_id)
+            .context("Failed to get player")?
+            .map_or_else(|| row.player_id.to_string(), |player| player.name);
+        let position = match row.position {
+            Some(position) => position.to_string(),
+            None if row.missed_cut => "CUT".to_string(),
+            None => "-".to_string(),
+        };
+        let to_par = match (row.thru, row.to_par) {
+            (Thru::NotStarted, _) => "-".to_string(),
+            (_, 0) => "E".to_string(),
+            (_, to_par) => format!("{:+}", to_par),
+        };
+        let scores: String = row.rounds.iter()
+            .map(|strokes| format!(" {:>4}", strokes.map_or_else(|| "-".to_string(), |s| s.to_string())))
+            .collect();
+        println!("   {:<4} {:<20} {:>5} {:>4}{} {:>5}", position, name, to_par, row.thru.to_string(), scores, row.total_strokes);
+    }
+    if let Some(cut) = tournament.cut && !leaderboard.cut_applied {
+        println!("\n   Cut after round {}: top {} and ties", cut.after_round, cut.top);
+    }
     Ok(())
 }
+
+/// Loads a group round with each player and their scorecard, in the order
+/// the players were added.
+fn load_group(repo: &dyn Repository, round_id: &Uuid) -> Result<(Round, Vec<(Player, Scorecard)>)> {
+    let round = repo.get_round(round_id)
+        .context("Failed to get round")?
+        .ok_or_else(|| anyhow::anyhow!("Round {} not found", round_id))?;
+
+    let mut group = Vec::with_capacity(round.entries.len());
+    for entry in &round.entries {
+        let scorecard = repo.get_scorecard(&entry.scorecard_id)
+            .context("Failed to get scorecard")?
+            .ok_or(GolfError::RoundNotFound(entry.scorecard_id))?;
+        let player = repo.get_player(&entry.player_id)
+            .context("Failed to get player")?
+            .ok_or_else(|| anyhow::anyhow!("Player {} not found", entry.player_id))?;
+        group.push((player, scorecard));
+    }
+    Ok((round, group))
+}
+
+/// Prints the standings, match status or skins won for a scored round.
+fn print_format_result(result: &FormatResult) {
+    match result {
+        FormatResult::StrokePlay(standings) => {
+            for (position, standing) in standings.iter().enumerate() {
+                println!("   {:>2}. {:<20} {:>4} ({}) thru {}", position + 1, standing.name, standing.score,
+                    if standing.to_par == 0 { "E".to_string() } else { format!("{:+}", standing.to_par) }, standing.thru);
+            }
+        }
+        FormatResult::Stableford(standings) => {
+            for (position, standing) in standings.iter().enumerate() {
+                println!("   {:>2}. {:<20} {:>3} pts thru {}", position + 1, standing.name, standing.score, standing.thru);
+            }
+        }
+        FormatResult::MatchPlay(result) => {
+            let holes: Vec<String> = result.hole_winners.iter()
+                .map(|winner| winner.map_or_else(|| "-".to_string(), |player| result.players[player].chars().take(1).collect()))
+                .collect();
+            if !holes.is_empty() {
+                println!("   Holes: {}", holes.join(" "));
+            }
+            println!("   {}", result.summary());
+        }
+        FormatResult::Skins(result) => {
+            for hole in &result.holes {
+                match hole.winner {
+                    Some(player) => println!("   Hole {:>2}: {} wins {} skin(s)", hole.hole, result.players[player], hole.value),
+                    None => println!("   Hole {:>2}: tied, carried over", hole.hole),
+                }
+            }
+            println!();
+            for (name, skins) in result.players.iter().zip(result.totals()) {
+                println!("   {:<20} {} skin(s)", name, skins);
+            }
+            if result.carryover > 0 {
+                println!("   {} skin(s) carrying over", result.carryover);
+            }
+        }
+    }
+}
+
+/// Prints every score change on a scorecard for `history`, oldest first.
+fn print_history(scorecard: &Scorecard) {
+    println!("🕘 History of round {}", scorecard.round_id);
+    if scorecard.history().is_empty() {
+        println!("   No scores recorded yet");
+        return;
+    }
+    let undone: Vec<usize> = scorecard.history().iter().filter_map(|event| event.undoes).collect();
+    let show = |strokes: Option<u8>| strokes.map_or_else(|| "-".to_string(), |s| s.to_string());
+    for (position, event) in scorecard.history().iter().enumerate() {
+        let mut note = match event.undoes {
+            Some(undoes) => format!("undo of #{}", undoes + 1),
+            None if event.previous.is_some() => "changed".to_string(),
+            None => "recorded".to_string(),
+        };
+        if undone.contains(&position) {
+            note.push_str(", undone");
+        }
+        if let Some(reason) = &event.reason {
+            note = format!("{}: {}", note, reason);
+        }
+        println!("   #{:<3} {}  hole {:>2}  {:>2} -> {:<2}  {}", position + 1, event.at.format("%Y-%m-%d %H:%M:%S"),
+            event.hole, show(event.previous), show(event.strokes), note);
+    }
+}
+
+/// Prints the scoring trend view of `stats --trend`.
+fn print_trend(player: &Player, stats: &PlayerStatistics, last: usize) {
+    let signed = |value: f64| if value.abs() < 0.05 { "E".to_string() } else { format!("{:+.1}", value) };
+
+    println!("📈 Scoring trend for {} ({} completed rounds)", player.name, stats.history.len());
+    if stats.history.is_empty() {
+        println!("   No completed rounds yet");
+        return;
+    }
+
+    let to_par: Vec<f64> = stats.history.iter().map(|round| f64::from(round.to_par)).collect();
+    let (first, latest) = (&stats.history[0], &stats.history[stats.history.len() - 1]);
+    println!("\n   To par          {}  {} to {}", sparkline(&to_par),
+        first.played_at.format("%Y-%m-%d"), latest.played_at.format("%Y-%m-%d"));
+    println!("   Last {:<2} average {}  {}", last, sparkline(&stats.moving_averages(last)),
+        stats.moving_average(last).map_or_else(String::new, signed));
+    println!("   (taller bars are higher scores)");
+
+    if let Some(slope) = stats.improvement_per_round {
+        let direction = match slope {
+            s if s < -0.05 => "improving",
+            s if s > 0.05 => "getting worse",
+            _ => "steady",
+        };
+        println!("\n   Trend: {:+.2} strokes per round ({})", slope, direction);
+    }
+    if let Some(std_dev) = stats.score_std_dev {
+        println!("   Consistency: {:.2} strokes standard deviation", std_dev);
+    }
+
+    let par_averages = [(3, stats.par3_average), (4, stats.par4_average), (5, stats.par5_average)];
+    if par_averages.iter().any(|(_, average)| average.is_some()) {
+        println!("\n   Scoring average by par:");
+        for (par, average) in par_averages {
+            if let Some(average) = average {
+                println!("      Par {}: {:.2} ({})", par, average, signed(average - f64::from(par)));
+            }
+        }
+    }
+
+    for (course, holes) in &stats.hole_averages {
+        println!("\n   {} hole averages:", course);
+        for (hole, average) in holes {
+            println!("      {:>2}: {:.2}", hole, average);
+        }
+    }
+}
+
+/// Prints a handicap index together with the scoring record it came from.
+fn print_handicap(player: &Player, calculation: &HandicapCalculation) {
+    match calculation.handicap_index {
+        Some(index) => println!("🎯 Handicap index for {}: {:.1}", player.name, index),
+        None => println!("🎯 {} needs at least 3 completed 18-hole rounds for a handicap index", player.name),
+    }
+    if calculation.skipped_rounds > 0 {
+        println!("   ({} incomplete or 9-hole rounds skipped)", calculation.skipped_rounds);
+    }
+    if calculation.differentials.is_empty() {
+        return;
+    }
+
+    println!("\n   Scoring record (* = counted):");
+    println!("     {:<10}  {:>5}  {:>8}  {:>12}  {:>6}", "Date", "Gross", "Adjusted", "Rating/Slope", "Diff");
+    for diff in &calculation.differentials {
+        println!("   {} {:<10}  {:>5}  {:>8}  {:>7.1}/{:<4}  {:>6.1}",
+            if diff.counted { "*" } else { " " },
+            diff.played_at.format("%Y-%m-%d"),
+            diff.gross_score,
+            diff.adjusted_gross_score,
+            diff.rating.course_rating,
+            diff.rating.slope_rating,
+            diff.differential);
+    }
+
+    if let Some(uncapped) = calculation.uncapped_index {
+        println!("\n   Average of lowest {} differentials{}: {:.1}",
+            calculation.differentials_counted,
+            if calculation.adjustment != 0.0 { format!(" with {:+.1} adjustment", calculation.adjustment) } else { String::new() },
+            uncapped);
+    }
+    if let Some(low) = calculation.low_index {
+        println!("   Low index over the last 365 days: {:.1}", low);
+    }
+    if calculation.hard_cap_applied {
+        println!("   Hard cap applied: index limited to 5.0 above the low index");
+    } else if calculation.soft_cap_applied {
+        println!("   Soft cap applied: increase beyond 3.0 above the low index halved");
+    }
+}
+
+/// Builds a course from the per-hole lists given to `add-course`.
+///
+/// Yardages and stroke indexes are optional, but when given must list every
+/// hole. Without any `--tee` the course gets a single unrated tee.
+fn course_from_args(name: &str, pars: &[u8], yardages: &[u16], stroke_indexes: &[u8], tees: Vec<TeeSet>) -> Result<Course> {
+    let max_holes = u8::try_from(pars.len())
+        .map_err(|_| anyhow::anyhow!("A course can have at most {} holes", u8::MAX))?;
+    for (option, len) in [("--yardages", yardages.len()), ("--stroke-indexes", stroke_indexes.len())] {
+        if len != 0 && len != pars.len() {
+            anyhow::bail!("{} lists {} holes but --pars lists {}", option, len, pars.len());
+        }
+    }
+
+    let mut holes = Vec::with_capacity(pars.len());
+    for (number, &par) in (1..=max_holes).zip(pars) {
+        let mut hole = Hole::new(number, par, max_holes)?;
+        if let Some(&yardage) = yardages.get(usize::from(number) - 1) {
+            hole = hole.with_yardage(yardage);
+        }
+        if let Some(&stroke_index) = stroke_indexes.get(usize::from(number) - 1) {
+            hole = hole.with_stroke_index(stroke_index);
+        }
+        holes.push(hole);
+    }
+
+    let tees = if tees.is_empty() {
+        vec![TeeSet::unrated("Standard", pars.iter().copied().map(u16::from).sum())]
+    } else {
+        tees
+    };
+    Ok(Course::new(name, holes, tees)?)
+}
+
+/// Prints a course's holes and tees.
+fn print_course(course: &Course) {
+    println!("⛳️ {} - {} holes, par {}", course.name, course.hole_count(), course.total_par());
+    println!("\n   {:>4}  {:>3}  {:>5}  {:>3}", "Hole", "Par", "Yards", "SI");
+    for hole in &course.holes {
+        println!("   {:>4}  {:>3}  {:>5}  {:>3}",
+            hole.number,
+            hole.par,
+            hole.yardage.map_or_else(|| "-".to_string(), |yards| yards.to_string()),
+            hole.stroke_index.map_or_else(|| "-".to_string(), |si| si.to_string()));
+    }
+    if let Some(yards) = course.total_yardage() {
+        println!("   {:>4}  {:>3}  {:>5}", "Tot", course.total_par(), yards);
+    }
+
+    println!("\n   Tees:");
+    for tee in &course.tees {
+        println!("     {:<12} {:>5.1}/{}", tee.name, tee.course_rating, tee.slope_rating);
+    }
+}
+
+/// Repairs the file repository in `data_dir`.
+///
+/// Runs before the repository is opened for normal use, since an open
+/// repository holds the directory lock that repairing needs.
+fn repair(data_dir: PathBuf, cli: &Cli) -> Result<()> {
+    if cli.backend != Backend::File {
+        anyhow::bail!("repair only applies to the file backend");
+    }
+
+    let report = repair_file_repository(data_dir, cli.storage_format)
+        .context("Failed to repair repository")?;
+
+    if report.is_clean() {
+        println!("✅ Repository is healthy, nothing to repair");
+        return Ok(());
+    }
+    for path in &report.removed_temp_files {
+        println!("🧹 Removed interrupted write {}", path.display());
+    }
+    for path in &report.quarantined {
+        println!("🚧 Quarantined unreadable file to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Finds the scorecard a new score should be written to.
+///
+/// An explicit `round` may name either a scorecard or a group round the
+/// player is in. Either way the scorecard must exist and belong to the
+/// player; completed scorecards are returned as-is so that
+/// `Scorecard::record_score` can refuse the write. Without one, the player
+/// must have exactly one scorecard still in progress.
+fn find_open_scorecard(repo: &dyn Repository, player_id: &Uuid, round: Option<Uuid>) -> Result<Scorecard> {
+    if let Some(round_id) = round {
+        let scorecard_id = match repo.get_round(&round_id).context("Failed to get round")? {
+            Some(group) => group.scorecard_id_for(player_id)
+                .ok_or_else(|| anyhow::anyhow!("Player {} is not in round {}", player_id, round_id))?,
+            None => round_id,
+        };
+        let scorecard = repo.get_scorecard(&scorecard_id)
+            .context("Failed to get scorecard")?
+            .ok_or(GolfError::RoundNotFound(round_id))?;
+        if &scorecard.player_id != player_id {
+            anyhow::bail!("Round {} does not belong to player {}", round_id, player_id);
+        }
+        return Ok(scorecard);
+    }
+
+    let mut open: Vec<Scorecard> = repo.get_scorecards_by_player(player_id)
+        .context("Failed to get scorecards for player")?
+        .into_iter()
+        .filter(|sc| !sc.is_complete())
+        .collect();
+
+    match open.len() {
+        0 => anyhow::bail!("Player {} has no open scorecard. Create one with `create-scorecard`", player_id),
+        1 => Ok(open.remove(0)),
+        _ => {
+            let rounds: Vec<String> = open.iter().map(|sc| sc.round_id.to_string()).collect();
+            anyhow::bail!("Player {} has {} open scorecards, pick one with --round:\n  {}",
+                player_id, open.len(), rounds.join("\n  "))
+        }
+    }
+}
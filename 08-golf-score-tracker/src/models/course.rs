@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GolfError, Result};
+use crate::models::Hole;
+
+/// Difficulty rating of a set of tees, as published by a golf association.
+///
+/// * `course_rating` - Expected score of a scratch golfer (e.g. 72.4)
+/// * `slope_rating` - Relative difficulty for a bogey golfer, 55 to 155,
+///   where 113 is a course of standard difficulty
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct CourseRating {
+    pub course_rating: f64,
+    pub slope_rating: u16,
+}
+
+impl CourseRating {
+    /// Slope rating of a course of standard difficulty.
+    pub const STANDARD_SLOPE: u16 = 113;
+
+    pub fn new(course_rating: f64, slope_rating: u16) -> Result<Self> {
+        if !(55..=155).contains(&slope_rating) {
+            return Err(GolfError::custom(format!(
+                "Slope rating {} is invalid. Must be between 55 and 155.",
+                slope_rating
+            )));
+        }
+        if !course_rating.is_finite() || course_rating <= 0.0 {
+            return Err(GolfError::custom(format!("Course rating {} is invalid", course_rating)));
+        }
+        Ok(Self { course_rating, slope_rating })
+    }
+
+    /// Rating assumed for an unrated course: scratch golfers shoot par and
+    /// the slope is standard.
+    pub fn unrated(total_par: u16) -> Self {
+        Self { course_rating: f64::from(total_par), slope_rating: Self::STANDARD_SLOPE }
+    }
+}
+
+/// A set of tees on a course, with the rating published for playing from
+/// them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeeSet {
+    pub name: String,
+    pub course_rating: f64,
+    pub slope_rating: u16,
+}
+
+impl TeeSet {
+    pub fn new(name: impl AsRef<str>, course_rating: f64, slope_rating: u16) -> Result<Self> {
+        let name = name.as_ref().trim();
+        if name.is_empty() {
+            return Err(GolfError::custom("Tee name cannot be empty"));
+        }
+        let rating = CourseRating::new(course_rating, slope_rating)?;
+        Ok(Self { name: name.to_string(), course_rating: rating.course_rating, slope_rating: rating.slope_rating })
+    }
+
+    /// Tees on an unrated course, see [`CourseRating::unrated`].
+    pub fn unrated(name: impl AsRef<str>, total_par: u16) -> Self {
+        let rating = CourseRating::unrated(total_par);
+        Self { name: name.as_ref().trim().to_string(), course_rating: rating.course_rating, slope_rating: rating.slope_rating }
+    }
+
+    pub fn rating(&self) -> CourseRating {
+        CourseRating { course_rating: self.course_rating, slope_rating: self.slope_rating }
+    }
+}
+
+/// A golf course: its holes in playing order and the tees it can be played
+/// from.
+///
+/// Stroke indexes are optional, but when one hole has one every hole must,
+/// and together they must rank the holes from 1 to the number of holes.
+/// Holes without a stroke index are ranked in playing order.
+///
+/// # Examples
+///
+/// ```
+/// use golf_score_tracker::models::{Course, Hole, TeeSet};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let holes = vec![
+///     Hole::new(1, 4, 3)?.with_yardage(380).with_stroke_index(2),
+///     Hole::new(2, 3, 3)?.with_yardage(165).with_stroke_index(3),
+///     Hole::new(3, 5, 3)?.with_yardage(510).with_stroke_index(1),
+/// ];
+/// let course = Course::new("Pitch & Putt", holes, vec![TeeSet::new("White", 11.8, 104)?])?;
+/// assert_eq!(course.total_par(), 12);
+/// assert_eq!(course.total_yardage(), Some(1055));
+/// assert_eq!(course.tee("white")?.slope_rating, 104);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Course {
+    pub name: String,
+    pub holes: Vec<Hole>,
+    pub tees: Vec<TeeSet>,
+    /// Other names the course can be looked up by, e.g. "masters".
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+impl Course {
+    pub fn new(name: impl AsRef<str>, holes: Vec<Hole>, tees: Vec<TeeSet>) -> Result<Self> {
+        let course = Self { name: name.as_ref().trim().to_string(), holes, tees, aliases: Vec::new() };
+        course.validate()?;
+        Ok(course)
+    }
+
+    /// Sets the other names the course can be looked up by.
+    pub fn with_aliases<S: AsRef<str>>(mut self, aliases: impl IntoIterator<Item = S>) -> Self {
+        self.aliases = aliases.into_iter().map(|alias| alias.as_ref().trim().to_string()).collect();
+        self
+    }
+
+    /// Checks the invariants `new` enforces, for courses that were
+    /// deserialized rather than constructed.
+    pub fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(GolfError::custom("Course name cannot be empty"));
+        }
+        if self.holes.is_empty() || self.holes.len() > usize::from(u8::MAX) {
+            return Err(GolfError::custom(format!(
+                "Course '{}' must have between 1 and {} holes",
+                self.name,
+                u8::MAX
+            )));
+        }
+
+        let max_holes = self.hole_count();
+        for (expected, hole) in (1..=max_holes).zip(&self.holes) {
+            if hole.number != expected {
+                return Err(GolfError::custom(format!(
+                    "Course '{}' lists hole {} where hole {} was expected",
+                    self.name, hole.number, expected
+                )));
+            }
+            Hole::new(hole.number, hole.par, max_holes)?;
+        }
+
+        let indexes: Vec<u8> = self.holes.iter().filter_map(|hole| hole.stroke_index).collect();
+        if !indexes.is_empty() {
+            let mut sorted = indexes.clone();
+            sorted.sort_unstable();
+            if indexes.len() != self.holes.len() || !sorted.iter().copied().eq(1..=max_holes) {
+                return Err(GolfError::custom(format!(
+                    "Stroke indexes on '{}' must rank every hole exactly once from 1 to {}",
+                    self.name, max_holes
+                )));
+            }
+        }
+
+        if self.tees.is_empty() {
+            return Err(GolfError::custom(format!("Course '{}' needs at least one set of tees", self.name)));
+        }
+        for (i, tee) in self.tees.iter().enumerate() {
+            TeeSet::new(&tee.name, tee.course_rating, tee.slope_rating)?;
+            if self.tees[..i].iter().any(|other| other.name.eq_ignore_ascii_case(&tee.name)) {
+                return Err(GolfError::custom(format!(
+                    "Course '{}' lists the {} tees twice",
+                    self.name, tee.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn hole_count(&self) -> u8 {
+        self.holes.len() as u8
+    }
+
+    pub fn hole(&self, number: u8) -> Option<&Hole> {
+        self.holes.iter().find(|hole| hole.number == number)
+    }
+
+    /// Par for every hole, keyed by hole number.
+    pub fn pars(&self) -> BTreeMap<u8, u8> {
+        self.holes.iter().map(|hole| (hole.number, hole.par)).collect()
+    }
+
+    pub fn total_par(&self) -> u16 {
+        self.holes.iter().map(|hole| u16::from(hole.par)).sum()
+    }
+
+    /// Total length of the course, if every hole has a yardage.
+    pub fn total_yardage(&self) -> Option<u32> {
+        self.holes.iter().map(|hole| hole.yardage.map(u32::from)).sum()
+    }
+
+    /// Stroke index for every hole, keyed by hole number.
+    pub fn stroke_indexes(&self) -> BTreeMap<u8, u8> {
+        self.holes
+            .iter()
+            .map(|hole| (hole.number, hole.stroke_index.unwrap_or(hole.number)))
+            .collect()
+    }
+
+    /// Looks up a set of tees by name, ignoring case.
+    pub fn tee(&self, name: &str) -> Result<&TeeSet> {
+        self.tees
+            .iter()
+            .find(|tee| tee.name.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| GolfError::custom(format!(
+                "Course '{}' has no {} tees. Available tees: {}",
+                self.name,
+                name.trim(),
+                self.tees.iter().map(|tee| tee.name.as_str()).collect::<Vec<_>>().join(", ")
+            )))
+    }
+
+    /// The tees listed first, used when none are chosen explicitly.
+    pub fn default_tee(&self) -> &TeeSet {
+        &self.tees[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holes(stroke_indexes: &[Option<u8>]) -> Vec<Hole> {
+        let max_holes = stroke_indexes.len() as u8;
+        (1..=max_holes)
+            .zip(stroke_indexes)
+            .map(|(number, &stroke_index)| Hole { stroke_index, ..Hole::new(number, 4, max_holes).expect("hole") })
+            .collect()
+    }
+
+    fn tees() -> Vec<TeeSet> {
+        vec![TeeSet::new("Blue", 35.1, 124).expect("tee")]
+    }
+
+    #[test]
+    fn missing_stroke_indexes_follow_playing_order() {
+        let course = Course::new("Nine", holes(&[None; 9]), tees()).expect("course");
+        assert_eq!(course.stroke_indexes(), (1..=9).map(|h| (h, h)).collect());
+        assert_eq!(course.total_yardage(), None);
+    }
+
+    #[test]
+    fn stroke_indexes_must_rank_every_hole_once() {
+        assert!(Course::new("Dupes", holes(&[Some(1), Some(1), Some(3)]), tees()).is_err());
+        assert!(Course::new("Partial", holes(&[Some(1), None, Some(2)]), tees()).is_err());
+        assert!(Course::new("Out of range", holes(&[Some(1), Some(2), Some(4)]), tees()).is_err());
+        assert!(Course::new("Ranked", holes(&[Some(3), Some(1), Some(2)]), tees()).is_ok());
+    }
+
+    #[test]
+    fn holes_must_be_numbered_in_order() {
+        let mut out_of_order = holes(&[None; 3]);
+        out_of_order.swap(0, 1);
+        assert!(Course::new("Shuffled", out_of_order, tees()).is_err());
+    }
+
+    #[test]
+    fn tees_are_required_and_unique() {
+        assert!(Course::new("Bare", holes(&[None; 3]), vec![]).is_err());
+        let twice = vec![TeeSet::new("Blue", 35.1, 124).expect("tee"), TeeSet::new("blue", 34.0, 120).expect("tee")];
+        assert!(Course::new("Twice", holes(&[None; 3]), twice).is_err());
+    }
+
+    #[test]
+    fn unknown_tee_lists_the_available_ones() {
+        let course = Course::new("Nine", holes(&[None; 9]), tees()).expect("course");
+        let message = course.tee("Red").unwrap_err().to_string();
+        assert!(message.contains("Blue"), "{}", message);
+    }
+}
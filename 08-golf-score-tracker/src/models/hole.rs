@@ -3,16 +3,37 @@ use serde ::{Deserialize, Serialize};
 use crate::error::Result;
 use crate::utils::validators::{validate_hole_number, validate_par};
 
+/// A hole on a course.
+///
+/// * `yardage` - Length of the hole, when known
+/// * `stroke_index` - Handicap ranking of the hole, 1 being the hardest.
+///   Decides where handicap strokes are received.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Hole {
     pub number: u8,
-    pub par: u8
+    pub par: u8,
+    #[serde(default)]
+    pub yardage: Option<u16>,
+    #[serde(default)]
+    pub stroke_index: Option<u8>,
 }
 
 impl Hole {
     pub fn new(number: u8, par:u8, max_holes: u8) -> Result<Self> {
         validate_hole_number(number, max_holes)?;
         validate_par(par)?;
-        Ok(Self { number, par })
+        Ok(Self { number, par, yardage: None, stroke_index: None })
     }
-}
\ No newline at end of file
+
+    /// Sets the length of the hole.
+    pub fn with_yardage(mut self, yardage: u16) -> Self {
+        self.yardage = Some(yardage);
+        self
+    }
+
+    /// Sets the handicap stroke index of the hole.
+    pub fn with_stroke_index(mut self, stroke_index: u8) -> Self {
+        self.stroke_index = Some(stroke_index);
+        self
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GolfError, Result};
+
+/// Where the tee shot on a par 4 or par 5 finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Fairway {
+    Hit,
+    Left,
+    Right,
+}
+
+impl Fairway {
+    pub fn name(self) -> &'static str {
+        match self {
+            Fairway::Hit => "hit",
+            Fairway::Left => "left",
+            Fairway::Right => "right",
+        }
+    }
+}
+
+impl fmt::Display for Fairway {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Fairway {
+    type Err = GolfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "hit" | "h" => Ok(Fairway::Hit),
+            "left" | "l" => Ok(Fairway::Left),
+            "right" | "r" => Ok(Fairway::Right),
+            _ => Err(GolfError::custom(format!("Unknown fairway result '{}'. Expected hit, left or right", s))),
+        }
+    }
+}
+
+/// Shot detail for a single hole, recorded alongside the stroke count.
+///
+/// Every field is optional so that players can track as much or as little
+/// as they like; scorecards saved before this detail existed load with
+/// none of it.
+///
+/// * `putts` - Putts taken on the green
+/// * `fairway` - Tee shot result on par 4s and 5s
+/// * `sand_save` - `Some(true)` when up and down from a greenside bunker,
+///   `Some(false)` when in one but not saved
+/// * `penalties` - Penalty strokes, already included in the hole's strokes
+///
+/// # Examples
+///
+/// ```
+/// use golf_score_tracker::models::{Fairway, HoleResult};
+///
+/// let result = HoleResult { putts: Some(2), fairway: Some(Fairway::Hit), ..HoleResult::default() };
+/// // Par in 4 with 2 putts: on the green in 2
+/// assert_eq!(result.green_in_regulation(4, 4), Some(true));
+/// assert_eq!(result.green_in_regulation(5, 4), Some(false));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HoleResult {
+    #[serde(default)]
+    pub putts: Option<u8>,
+    #[serde(default)]
+    pub fairway: Option<Fairway>,
+    #[serde(default)]
+    pub sand_save: Option<bool>,
+    #[serde(default)]
+    pub penalties: u8,
+}
+
+impl HoleResult {
+    /// True when no detail at all was recorded.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the green was reached in regulation (par minus two strokes),
+    /// derived from the putts taken. `None` when putts were not recorded.
+    pub fn green_in_regulation(&self, strokes: u8, par: u8) -> Option<bool> {
+        self.putts.map(|putts| strokes.saturating_sub(putts) + 2 <= par)
+    }
+}
@@ -1,11 +1,17 @@
+pub mod course;
 pub mod player;
 pub mod hole;
+pub mod hole_result;
 pub mod round;
 pub mod scorecard;
 pub mod statistics;
+pub mod tournament;
 
+pub use course::{Course, CourseRating, TeeSet};
 pub use player::Player;
 pub use hole::Hole;
-pub use round::Round;
-pub use scorecard::Scorecard;
-pub use statistics::PlayerStatistics;
\ No newline at end of file
+pub use hole_result::{Fairway, HoleResult};
+pub use round::{Round, RoundEntry};
+pub use scorecard::{ScoreEvent, Scorecard};
+pub use statistics::{PlayerStatistics, RoundScore};
+pub use tournament::{CutRule, Tournament, TournamentEntry};
\ No newline at end of file
@@ -2,21 +2,113 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::error::{GolfError, Result};
+use crate::models::{Course, Scorecard, TeeSet};
+
+/// A player's place in a round, linking them to their scorecard.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RoundEntry {
+    pub player_id: Uuid,
+    pub scorecard_id: Uuid,
+}
+
+/// A group of players on the same course and date, each with their own
+/// scorecard.
+///
+/// The round only stores which scorecard belongs to which player, in the
+/// order the players were added; the scorecards themselves are saved
+/// separately.
+///
+/// # Examples
+///
+/// ```
+/// use golf_score_tracker::models::Round;
+/// use golf_score_tracker::utils::get_course;
+/// use uuid::Uuid;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let course = get_course("St Andrews")?;
+/// let mut round = Round::new(&course);
+/// let (rory, shane) = (Uuid::new_v4(), Uuid::new_v4());
+/// let rory_card = round.add_player(rory, &course, course.default_tee())?;
+/// round.add_player(shane, &course, course.default_tee())?;
+///
+/// assert_eq!(round.player_ids(), vec![rory, shane]);
+/// assert_eq!(rory_card.played_at, round.date);
+/// assert!(round.add_player(rory, &course, course.default_tee()).is_err());
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Round {
     pub id: Uuid,
     pub date: DateTime<Utc>,
-    pub course: String,    
-    pub max_holes: u8
+    pub course: String,
+    pub max_holes: u8,
+    #[serde(default)]
+    pub entries: Vec<RoundEntry>,
 }
 
 impl Round {
-    pub fn new(course: impl AsRef<str>, max_holes: u8) -> Self {
+    /// Starts a round on `course`, recording its name and number of holes.
+    pub fn new(course: &Course) -> Self {
         Self {
             id: Uuid::new_v4(),
             date: Utc::now(),
-            course: course.as_ref().trim().to_string(),
-            max_holes
+            course: course.name.clone(),
+            max_holes: course.hole_count(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Sets when the round was played.
+    pub fn with_date(mut self, date: DateTime<Utc>) -> Self {
+        self.date = date;
+        self
+    }
+
+    /// Creates a scorecard for `player_id` on this round's course and date,
+    /// adds it to the round and returns it for saving.
+    pub fn add_player(&mut self, player_id: Uuid, course: &Course, tee: &TeeSet) -> Result<Scorecard> {
+        let scorecard = Scorecard::new(player_id, course, tee)?.with_played_at(self.date);
+        self.add_scorecard(&scorecard)?;
+        Ok(scorecard)
+    }
+
+    /// Adds an existing scorecard to the round.
+    ///
+    /// The scorecard must be for the round's course and number of holes,
+    /// and each player can only be in a round once.
+    pub fn add_scorecard(&mut self, scorecard: &Scorecard) -> Result<()> {
+        if scorecard.max_holes != self.max_holes
+            || scorecard.course.as_deref().is_some_and(|course| course != self.course)
+        {
+            return Err(GolfError::custom(format!(
+                "Scorecard {} is not for the {} holes of {}",
+                scorecard.round_id, self.max_holes, self.course
+            )));
+        }
+        if self.includes_player(&scorecard.player_id) {
+            return Err(GolfError::custom(format!(
+                "Player {} is already in round {}",
+                scorecard.player_id, self.id
+            )));
         }
+        self.entries.push(RoundEntry { player_id: scorecard.player_id, scorecard_id: scorecard.round_id });
+        Ok(())
     }
-}
\ No newline at end of file
+
+    pub fn includes_player(&self, player_id: &Uuid) -> bool {
+        self.entries.iter().any(|entry| &entry.player_id == player_id)
+    }
+
+    /// Scorecard of a player in this round, if they are in it.
+    pub fn scorecard_id_for(&self, player_id: &Uuid) -> Option<Uuid> {
+        self.entries.iter().find(|entry| &entry.player_id == player_id).map(|entry| entry.scorecard_id)
+    }
+
+    /// Players in the order they were added.
+    pub fn player_ids(&self) -> Vec<Uuid> {
+        self.entries.iter().map(|entry| entry.player_id).collect()
+    }
+}
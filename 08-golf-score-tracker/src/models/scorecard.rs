@@ -1,21 +1,106 @@
 use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::error::Result;
-use crate::utils::validators::{validate_hole_number, validate_par, validate_score};
+use crate::error::{GolfError, Result};
+use crate::models::{Course, CourseRating, HoleResult, TeeSet};
+use crate::services::handicap::{course_handicap, strokes_received};
+use crate::utils::validators::{validate_hole_number, validate_hole_result, validate_par, validate_score};
 
+/// One change to a hole's score, kept in a scorecard's
+/// [`history`](Scorecard::history).
+///
+/// Recording, correcting and undoing scores all add an event; nothing is
+/// ever removed from the history, so it shows every value a hole has had.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScoreEvent {
+    pub hole: u8,
+    /// Strokes before the change, `None` if the hole had no score.
+    pub previous: Option<u8>,
+    /// Strokes after the change, `None` if an undo removed the score.
+    pub strokes: Option<u8>,
+    #[serde(alias = "corrected_at")]
+    pub at: DateTime<Utc>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Position in the history of the event this one undid.
+    #[serde(default)]
+    pub undoes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Scorecard {
     pub round_id: Uuid,
     pub player_id: Uuid,
     pub max_holes: u8,
     scores: BTreeMap<u8, u8>,
     pars: BTreeMap<u8, u8>,
+    /// When the round was played. Scorecards saved before this was
+    /// recorded load as the Unix epoch, so they sort as the oldest rounds.
+    #[serde(default)]
+    pub played_at: DateTime<Utc>,
+    /// Rating and slope of the tees played, when known. Handicap
+    /// calculations treat unrated rounds as par with a standard slope.
+    #[serde(default)]
+    pub rating: Option<CourseRating>,
+    /// Name of the course played, when the scorecard was created from one.
+    #[serde(default)]
+    pub course: Option<String>,
+    /// Name of the tees played, when the scorecard was created from a course.
+    #[serde(default)]
+    pub tee: Option<String>,
+    /// Course handicap the player had for this round. Handicap strokes are
+    /// allocated from it by stroke index for net scoring; without one the
+    /// card is only scored gross.
+    #[serde(default)]
+    pub course_handicap: Option<i32>,
+    /// Handicap stroke index of each hole. Empty for scorecards that were
+    /// not created from a course, which rank holes in playing order.
+    #[serde(default)]
+    stroke_indexes: BTreeMap<u8, u8>,
+    /// Putts, fairway and penalty detail for holes where any was recorded.
+    #[serde(default)]
+    results: BTreeMap<u8, HoleResult>,
+    /// Every change to a score, oldest first.
+    #[serde(default, alias = "corrections")]
+    history: Vec<ScoreEvent>,
 }
 
 impl Scorecard {
-    pub fn new(player_id: Uuid, max_holes: u8, pars: BTreeMap<u8, u8>) -> Result<Self> {
+    /// Creates a scorecard for a round on `course`, played from `tee`.
+    ///
+    /// The layout, stroke indexes and tee rating are copied onto the
+    /// scorecard, so later edits to the course do not change rounds that
+    /// were already played.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::Scorecard;
+    /// use golf_score_tracker::utils::get_course;
+    /// use uuid::Uuid;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let course = get_course("Pebble Beach")?;
+    /// let scorecard = Scorecard::new(Uuid::new_v4(), &course, course.tee("Blue")?)?;
+    /// assert_eq!(scorecard.tee.as_deref(), Some("Blue"));
+    /// assert_eq!(scorecard.stroke_index(8), Some(1));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(player_id: Uuid, course: &Course, tee: &TeeSet) -> Result<Self> {
+        let mut scorecard = Self::from_pars(player_id, course.hole_count(), course.pars())?;
+        scorecard.rating = Some(tee.rating());
+        scorecard.course = Some(course.name.clone());
+        scorecard.tee = Some(tee.name.clone());
+        scorecard.stroke_indexes = course.stroke_indexes();
+        Ok(scorecard)
+    }
+
+    /// Creates a scorecard from a bare par layout, without a course, tees
+    /// or stroke indexes.
+    pub fn from_pars(player_id: Uuid, max_holes: u8, pars: BTreeMap<u8, u8>) -> Result<Self> {
         for (&hole, &to_par) in &pars {
             validate_hole_number(hole, max_holes)?;
             validate_par(to_par)?;
@@ -26,17 +111,342 @@ impl Scorecard {
             max_holes,
             scores: BTreeMap::new(),
             pars,
+            played_at: Utc::now(),
+            rating: None,
+            course: None,
+            tee: None,
+            course_handicap: None,
+            stroke_indexes: BTreeMap::new(),
+            results: BTreeMap::new(),
+            history: Vec::new(),
         })
     }
 
+    /// Sets the rating of the tees played.
+    pub fn with_rating(mut self, rating: CourseRating) -> Self {
+        self.rating = Some(rating);
+        self
+    }
+
+    /// Sets the course handicap used for net scoring.
+    pub fn with_course_handicap(mut self, course_handicap: i32) -> Self {
+        self.course_handicap = Some(course_handicap);
+        self
+    }
+
+    /// Sets the course handicap for a player with `handicap_index` on the
+    /// tees this scorecard was created for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::Scorecard;
+    /// use golf_score_tracker::utils::get_course;
+    /// use uuid::Uuid;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let course = get_course("Pebble Beach")?;
+    /// // 10.0 x 143 / 113 + (74.7 - 72), rounded
+    /// let scorecard = Scorecard::new(Uuid::new_v4(), &course, course.tee("Blue")?)?.with_handicap_index(10.0);
+    /// assert_eq!(scorecard.course_handicap, Some(15));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_handicap_index(self, handicap_index: f64) -> Self {
+        let course_handicap = course_handicap(handicap_index, self.effective_rating(), self.total_par());
+        self.with_course_handicap(course_handicap)
+    }
+
+    /// Sets when the round was played.
+    pub fn with_played_at(mut self, played_at: DateTime<Utc>) -> Self {
+        self.played_at = played_at;
+        self
+    }
+
+    /// Records the strokes taken on a hole.
+    ///
+    /// Re-recording a hole overwrites the previous value while the round is
+    /// still in progress; the change is kept in the [`history`](Self::history)
+    /// and can be reverted with [`undo`](Self::undo). Once every hole has a score the scorecard is
+    /// complete and further writes are refused with
+    /// `GolfError::ScorecardComplete`.
     pub fn record_score(&mut self, hole: u8, strokes:u8) -> Result<()> {
+        self.record_hole(hole, strokes, HoleResult::default())
+    }
+
+    /// Records the strokes taken on a hole together with shot detail.
+    ///
+    /// Behaves like `record_score`; re-recording a hole replaces both the
+    /// strokes and any detail recorded before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::Scorecard;
+    /// use golf_score_tracker::models::{Fairway, HoleResult};
+    /// use golf_score_tracker::utils::create_standard_pars;
+    /// use uuid::Uuid;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut scorecard = Scorecard::from_pars(Uuid::new_v4(), 9, create_standard_pars(9))?;
+    /// let result = HoleResult { putts: Some(1), fairway: Some(Fairway::Left), ..HoleResult::default() };
+    /// scorecard.record_hole(1, 4, result)?;
+    /// assert_eq!(scorecard.hole_result(1), Some(&result));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn record_hole(&mut self, hole: u8, strokes: u8, result: HoleResult) -> Result<()> {
+        if self.is_complete() {
+            return Err(GolfError::ScorecardComplete(self.round_id));
+        }
         validate_hole_number(hole, self.max_holes)?;
         let par = *self.pars.get(&hole).expect("par must exist for each hole");
         validate_score(strokes, hole, par)?;
+        validate_hole_result(&result, strokes, hole, par)?;
+        let previous = self.scores.insert(hole, strokes);
+        if previous != Some(strokes) {
+            self.push_event(hole, previous, Some(strokes), None, None);
+        }
+        if result.is_empty() {
+            self.results.remove(&hole);
+        } else {
+            self.results.insert(hole, result);
+        }
+        Ok(())
+    }
+
+    /// Changes the strokes already recorded on a hole, even on a complete
+    /// scorecard, and keeps the change and its reason in the history.
+    ///
+    /// Use this to fix a mis-entered score; `record_score` is for scoring
+    /// holes as they are played. Shot detail recorded for the hole is kept
+    /// and must still fit the new score.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::Scorecard;
+    /// use golf_score_tracker::utils::create_standard_pars;
+    /// use uuid::Uuid;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut scorecard = Scorecard::from_pars(Uuid::new_v4(), 9, create_standard_pars(9))?;
+    /// for hole in 1..=9 {
+    ///     scorecard.record_score(hole, 4)?;
+    /// }
+    /// assert!(scorecard.record_score(3, 5).is_err());
+    ///
+    /// scorecard.correct_score(3, 5, Some("Marker wrote a 4"))?;
+    /// assert_eq!(scorecard.get_score(3), Some(5));
+    /// assert_eq!(scorecard.history().last().unwrap().previous, Some(4));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn correct_score(&mut self, hole: u8, strokes: u8, reason: Option<&str>) -> Result<()> {
+        validate_hole_number(hole, self.max_holes)?;
+        let previous = self.get_score(hole).ok_or_else(|| {
+            GolfError::custom(format!("Hole {} has no score to correct yet. Record it instead.", hole))
+        })?;
+        let par = self.pars[&hole];
+        validate_score(strokes, hole, par)?;
+        if let Some(result) = self.results.get(&hole) {
+            validate_hole_result(result, strokes, hole, par)?;
+        }
+        if strokes == previous {
+            return Ok(());
+        }
+
         self.scores.insert(hole, strokes);
+        self.push_event(hole, Some(previous), Some(strokes), reason, None);
+        Ok(())
+    }
+
+    /// Reverts the most recent change that has not been undone yet,
+    /// returning the event that was reverted.
+    ///
+    /// The undo is itself added to the history, so calling this again
+    /// reverts the change before. Undoing a hole's first score removes it
+    /// along with its shot detail; other detail is kept if it still fits
+    /// the restored score. Like `correct_score`, this works on complete
+    /// scorecards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::Scorecard;
+    /// use golf_score_tracker::utils::create_standard_pars;
+    /// use uuid::Uuid;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut scorecard = Scorecard::from_pars(Uuid::new_v4(), 9, create_standard_pars(9))?;
+    /// scorecard.record_score(1, 4)?;
+    /// scorecard.record_score(1, 7)?;
+    ///
+    /// scorecard.undo(None)?;
+    /// assert_eq!(scorecard.get_score(1), Some(4));
+    /// scorecard.undo(Some("Wrong card"))?;
+    /// assert_eq!(scorecard.get_score(1), None);
+    /// assert!(scorecard.undo(None).is_err());
+    /// assert_eq!(scorecard.history().len(), 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn undo(&mut self, reason: Option<&str>) -> Result<ScoreEvent> {
+        let position = self.last_change().ok_or_else(|| {
+            GolfError::custom(format!("Scorecard {} has no changes to undo", self.round_id))
+        })?;
+        let event = self.history[position].clone();
+        match event.previous {
+            Some(previous) => {
+                self.scores.insert(event.hole, previous);
+                let par = self.pars[&event.hole];
+                if let Some(result) = self.results.get(&event.hole)
+                    && validate_hole_result(result, previous, event.hole, par).is_err()
+                {
+                    self.results.remove(&event.hole);
+                }
+            }
+            None => {
+                self.scores.remove(&event.hole);
+                self.results.remove(&event.hole);
+            }
+        }
+        self.push_event(event.hole, event.strokes, event.previous, reason, Some(position));
+        Ok(event)
+    }
+
+    /// Position of the latest change that [`undo`](Self::undo) would revert.
+    pub fn last_change(&self) -> Option<usize> {
+        let undone: Vec<usize> = self.history.iter().filter_map(|event| event.undoes).collect();
+        (0..self.history.len())
+            .rev()
+            .find(|position| self.history[*position].undoes.is_none() && !undone.contains(position))
+    }
+
+    /// Every change made to this scorecard's scores, oldest first.
+    pub fn history(&self) -> &[ScoreEvent] {
+        &self.history
+    }
+
+    /// Restores the history for backends that rebuild scorecards with
+    /// `from_parts`.
+    pub(crate) fn set_history(&mut self, history: Vec<ScoreEvent>) {
+        self.history = history;
+    }
+
+    fn push_event(&mut self, hole: u8, previous: Option<u8>, strokes: Option<u8>, reason: Option<&str>, undoes: Option<usize>) {
+        self.history.push(ScoreEvent {
+            hole,
+            previous,
+            strokes,
+            at: Utc::now(),
+            reason: reason.map(str::trim).filter(|reason| !reason.is_empty()).map(String::from),
+            undoes,
+        });
+    }
+
+    /// Rebuilds a scorecard from previously persisted parts.
+    ///
+    /// Storage backends that do not go through serde use this to restore a
+    /// scorecard; the same validation as `from_pars` and `record_score`
+    /// applies.
+    pub(crate) fn from_parts(
+        round_id: Uuid,
+        player_id: Uuid,
+        max_holes: u8,
+        pars: BTreeMap<u8, u8>,
+        scores: BTreeMap<u8, u8>,
+    ) -> Result<Self> {
+        let mut scorecard = Self::from_pars(player_id, max_holes, pars)?;
+        scorecard.round_id = round_id;
+        for (&hole, &strokes) in &scores {
+            validate_hole_number(hole, max_holes)?;
+            let par = scorecard.get_par(hole).ok_or(GolfError::InvalidHole { hole, max_holes })?;
+            validate_score(strokes, hole, par)?;
+        }
+        scorecard.scores = scores;
+        Ok(scorecard)
+    }
+
+    /// Updates this scorecard from `other`, another copy of the same round.
+    /// The holes scored on `other` replace the ones here, shot detail
+    /// included, and so do its date, rating, course, tees, course handicap
+    /// and stroke indexes. Holes only scored here are kept.
+    ///
+    /// This scorecard's history is kept, and every hole whose strokes change
+    /// adds an event with `reason`, so merging never loses the record of how
+    /// a score came about. Both scorecards must have the same layout.
+    pub fn merge(&mut self, other: &Scorecard, reason: Option<&str>) -> Result<()> {
+        if self.max_holes != other.max_holes || self.pars != other.pars {
+            return Err(GolfError::custom(format!(
+                "Scorecard {} cannot be merged with a scorecard for a different layout",
+                self.round_id
+            )));
+        }
+        self.played_at = other.played_at;
+        self.rating = other.rating;
+        self.course = other.course.clone();
+        self.tee = other.tee.clone();
+        self.course_handicap = other.course_handicap;
+        self.stroke_indexes = other.stroke_indexes.clone();
+        for (&hole, &strokes) in &other.scores {
+            let previous = self.scores.insert(hole, strokes);
+            if previous != Some(strokes) {
+                self.push_event(hole, previous, Some(strokes), reason, None);
+            }
+            match other.results.get(&hole) {
+                Some(result) => self.results.insert(hole, *result),
+                None => self.results.remove(&hole),
+            };
+        }
         Ok(())
     }
 
+    /// Par for every hole, keyed by hole number.
+    pub fn pars(&self) -> &BTreeMap<u8, u8> {
+        &self.pars
+    }
+
+    /// Stroke index of every hole, keyed by hole number. Empty when the
+    /// scorecard was not created from a course.
+    pub fn stroke_indexes(&self) -> &BTreeMap<u8, u8> {
+        &self.stroke_indexes
+    }
+
+    /// Restores stroke indexes for backends that rebuild scorecards with
+    /// `from_parts`.
+    pub(crate) fn set_stroke_indexes(&mut self, stroke_indexes: BTreeMap<u8, u8>) {
+        self.stroke_indexes = stroke_indexes;
+    }
+
+    /// Shot detail recorded for a hole, if any.
+    pub fn hole_result(&self, hole: u8) -> Option<&HoleResult> {
+        self.results.get(&hole)
+    }
+
+    /// Restores shot detail for backends that rebuild scorecards with
+    /// `from_parts`.
+    pub(crate) fn set_hole_results(&mut self, results: BTreeMap<u8, HoleResult>) {
+        self.results = results;
+    }
+
+    /// Whether the green on a hole was hit in regulation, when putts were
+    /// recorded for it.
+    pub fn green_in_regulation(&self, hole: u8) -> Option<bool> {
+        let (strokes, par) = (self.get_score(hole)?, self.get_par(hole)?);
+        self.hole_result(hole)?.green_in_regulation(strokes, par)
+    }
+
+    /// Stroke index of a hole, if the scorecard has stroke indexes.
+    pub fn stroke_index(&self, hole: u8) -> Option<u8> {
+        self.stroke_indexes.get(&hole).copied()
+    }
+
+    /// Strokes recorded so far, keyed by hole number.
+    pub fn scores(&self) -> &BTreeMap<u8, u8> {
+        &self.scores
+    }
+
     pub fn get_par (&self, hole: u8) -> Option<u8> {
         self.pars.get(&hole).copied()
     }
@@ -57,8 +467,65 @@ impl Scorecard {
         if !self.is_complete() {
             return None;
         }
-        let total_par: u16 = self.pars.values().copied().map(u16::from).sum();
         let total_strokes: u16 = self.scores.values().copied().map(u16::from).sum();
-        Some(total_strokes as i16 - total_par as i16)
+        Some(total_strokes as i16 - self.total_par() as i16)
+    }
+
+    /// Handicap strokes received on `hole`: the course handicap allocated
+    /// by stroke index, or 0 without a course handicap. Plus handicaps give
+    /// strokes back, which is negative.
+    pub fn handicap_strokes(&self, hole: u8) -> i16 {
+        self.course_handicap.map_or(0, |course_handicap| {
+            let stroke_index = self.stroke_index(hole).unwrap_or(hole);
+            strokes_received(course_handicap, stroke_index, self.max_holes) as i16
+        })
+    }
+
+    /// Strokes on `hole` less the handicap strokes received there.
+    pub fn net_score(&self, hole: u8) -> Option<i16> {
+        self.get_score(hole).map(|strokes| i16::from(strokes) - self.handicap_strokes(hole))
+    }
+
+    /// Net strokes for the round, once complete.
+    pub fn net_total(&self) -> Option<i16> {
+        self.is_complete().then(|| self.scores.keys().filter_map(|&hole| self.net_score(hole)).sum())
+    }
+
+    /// Net strokes relative to par, once complete.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::Scorecard;
+    /// use golf_score_tracker::utils::create_standard_pars;
+    /// use uuid::Uuid;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut scorecard = Scorecard::from_pars(Uuid::new_v4(), 9, create_standard_pars(9))?
+    ///     .with_course_handicap(5);
+    /// for hole in 1..=9 {
+    ///     scorecard.record_score(hole, scorecard.get_par(hole).unwrap() + 1)?;
+    /// }
+    /// assert_eq!(scorecard.score_relative_to_par(), Some(9));
+    /// assert_eq!(scorecard.net_relative_to_par(), Some(4));
+    /// // Strokes go to the five lowest stroke indexes, here holes 1 to 5
+    /// assert_eq!(scorecard.net_score(5), Some(3));
+    /// assert_eq!(scorecard.net_score(6), Some(6));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn net_relative_to_par(&self) -> Option<i16> {
+        self.net_total().map(|net| net - self.total_par() as i16)
+    }
+
+    /// Sum of par over every hole on the card.
+    pub fn total_par(&self) -> u16 {
+        self.pars.values().copied().map(u16::from).sum()
+    }
+
+    /// Rating used for handicap purposes, falling back to an unrated
+    /// course when none was recorded.
+    pub fn effective_rating(&self) -> CourseRating {
+        self.rating.unwrap_or_else(|| CourseRating::unrated(self.total_par()))
     }
 }
\ No newline at end of file
@@ -19,7 +19,7 @@
 //!     pars.insert(hole, 4);
 //! }
 //! 
-//! let mut scorecard = Scorecard::new(player.id, 9, pars)?;
+//! let mut scorecard = Scorecard::from_pars(player.id, 9, pars)?;
 //! for hole in 1..=9 {
 //!     scorecard.record_score(hole, 4)?;
 //! }
@@ -31,7 +31,23 @@
 //! # Ok(())
 //! # }
 //! ```
-use crate::models::Scorecard;
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::{Fairway, Scorecard};
+use crate::services::HandicapCalculation;
+
+/// A completed round in a player's scoring history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RoundScore {
+    pub round_id: Uuid,
+    pub played_at: DateTime<Utc>,
+    pub strokes: u16,
+    pub to_par: i16,
+}
 
 /// Comprehensive statistics for a player's golf performance.
 ///
@@ -52,8 +68,36 @@ use crate::models::Scorecard;
 /// * `pars` - Number of holes played at par
 /// * `bogeys` - Number of holes played 1 stroke over par
 /// * `double_bogeys` - Number of holes played 2+ strokes over par
+/// * `net_rounds` - Completed rounds with a course handicap, which the net
+///   statistics below are calculated from
+/// * `average_net_score` / `best_net_score` - Net strokes after handicap
+///   strokes are deducted
+/// * `net_eagles` ... `net_double_bogeys` - Hole scores relative to par after
+///   the handicap strokes received on each hole
+/// * `handicap_index` - World Handicap System index, `None` with fewer than 3
+///   completed 18-hole rounds (see `HandicapCalculation` for the derivation)
+/// * `putts_per_round` - Mean putts over completed rounds with putts recorded
+///   on every hole
+/// * `gir_percentage` - Greens hit in regulation, out of holes with putts recorded
+/// * `fairway_percentage` - Fairways hit, out of holes with a fairway result
+/// * `fairways_missed_left` / `fairways_missed_right` - Missed fairways by side
+/// * `scrambling_percentage` - Par or better after missing the green in regulation
+/// * `sand_save_percentage` - Up and downs from greenside bunkers
+/// * `penalty_strokes` - Total penalty strokes recorded
+/// * `history` - Completed rounds, oldest first by `played_at`
+/// * `score_std_dev` - Standard deviation of scores relative to par, a
+///   measure of consistency; `None` with fewer than 2 completed rounds
+/// * `improvement_per_round` - Slope of a least-squares line through the
+///   scores relative to par in the order played. Negative means improving;
+///   `None` with fewer than 2 completed rounds
+/// * `par3_average` / `par4_average` / `par5_average` - Mean strokes on holes
+///   of each par
+/// * `hole_averages` - Mean strokes on each hole, by course name, for
+///   rounds played on a named course
 ///
-#[derive(Debug, Clone)]
+/// The shot detail and trend statistics only cover completed rounds, and
+/// are `None` when no hole has the detail they need.
+#[derive(Debug, Clone, Serialize)]
 pub struct PlayerStatistics {
 pub total_rounds: usize,
     pub completed_rounds: usize,
@@ -66,7 +110,31 @@ pub total_rounds: usize,
     pub birdies: usize,
     pub pars: usize,
     pub bogeys: usize,
-    pub double_bogeys: usize
+    pub double_bogeys: usize,
+    pub net_rounds: usize,
+    pub average_net_score: Option<f64>,
+    pub best_net_score: Option<i16>,
+    pub net_eagles: usize,
+    pub net_birdies: usize,
+    pub net_pars: usize,
+    pub net_bogeys: usize,
+    pub net_double_bogeys: usize,
+    pub handicap_index: Option<f64>,
+    pub putts_per_round: Option<f64>,
+    pub gir_percentage: Option<f64>,
+    pub fairway_percentage: Option<f64>,
+    pub fairways_missed_left: usize,
+    pub fairways_missed_right: usize,
+    pub scrambling_percentage: Option<f64>,
+    pub sand_save_percentage: Option<f64>,
+    pub penalty_strokes: usize,
+    pub history: Vec<RoundScore>,
+    pub score_std_dev: Option<f64>,
+    pub improvement_per_round: Option<f64>,
+    pub par3_average: Option<f64>,
+    pub par4_average: Option<f64>,
+    pub par5_average: Option<f64>,
+    pub hole_averages: BTreeMap<String, BTreeMap<u8, f64>>,
 }
 
 impl PlayerStatistics {
@@ -87,9 +155,11 @@ impl PlayerStatistics {
     ///
     /// # Performance
     ///
-    /// This method makes multiple passes over the scorecard data using
-    /// iterator chains. For large datasets (1000+ rounds), consider
-    /// caching the result rather than recalculating frequently.    
+    /// The trend analytics (the round history behind `score_std_dev`,
+    /// `improvement_per_round` and the moving averages, the par 3/4/5
+    /// averages and `hole_averages`) are gathered in a single pass over the
+    /// completed scorecards. For large datasets (1000+ rounds), consider
+    /// caching the result rather than recalculating frequently.
     pub fn from_scorecards( scorecards: &[Scorecard]) -> Self {
         let total_rounds = scorecards.len();
 
@@ -108,7 +178,21 @@ impl PlayerStatistics {
         let total_under_par = relative_scores.iter().filter(|&&score| score < 0).map(|&score| score as i32).sum();
         let total_over_par = relative_scores.iter().filter(|&&score| score > 0).map(|&score| score as i32).sum();
 
-        let (eagles, birdies, pars, bogeys, double_bogeys) = Self::calculate_hole_statistics(&completed_scorecards);
+        let (eagles, birdies, pars, bogeys, double_bogeys) =
+            Self::calculate_hole_statistics(&completed_scorecards, |card, hole| card.get_score(hole).map(i16::from));
+
+        let net_scorecards: Vec<&Scorecard> =
+            completed_scorecards.iter().copied().filter(|x| x.course_handicap.is_some()).collect();
+        let net_rounds = net_scorecards.len();
+        let net_totals: Vec<i16> = net_scorecards.iter().filter_map(|x| x.net_total()).collect();
+        let average_net_score = (net_rounds > 0)
+            .then(|| net_totals.iter().map(|&total| f64::from(total)).sum::<f64>() / net_rounds as f64);
+        let best_net_score = net_totals.iter().copied().min();
+        let (net_eagles, net_birdies, net_pars, net_bogeys, net_double_bogeys) =
+            Self::calculate_hole_statistics(&net_scorecards, |card, hole| card.net_score(hole));
+        let handicap_index = HandicapCalculation::from_scorecards(scorecards).handicap_index;
+        let detail = ShotDetail::from_scorecards(&completed_scorecards);
+        let trend = ScoringTrend::from_scorecards(&completed_scorecards);
 
         Self {
             total_rounds,
@@ -122,12 +206,88 @@ impl PlayerStatistics {
             birdies,
             pars,
             bogeys,
-            double_bogeys
+            double_bogeys,
+            net_rounds,
+            average_net_score,
+            best_net_score,
+            net_eagles,
+            net_birdies,
+            net_pars,
+            net_bogeys,
+            net_double_bogeys,
+            handicap_index,
+            putts_per_round: detail.putts_per_round(),
+            gir_percentage: percentage(detail.greens_hit, detail.greens_known),
+            fairway_percentage: percentage(detail.fairways_hit, detail.fairways_known),
+            fairways_missed_left: detail.fairways_missed_left,
+            fairways_missed_right: detail.fairways_missed_right,
+            scrambling_percentage: percentage(detail.scrambles_made, detail.scramble_chances),
+            sand_save_percentage: percentage(detail.sand_saves_made, detail.sand_save_chances),
+            penalty_strokes: detail.penalty_strokes,
+            score_std_dev: trend.std_dev(),
+            improvement_per_round: trend.slope(),
+            par3_average: trend.par_average(3),
+            par4_average: trend.par_average(4),
+            par5_average: trend.par_average(5),
+            hole_averages: trend.hole_averages(),
+            history: trend.history,
         }
     }
 
-    
-    fn calculate_hole_statistics(scorecards: &[&Scorecard]) -> (usize, usize, usize, usize, usize) {
+    /// Mean score relative to par over the most recent `rounds` completed
+    /// rounds, or over all of them when there are fewer.
+    pub fn moving_average(&self, rounds: usize) -> Option<f64> {
+        let recent = &self.history[self.history.len().saturating_sub(rounds)..];
+        (rounds > 0 && !recent.is_empty())
+            .then(|| recent.iter().map(|round| f64::from(round.to_par)).sum::<f64>() / recent.len() as f64)
+    }
+
+    /// The moving average of scores relative to par after each round in
+    /// the history, over a window of up to `rounds` rounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::{PlayerStatistics, Scorecard};
+    /// use golf_score_tracker::utils::create_standard_pars;
+    /// use chrono::{Duration, Utc};
+    /// use uuid::Uuid;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let player_id = Uuid::new_v4();
+    /// let mut rounds = Vec::new();
+    /// for (day, over) in [6, 4, 2, 0].into_iter().enumerate() {
+    ///     let mut card = Scorecard::from_pars(player_id, 9, create_standard_pars(9))?
+    ///         .with_played_at(Utc::now() - Duration::days(10 - day as i64));
+    ///     for hole in 1..=9 {
+    ///         card.record_score(hole, card.get_par(hole).unwrap() + u8::from(hole <= over))?;
+    ///     }
+    ///     rounds.push(card);
+    /// }
+    ///
+    /// let stats = PlayerStatistics::from_scorecards(&rounds);
+    /// assert_eq!(stats.moving_averages(2), vec![6.0, 5.0, 3.0, 1.0]);
+    /// assert_eq!(stats.moving_average(3), Some(2.0));
+    /// assert_eq!(stats.improvement_per_round, Some(-2.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn moving_averages(&self, rounds: usize) -> Vec<f64> {
+        let window = rounds.max(1);
+        (1..=self.history.len())
+            .map(|end| {
+                let recent = &self.history[end.saturating_sub(window)..end];
+                recent.iter().map(|round| f64::from(round.to_par)).sum::<f64>() / recent.len() as f64
+            })
+            .collect()
+    }
+
+    /// Counts holes by score relative to par, where `score` gives the
+    /// gross or net strokes on a hole.
+    fn calculate_hole_statistics(
+        scorecards: &[&Scorecard],
+        score: impl Fn(&Scorecard, u8) -> Option<i16>,
+    ) -> (usize, usize, usize, usize, usize) {
         let mut eagles = 0;
         let mut birdies = 0;
         let mut pars = 0;
@@ -136,16 +296,14 @@ impl PlayerStatistics {
 
         for scorecard in scorecards {
             for hole in 1..=scorecard.max_holes {
-                if let Some(strokes) = scorecard.get_score(hole) {
-                    if let Some(par) = scorecard.get_par(hole) {
-                        let difference = strokes as i8 - par as i8;
-                        match difference {
-                            ..=-2 => eagles += 1,
-                            -1 => birdies += 1,
-                            0 => pars += 1,
-                            1 => bogeys += 1,
-                            2.. => double_bogeys += 1,
-                        }
+                if let (Some(strokes), Some(par)) = (score(scorecard, hole), scorecard.get_par(hole)) {
+                    let difference = strokes - i16::from(par);
+                    match difference {
+                        ..=-2 => eagles += 1,
+                        -1 => birdies += 1,
+                        0 => pars += 1,
+                        1 => bogeys += 1,
+                        2.. => double_bogeys += 1,
                     }
                 }
             }
@@ -155,10 +313,172 @@ impl PlayerStatistics {
     }
 }
 
+/// Running totals of the per-hole shot detail across scorecards.
+#[derive(Debug, Default)]
+struct ShotDetail {
+    round_putts: Vec<u16>,
+    greens_hit: usize,
+    greens_known: usize,
+    fairways_hit: usize,
+    fairways_known: usize,
+    fairways_missed_left: usize,
+    fairways_missed_right: usize,
+    scrambles_made: usize,
+    scramble_chances: usize,
+    sand_saves_made: usize,
+    sand_save_chances: usize,
+    penalty_strokes: usize,
+}
+
+impl ShotDetail {
+    fn from_scorecards(scorecards: &[&Scorecard]) -> Self {
+        let mut detail = Self::default();
+
+        for scorecard in scorecards {
+            let mut putts = Some(0u16);
+            for hole in 1..=scorecard.max_holes {
+                let result = scorecard.hole_result(hole).copied().unwrap_or_default();
+                putts = putts.zip(result.putts).map(|(total, p)| total + u16::from(p));
+
+                if let Some(gir) = scorecard.green_in_regulation(hole) {
+                    detail.greens_known += 1;
+                    if gir {
+                        detail.greens_hit += 1;
+                    } else if let (Some(strokes), Some(par)) = (scorecard.get_score(hole), scorecard.get_par(hole)) {
+                        detail.scramble_chances += 1;
+                        detail.scrambles_made += usize::from(strokes <= par);
+                    }
+                }
+
+                if let Some(fairway) = result.fairway {
+                    detail.fairways_known += 1;
+                    match fairway {
+                        Fairway::Hit => detail.fairways_hit += 1,
+                        Fairway::Left => detail.fairways_missed_left += 1,
+                        Fairway::Right => detail.fairways_missed_right += 1,
+                    }
+                }
+
+                if let Some(saved) = result.sand_save {
+                    detail.sand_save_chances += 1;
+                    detail.sand_saves_made += usize::from(saved);
+                }
+                detail.penalty_strokes += usize::from(result.penalties);
+            }
+            detail.round_putts.extend(putts);
+        }
+
+        detail
+    }
+
+    fn putts_per_round(&self) -> Option<f64> {
+        (!self.round_putts.is_empty()).then(|| {
+            f64::from(self.round_putts.iter().sum::<u16>()) / self.round_putts.len() as f64
+        })
+    }
+}
+
+/// Scoring history, par-type and per-hole totals gathered in a single pass
+/// over the completed scorecards.
+#[derive(Debug, Default)]
+struct ScoringTrend {
+    history: Vec<RoundScore>,
+    /// Strokes and holes played on par 3s, 4s and 5s.
+    par_totals: [(u32, u32); 3],
+    /// Strokes and times played for each hole, by course name.
+    hole_totals: BTreeMap<String, BTreeMap<u8, (u32, u32)>>,
+}
+
+impl ScoringTrend {
+    fn from_scorecards(scorecards: &[&Scorecard]) -> Self {
+        let mut trend = Self::default();
+
+        for scorecard in scorecards {
+            for (&hole, &strokes) in scorecard.scores() {
+                if let Some(par) = scorecard.get_par(hole)
+                    && let Some(totals) = trend.par_totals.get_mut(usize::from(par).wrapping_sub(3))
+                {
+                    totals.0 += u32::from(strokes);
+                    totals.1 += 1;
+                }
+                if let Some(course) = &scorecard.course {
+                    let totals = trend.hole_totals.entry(course.clone()).or_default().entry(hole).or_default();
+                    totals.0 += u32::from(strokes);
+                    totals.1 += 1;
+                }
+            }
+            if let (Some(strokes), Some(to_par)) = (scorecard.total_strokes(), scorecard.score_relative_to_par()) {
+                trend.history.push(RoundScore {
+                    round_id: scorecard.round_id,
+                    played_at: scorecard.played_at,
+                    strokes,
+                    to_par,
+                });
+            }
+        }
+        trend.history.sort_by_key(|round| round.played_at);
+
+        trend
+    }
+
+    fn scores(&self) -> impl Iterator<Item = f64> + '_ {
+        self.history.iter().map(|round| f64::from(round.to_par))
+    }
+
+    fn mean(&self) -> f64 {
+        self.scores().sum::<f64>() / self.history.len() as f64
+    }
+
+    fn std_dev(&self) -> Option<f64> {
+        (self.history.len() >= 2).then(|| {
+            let mean = self.mean();
+            let variance = self.scores().map(|score| (score - mean).powi(2)).sum::<f64>() / self.history.len() as f64;
+            variance.sqrt()
+        })
+    }
+
+    /// Least-squares slope of score against round number.
+    fn slope(&self) -> Option<f64> {
+        (self.history.len() >= 2).then(|| {
+            let n = self.history.len() as f64;
+            let mean_x = (n - 1.0) / 2.0;
+            let mean_y = self.mean();
+            let (covariance, variance) = self.scores().enumerate().fold((0.0, 0.0), |(cov, var), (x, y)| {
+                let dx = x as f64 - mean_x;
+                (cov + dx * (y - mean_y), var + dx * dx)
+            });
+            covariance / variance
+        })
+    }
+
+    fn par_average(&self, par: u8) -> Option<f64> {
+        let (strokes, holes) = self.par_totals[usize::from(par - 3)];
+        (holes > 0).then(|| f64::from(strokes) / f64::from(holes))
+    }
+
+    fn hole_averages(&self) -> BTreeMap<String, BTreeMap<u8, f64>> {
+        self.hole_totals
+            .iter()
+            .map(|(course, holes)| {
+                let averages = holes
+                    .iter()
+                    .map(|(&hole, &(strokes, played))| (hole, f64::from(strokes) / f64::from(played)))
+                    .collect();
+                (course.clone(), averages)
+            })
+            .collect()
+    }
+}
+
+/// `part` as a percentage of `whole`, `None` when `whole` is zero.
+fn percentage(part: usize, whole: usize) -> Option<f64> {
+    (whole > 0).then(|| part as f64 * 100.0 / whole as f64)
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::HoleResult;
     use std::collections::BTreeMap;
     use uuid::Uuid;
 
@@ -168,11 +488,11 @@ mod tests {
             map_pars.insert((hole + 1) as u8, *par);
         } 
 
-        let mut scorecard = Scorecard::new(player_id, scores.len() as u8, map_pars).expect("Failed to create a test scorecard");
+        let mut scorecard = Scorecard::from_pars(player_id, scores.len() as u8, map_pars).expect("Failed to create a test scorecard");
         
         for (hole, strokes) in scores.iter().enumerate() {
             scorecard.record_score((hole + 1) as u8, *strokes)
-                .expect(&format!("Failed to record score for hole {}", hole + 1));
+                .unwrap_or_else(|_| panic!("Failed to record score for hole {}", hole + 1));
         }
         
         scorecard
@@ -347,4 +667,92 @@ mod tests {
         assert_eq!(results.completed_rounds, 2);
         assert_eq!(results.pars, 27); 
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn shot_detail_statistics() {
+        let player_id = Uuid::new_v4();
+        let pars: BTreeMap<u8, u8> = [(1, 4), (2, 4), (3, 3), (4, 5)].into_iter().collect();
+        let mut scorecard = Scorecard::from_pars(player_id, 4, pars).expect("scorecard");
+        let detail = |putts, fairway, sand_save, penalties| HoleResult { putts: Some(putts), fairway, sand_save, penalties };
+        // GIR, two putt par
+        scorecard.record_hole(1, 4, detail(2, Some(Fairway::Hit), None, 0)).expect("record");
+        // Missed green, up and down from the bunker for par
+        scorecard.record_hole(2, 4, detail(1, Some(Fairway::Left), Some(true), 0)).expect("record");
+        // Missed green, failed sand save
+        scorecard.record_hole(3, 4, detail(2, None, Some(false), 0)).expect("record");
+        // Penalty off the tee, three putt
+        scorecard.record_hole(4, 7, detail(3, Some(Fairway::Right), None, 1)).expect("record");
+
+        let stats = PlayerStatistics::from_scorecards(&[scorecard]);
+
+        assert_eq!(stats.putts_per_round, Some(8.0));
+        assert_eq!(stats.gir_percentage, Some(25.0));
+        assert_eq!(stats.fairway_percentage, Some(100.0 / 3.0));
+        assert_eq!((stats.fairways_missed_left, stats.fairways_missed_right), (1, 1));
+        assert_eq!(stats.scrambling_percentage, Some(100.0 / 3.0));
+        assert_eq!(stats.sand_save_percentage, Some(50.0));
+        assert_eq!(stats.penalty_strokes, 1);
+    }
+
+    #[test]
+    fn shot_detail_is_none_when_not_tracked() {
+        let scorecard = create_test_scorecard(Uuid::new_v4(), vec![4, 4, 4], vec![4, 4, 4]);
+        let stats = PlayerStatistics::from_scorecards(&[scorecard]);
+
+        assert_eq!(stats.putts_per_round, None);
+        assert_eq!(stats.gir_percentage, None);
+        assert_eq!(stats.scrambling_percentage, None);
+    }
+
+    #[test]
+    fn net_statistics_only_cover_rounds_with_a_course_handicap() {
+        let player_id = Uuid::new_v4();
+        let gross_only = create_test_scorecard(player_id, vec![6, 6, 6], vec![4, 4, 4]);
+        // One handicap stroke on each of the three holes
+        let mut with_handicap = Scorecard::from_pars(player_id, 3, [(1, 4), (2, 4), (3, 4)].into()).expect("scorecard")
+            .with_course_handicap(3);
+        for (hole, strokes) in [(1, 5), (2, 5), (3, 4)] {
+            with_handicap.record_score(hole, strokes).expect("record");
+        }
+
+        let stats = PlayerStatistics::from_scorecards(&[gross_only, with_handicap]);
+
+        assert_eq!((stats.bogeys, stats.pars, stats.double_bogeys), (2, 1, 3));
+        assert_eq!(stats.net_rounds, 1);
+        assert_eq!(stats.average_net_score, Some(11.0));
+        assert_eq!(stats.best_net_score, Some(11));
+        assert_eq!((stats.net_birdies, stats.net_pars, stats.net_bogeys), (1, 2, 0));
+    }
+
+    #[test]
+    fn trend_statistics_follow_the_order_rounds_were_played() {
+        let player_id = Uuid::new_v4();
+        let now = chrono::Utc::now();
+        let course = crate::utils::get_course("St_Andrews").expect("course");
+        // Played newest first: +4, +2, 0 relative to par 72 in date order
+        let rounds: Vec<Scorecard> = [(1, 0), (2, 2), (3, 4)]
+            .into_iter()
+            .map(|(days_ago, over)| {
+                let mut card = Scorecard::new(player_id, &course, course.default_tee()).expect("scorecard")
+                    .with_played_at(now - chrono::Duration::days(days_ago));
+                for hole in 1..=18 {
+                    card.record_score(hole, card.get_par(hole).unwrap() + u8::from(hole <= over)).expect("record");
+                }
+                card
+            })
+            .collect();
+
+        let stats = PlayerStatistics::from_scorecards(&rounds);
+
+        let to_par: Vec<i16> = stats.history.iter().map(|round| round.to_par).collect();
+        assert_eq!(to_par, vec![4, 2, 0]);
+        assert_eq!(stats.improvement_per_round, Some(-2.0));
+        assert_eq!(stats.score_std_dev, Some((8.0f64 / 3.0).sqrt()));
+        assert_eq!(stats.moving_average(2), Some(1.0));
+        assert_eq!(stats.par3_average, Some(3.0));
+        // Holes 1 and 2 are par 4s bogeyed in two of the three rounds
+        let old_course = &stats.hole_averages["St Andrews Old Course"];
+        assert_eq!(old_course[&1], 4.0 + 2.0 / 3.0);
+        assert_eq!(old_course[&18], 4.0);
+    }
+}
@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{GolfError, Result};
+use crate::models::{Course, Scorecard, TeeSet};
+
+/// Cut made after a round: the leading `top` players and anyone tied with
+/// them play on, everyone else is done.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CutRule {
+    pub after_round: u8,
+    pub top: usize,
+}
+
+/// A player's scorecard for one round of a tournament.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TournamentEntry {
+    pub round: u8,
+    pub player_id: Uuid,
+    pub scorecard_id: Uuid,
+}
+
+/// A multi-round event with a field of players, a course per round and an
+/// optional cut.
+///
+/// Like [`Round`](crate::models::Round), a tournament only records which
+/// scorecard belongs to which player and round; the scorecards are saved
+/// separately. Rounds are numbered from 1.
+///
+/// # Examples
+///
+/// ```
+/// use golf_score_tracker::models::{CutRule, Tournament};
+/// use golf_score_tracker::utils::get_course;
+/// use uuid::Uuid;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let augusta = get_course("Augusta")?;
+/// let mut masters = Tournament::new("The Masters", &[&augusta, &augusta, &augusta, &augusta])?
+///     .with_cut(CutRule { after_round: 2, top: 50 })?;
+/// let scottie = Uuid::new_v4();
+/// masters.join(scottie)?;
+///
+/// let card = masters.start_round(1, scottie, &augusta, augusta.default_tee())?;
+/// assert_eq!(masters.scorecard_id_for(1, &scottie), Some(card.round_id));
+/// assert!(masters.start_round(5, scottie, &augusta, augusta.default_tee()).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Tournament {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    /// Course name for each round, in order.
+    pub courses: Vec<String>,
+    #[serde(default)]
+    pub cut: Option<CutRule>,
+    #[serde(default)]
+    pub field: Vec<Uuid>,
+    #[serde(default)]
+    pub entries: Vec<TournamentEntry>,
+}
+
+impl Tournament {
+    /// Creates a tournament with one round per course in `rounds`.
+    pub fn new(name: impl Into<String>, rounds: &[&Course]) -> Result<Self> {
+        let name = name.into();
+        if name.trim().is_empty() {
+            return Err(GolfError::custom("Tournament name cannot be empty"));
+        }
+        if rounds.is_empty() || rounds.len() > usize::from(u8::MAX) {
+            return Err(GolfError::custom(format!(
+                "A tournament needs between 1 and {} rounds",
+                u8::MAX
+            )));
+        }
+        Ok(Self {
+            id: Uuid::new_v4(),
+            name,
+            created_at: Utc::now(),
+            courses: rounds.iter().map(|course| course.name.clone()).collect(),
+            cut: None,
+            field: Vec::new(),
+            entries: Vec::new(),
+        })
+    }
+
+    /// Adds a cut after one of the rounds before the last.
+    pub fn with_cut(mut self, cut: CutRule) -> Result<Self> {
+        if cut.after_round == 0 || cut.after_round >= self.round_count() {
+            return Err(GolfError::custom(format!(
+                "The cut must come after one of rounds 1 to {}",
+                self.round_count().saturating_sub(1)
+            )));
+        }
+        if cut.top == 0 {
+            return Err(GolfError::custom("The cut must keep at least one player"));
+        }
+        self.cut = Some(cut);
+        Ok(self)
+    }
+
+    pub fn round_count(&self) -> u8 {
+        self.courses.len() as u8
+    }
+
+    /// Course name played in `round`.
+    pub fn course_for(&self, round: u8) -> Result<&str> {
+        round
+            .checked_sub(1)
+            .and_then(|index| self.courses.get(usize::from(index)))
+            .map(String::as_str)
+            .ok_or_else(|| GolfError::custom(format!(
+                "Round {} is invalid. {} has rounds 1 to {}",
+                round,
+                self.name,
+                self.round_count()
+            )))
+    }
+
+    /// Adds a player to the field. Players can only join before the first
+    /// round has started.
+    pub fn join(&mut self, player_id: Uuid) -> Result<()> {
+        if self.field.contains(&player_id) {
+            return Err(GolfError::custom(format!("Player {} has already joined {}", player_id, self.name)));
+        }
+        if !self.entries.is_empty() {
+            return Err(GolfError::custom(format!("{} has already started", self.name)));
+        }
+        self.field.push(player_id);
+        Ok(())
+    }
+
+    /// Creates a player's scorecard for `round`, adds it to the tournament
+    /// and returns it for saving.
+    pub fn start_round(&mut self, round: u8, player_id: Uuid, course: &Course, tee: &TeeSet) -> Result<Scorecard> {
+        let scorecard = Scorecard::new(player_id, course, tee)?;
+        self.add_scorecard(round, &scorecard)?;
+        Ok(scorecard)
+    }
+
+    /// Adds an existing scorecard as a player's card for `round`.
+    ///
+    /// The player must be in the field, the scorecard must be for the
+    /// round's course, and each player has one scorecard per round.
+    pub fn add_scorecard(&mut self, round: u8, scorecard: &Scorecard) -> Result<()> {
+        let course = self.course_for(round)?;
+        if scorecard.course.as_deref().is_some_and(|name| name != course) {
+            return Err(GolfError::custom(format!(
+                "Round {} of {} is played on {}, not {}",
+                round,
+                self.name,
+                course,
+                scorecard.course.as_deref().unwrap_or_default()
+            )));
+        }
+        if !self.field.contains(&scorecard.player_id) {
+            return Err(GolfError::custom(format!("Player {} is not in the field for {}", scorecard.player_id, self.name)));
+        }
+        if self.scorecard_id_for(round, &scorecard.player_id).is_some() {
+            return Err(GolfError::custom(format!(
+                "Player {} already has a scorecard for round {} of {}",
+                scorecard.player_id, round, self.name
+            )));
+        }
+        self.entries.push(TournamentEntry { round, player_id: scorecard.player_id, scorecard_id: scorecard.round_id });
+        Ok(())
+    }
+
+    /// Scorecard of a player in `round`, if it has been started.
+    pub fn scorecard_id_for(&self, round: u8, player_id: &Uuid) -> Option<Uuid> {
+        self.entries
+            .iter()
+            .find(|entry| entry.round == round && &entry.player_id == player_id)
+            .map(|entry| entry.scorecard_id)
+    }
+
+    pub fn includes_player(&self, player_id: &Uuid) -> bool {
+        self.field.contains(player_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_standard_course;
+
+    #[test]
+    fn field_is_closed_once_play_starts() {
+        let course = create_standard_course(18);
+        let mut tournament = Tournament::new("Club Championship", &[&course, &course]).expect("tournament");
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        tournament.join(first).expect("join");
+        assert!(tournament.join(first).is_err());
+
+        tournament.start_round(1, first, &course, course.default_tee()).expect("start");
+
+        assert!(tournament.join(second).is_err());
+        assert!(tournament.start_round(1, first, &course, course.default_tee()).is_err());
+        assert!(tournament.start_round(2, second, &course, course.default_tee()).is_err());
+    }
+
+    #[test]
+    fn cut_must_fall_before_the_last_round() {
+        let course = create_standard_course(18);
+        let tournament = Tournament::new("Two Day Open", &[&course, &course]).expect("tournament");
+
+        assert!(tournament.clone().with_cut(CutRule { after_round: 2, top: 10 }).is_err());
+        assert!(tournament.clone().with_cut(CutRule { after_round: 1, top: 0 }).is_err());
+        assert!(tournament.with_cut(CutRule { after_round: 1, top: 10 }).is_ok());
+    }
+}
@@ -0,0 +1,496 @@
+//! Handicap index calculation following the World Handicap System (WHS).
+//!
+//! The calculation replays a player's completed 18-hole rounds in the order
+//! they were played:
+//!
+//! 1. Each hole score is capped at **net double bogey**: par + 2 + the
+//!    handicap strokes received on that hole. Until a handicap index exists
+//!    (fewer than 3 rounds) the cap is par + 5.
+//! 2. The adjusted gross score becomes a **score differential**:
+//!    `(113 / slope) × (adjusted gross − course rating)`.
+//! 3. The index averages the lowest differentials of the most recent 20
+//!    (the best 8 once 20 exist, fewer for a shorter record) and applies the
+//!    WHS adjustment for short records.
+//! 4. Once 20 scores exist, the **soft cap** halves any increase of more
+//!    than 3.0 over the lowest index of the previous 365 days, and the
+//!    **hard cap** limits the increase to 5.0.
+//!
+//! Playing conditions calculations and 9-hole score combining are not
+//! modelled; 9-hole and incomplete rounds are skipped.
+//!
+//! # Examples
+//!
+//! ```
+//! use golf_score_tracker::Scorecard;
+//! use golf_score_tracker::services::HandicapCalculation;
+//! use golf_score_tracker::utils::create_standard_pars;
+//! use uuid::Uuid;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let player_id = Uuid::new_v4();
+//! let mut rounds = Vec::new();
+//! for _ in 0..3 {
+//!     let mut card = Scorecard::from_pars(player_id, 18, create_standard_pars(18))?;
+//!     for hole in 1..=18 {
+//!         card.record_score(hole, card.get_par(hole).unwrap() + 1)?;
+//!     }
+//!     rounds.push(card);
+//! }
+//!
+//! // Three rounds of +18 on an unrated course: lowest differential 18.0, minus 2.0
+//! let calculation = HandicapCalculation::from_scorecards(&rounds);
+//! assert_eq!(calculation.handicap_index, Some(16.0));
+//! # Ok(())
+//! # }
+//! ```
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::models::{CourseRating, Scorecard};
+
+/// Highest handicap index the WHS allows.
+pub const MAX_HANDICAP_INDEX: f64 = 54.0;
+
+/// Number of most recent differentials an index is calculated from.
+pub const SCORING_RECORD_SIZE: usize = 20;
+
+/// Holes a round must have to count towards the index.
+const ELIGIBLE_HOLES: u8 = 18;
+
+/// Increase over the low index above which the soft cap starts.
+const SOFT_CAP_THRESHOLD: f64 = 3.0;
+
+/// Largest increase over the low index the hard cap allows.
+const HARD_CAP_THRESHOLD: f64 = 5.0;
+
+/// One round's contribution to the scoring record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreDifferential {
+    pub round_id: Uuid,
+    pub played_at: DateTime<Utc>,
+    /// Strokes actually taken.
+    pub gross_score: u16,
+    /// Strokes after capping every hole at net double bogey.
+    pub adjusted_gross_score: u16,
+    pub rating: CourseRating,
+    /// Index the player held when the round was played, if any.
+    pub index_before: Option<f64>,
+    /// Course handicap used for the net double bogey cap, if an index existed.
+    pub course_handicap: Option<i32>,
+    /// Score differential, rounded to the nearest tenth.
+    pub differential: f64,
+    /// Whether this differential is one of those averaged into the current index.
+    pub counted: bool,
+}
+
+/// Result of a handicap index calculation, with every intermediate value
+/// kept so the derivation can be shown to the player.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandicapCalculation {
+    /// The most recent differentials (up to 20), oldest first.
+    pub differentials: Vec<ScoreDifferential>,
+    /// Rounds ignored because they were incomplete or not 18 holes.
+    pub skipped_rounds: usize,
+    /// Number of lowest differentials averaged.
+    pub differentials_counted: usize,
+    /// WHS adjustment added for short scoring records.
+    pub adjustment: f64,
+    /// Index before soft/hard caps were applied.
+    pub uncapped_index: Option<f64>,
+    /// Lowest index held in the 365 days before the most recent round.
+    pub low_index: Option<f64>,
+    pub soft_cap_applied: bool,
+    pub hard_cap_applied: bool,
+    /// The resulting handicap index, `None` with fewer than 3 eligible rounds.
+    pub handicap_index: Option<f64>,
+}
+
+/// An index computed from one state of the scoring record.
+#[derive(Debug, Clone, Copy)]
+struct IndexResult {
+    counted: usize,
+    adjustment: f64,
+    uncapped: f64,
+    low_index: Option<f64>,
+    soft_cap_applied: bool,
+    hard_cap_applied: bool,
+    index: f64,
+}
+
+impl HandicapCalculation {
+    /// Calculates a handicap index from a player's scorecards.
+    ///
+    /// Scorecards may be given in any order; they are sorted by
+    /// `played_at`. Incomplete and 9-hole rounds are counted in
+    /// `skipped_rounds` and otherwise ignored.
+    pub fn from_scorecards(scorecards: &[Scorecard]) -> Self {
+        let mut eligible: Vec<&Scorecard> = scorecards
+            .iter()
+            .filter(|sc| sc.is_complete() && sc.max_holes == ELIGIBLE_HOLES)
+            .collect();
+        eligible.sort_by_key(|sc| (sc.played_at, sc.round_id));
+        let skipped_rounds = scorecards.len() - eligible.len();
+
+        let mut all: Vec<ScoreDifferential> = Vec::with_capacity(eligible.len());
+        let mut index_history: Vec<(DateTime<Utc>, f64)> = Vec::new();
+        let mut latest: Option<IndexResult> = None;
+
+        for scorecard in eligible {
+            let index_before = latest.map(|result| result.index);
+            all.push(Self::differential_for(scorecard, index_before));
+
+            let record_start = all.len().saturating_sub(SCORING_RECORD_SIZE);
+            let record = &all[record_start..];
+            latest = Self::index_for(record, &index_history, scorecard.played_at);
+            // Only established indexes (a full scoring record) can become the low index
+            if let Some(result) = latest.filter(|_| record.len() >= SCORING_RECORD_SIZE) {
+                index_history.push((scorecard.played_at, result.index));
+            }
+        }
+
+        let record_start = all.len().saturating_sub(SCORING_RECORD_SIZE);
+        let mut differentials = all.split_off(record_start);
+        if let Some(result) = latest {
+            for position in Self::lowest_positions(&differentials, result.counted) {
+                differentials[position].counted = true;
+            }
+        }
+
+        Self {
+            differentials,
+            skipped_rounds,
+            differentials_counted: latest.map_or(0, |r| r.counted),
+            adjustment: latest.map_or(0.0, |r| r.adjustment),
+            uncapped_index: latest.map(|r| r.uncapped),
+            low_index: latest.and_then(|r| r.low_index),
+            soft_cap_applied: latest.is_some_and(|r| r.soft_cap_applied),
+            hard_cap_applied: latest.is_some_and(|r| r.hard_cap_applied),
+            handicap_index: latest.map(|r| r.index),
+        }
+    }
+
+    fn differential_for(scorecard: &Scorecard, index_before: Option<f64>) -> ScoreDifferential {
+        let rating = scorecard.effective_rating();
+        let course_handicap = index_before.map(|index| course_handicap(index, rating, scorecard.total_par()));
+
+        let mut gross_score = 0u16;
+        let mut adjusted_gross_score = 0u16;
+        for (&hole, &strokes) in scorecard.scores() {
+            let par = scorecard.get_par(hole).unwrap_or(4);
+            let max_score = match course_handicap {
+                Some(ch) => i32::from(par) + 2 + strokes_received(ch, scorecard.stroke_index(hole).unwrap_or(hole), scorecard.max_holes),
+                None => i32::from(par) + 5,
+            };
+            gross_score += u16::from(strokes);
+            adjusted_gross_score += i32::from(strokes).min(max_score).max(1) as u16;
+        }
+
+        let differential = f64::from(CourseRating::STANDARD_SLOPE) / f64::from(rating.slope_rating)
+            * (f64::from(adjusted_gross_score) - rating.course_rating);
+
+        ScoreDifferential {
+            round_id: scorecard.round_id,
+            played_at: scorecard.played_at,
+            gross_score,
+            adjusted_gross_score,
+            rating,
+            index_before,
+            course_handicap,
+            differential: round_to_tenth(differential),
+            counted: false,
+        }
+    }
+
+    /// Computes the index for a scoring record (at most 20 differentials,
+    /// oldest first) as of the round played at `as_of`.
+    fn index_for(record: &[ScoreDifferential], history: &[(DateTime<Utc>, f64)], as_of: DateTime<Utc>) -> Option<IndexResult> {
+        let (counted, adjustment) = differentials_to_use(record.len())?;
+
+        let lowest = Self::lowest_positions(record, counted);
+        let average = lowest.iter().map(|&i| record[i].differential).sum::<f64>() / counted as f64;
+        let uncapped = (average + adjustment).min(MAX_HANDICAP_INDEX);
+
+        let low_index = if record.len() >= SCORING_RECORD_SIZE {
+            let window_start = as_of - Duration::days(365);
+            history
+                .iter()
+                .filter(|(played_at, _)| *played_at >= window_start)
+                .map(|&(_, index)| index)
+                .reduce(f64::min)
+        } else {
+            None
+        };
+
+        let mut index = uncapped;
+        let mut soft_cap_applied = false;
+        let mut hard_cap_applied = false;
+        if let Some(low) = low_index {
+            if index - low > SOFT_CAP_THRESHOLD {
+                index = low + SOFT_CAP_THRESHOLD + (index - low - SOFT_CAP_THRESHOLD) / 2.0;
+                soft_cap_applied = true;
+            }
+            if index - low > HARD_CAP_THRESHOLD {
+                index = low + HARD_CAP_THRESHOLD;
+                hard_cap_applied = true;
+            }
+        }
+
+        Some(IndexResult {
+            counted,
+            adjustment,
+            uncapped: round_to_tenth(uncapped),
+            low_index,
+            soft_cap_applied,
+            hard_cap_applied,
+            index: round_to_tenth(index),
+        })
+    }
+
+    /// Positions of the `count` lowest differentials, earliest first on ties.
+    fn lowest_positions(record: &[ScoreDifferential], count: usize) -> Vec<usize> {
+        let mut positions: Vec<usize> = (0..record.len()).collect();
+        positions.sort_by(|&a, &b| record[a].differential.total_cmp(&record[b].differential));
+        positions.truncate(count);
+        positions
+    }
+}
+
+/// How many of the lowest differentials to average, and the adjustment to
+/// add, for a scoring record of `scores` differentials (WHS Rule 5.2).
+///
+/// Returns `None` when fewer than 3 scores exist.
+pub fn differentials_to_use(scores: usize) -> Option<(usize, f64)> {
+    let rule = match scores {
+        0..=2 => return None,
+        3 => (1, -2.0),
+        4 => (1, -1.0),
+        5 => (1, 0.0),
+        6 => (2, -1.0),
+        7..=8 => (2, 0.0),
+        9..=11 => (3, 0.0),
+        12..=14 => (4, 0.0),
+        15..=16 => (5, 0.0),
+        17..=18 => (6, 0.0),
+        19 => (7, 0.0),
+        _ => (8, 0.0),
+    };
+    Some(rule)
+}
+
+/// Course handicap for a handicap index on a set of tees:
+/// `index × slope / 113 + (course rating − par)`, rounded to a whole number.
+pub fn course_handicap(handicap_index: f64, rating: CourseRating, par: u16) -> i32 {
+    let strokes = handicap_index * f64::from(rating.slope_rating) / f64::from(CourseRating::STANDARD_SLOPE)
+        + (rating.course_rating - f64::from(par));
+    strokes.round() as i32
+}
+
+/// Handicap strokes received on a hole with the given stroke index.
+///
+/// Strokes are allocated starting from stroke index 1 (the hardest hole),
+/// wrapping around for course handicaps above the number of holes. Plus
+/// handicaps (negative values) give strokes back starting from the easiest
+/// hole, which yields a negative result.
+pub fn strokes_received(course_handicap: i32, stroke_index: u8, holes: u8) -> i32 {
+    let holes = i32::from(holes.max(1));
+    let stroke_index = i32::from(stroke_index);
+    if course_handicap >= 0 {
+        course_handicap / holes + i32::from(stroke_index <= course_handicap % holes)
+    } else {
+        let plus = -course_handicap;
+        -(plus / holes + i32::from(stroke_index > holes - plus % holes))
+    }
+}
+
+fn round_to_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use crate::models::{Course, Hole, TeeSet};
+
+    fn flat_pars() -> BTreeMap<u8, u8> {
+        (1..=18).map(|hole| (hole, 4)).collect()
+    }
+
+    /// An 18-hole par-72 round `over` strokes over par, spread as bogeys
+    /// then doubles, played `day` days after a fixed start date.
+    fn round(player_id: Uuid, over: u8, day: i64) -> Scorecard {
+        let start = DateTime::parse_from_rfc3339("2025-01-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        let mut card = Scorecard::from_pars(player_id, 18, flat_pars())
+            .expect("scorecard")
+            .with_played_at(start + Duration::days(day));
+        for hole in 1..=18u8 {
+            let extra = u8::from(hole <= over) + u8::from(hole + 18 <= over);
+            card.record_score(hole, 4 + extra).expect("record");
+        }
+        card
+    }
+
+    #[test]
+    fn no_index_with_fewer_than_three_rounds() {
+        let player_id = Uuid::new_v4();
+        let calc = HandicapCalculation::from_scorecards(&[round(player_id, 10, 0), round(player_id, 12, 1)]);
+
+        assert_eq!(calc.handicap_index, None);
+        assert_eq!(calc.differentials.len(), 2);
+    }
+
+    #[test]
+    fn short_record_uses_table_adjustment() {
+        let player_id = Uuid::new_v4();
+        let cards: Vec<Scorecard> = [20, 14, 17, 25, 19, 16].iter().enumerate()
+            .map(|(day, &over)| round(player_id, over, day as i64))
+            .collect();
+
+        let calc = HandicapCalculation::from_scorecards(&cards);
+
+        // Six scores: average of lowest two (14, 16) minus 1.0
+        assert_eq!(calc.differentials_counted, 2);
+        assert_eq!(calc.adjustment, -1.0);
+        assert_eq!(calc.handicap_index, Some(14.0));
+        assert_eq!(calc.differentials.iter().filter(|d| d.counted).count(), 2);
+    }
+
+    #[test]
+    fn best_eight_of_last_twenty() {
+        let player_id = Uuid::new_v4();
+        // 5 old rounds at +1 that fall out of the record, then 20 rounds
+        let mut cards: Vec<Scorecard> = (0..5).map(|day| round(player_id, 1, day)).collect();
+        for (offset, over) in [10, 10, 10, 10, 12, 12, 12, 12, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20].iter().enumerate() {
+            cards.push(round(player_id, *over, 5 + offset as i64));
+        }
+
+        let calc = HandicapCalculation::from_scorecards(&cards);
+
+        assert_eq!(calc.differentials.len(), 20);
+        assert_eq!(calc.differentials_counted, 8);
+        // Average of four 10s and four 12s
+        assert_eq!(calc.uncapped_index, Some(11.0));
+    }
+
+    #[test]
+    fn net_double_bogey_caps_blow_up_holes() {
+        let player_id = Uuid::new_v4();
+        let mut card = Scorecard::from_pars(player_id, 18, flat_pars()).expect("scorecard");
+        card.record_score(1, 12).expect("record");
+        for hole in 2..=18 {
+            card.record_score(hole, 4).expect("record");
+        }
+
+        let calc = HandicapCalculation::from_scorecards(&[card]);
+        let diff = &calc.differentials[0];
+
+        // No index yet: the 12 counts as par + 5 = 9
+        assert_eq!(diff.gross_score, 80);
+        assert_eq!(diff.adjusted_gross_score, 77);
+        assert_eq!(diff.differential, 5.0);
+    }
+
+    #[test]
+    fn net_double_bogey_follows_course_stroke_indexes() {
+        let player_id = Uuid::new_v4();
+        // Three rounds at +10 give an 8.0 index and a course handicap of 8
+        let mut cards: Vec<Scorecard> = (0..3).map(|day| round(player_id, 10, day)).collect();
+
+        // Hole 1 is the easiest hole, so no stroke is received there
+        let holes = (1..=18u8)
+            .map(|number| Hole::new(number, 4, 18).expect("hole").with_stroke_index(19 - number))
+            .collect();
+        let course = Course::new("Reversed", holes, vec![TeeSet::unrated("White", 72)]).expect("course");
+        let mut card = Scorecard::new(player_id, &course, course.default_tee())
+            .expect("scorecard")
+            .with_played_at(cards[2].played_at + Duration::days(1));
+        card.record_score(1, 12).expect("record");
+        for hole in 2..=18 {
+            card.record_score(hole, 4).expect("record");
+        }
+        cards.push(card);
+
+        let calc = HandicapCalculation::from_scorecards(&cards);
+
+        assert_eq!(calc.differentials[3].course_handicap, Some(8));
+        assert_eq!(calc.differentials[3].adjusted_gross_score, 6 + 17 * 4);
+    }
+
+    #[test]
+    fn differential_uses_rating_and_slope() {
+        let player_id = Uuid::new_v4();
+        let card = round(player_id, 18, 0).with_rating(CourseRating::new(70.0, 130).unwrap());
+
+        let calc = HandicapCalculation::from_scorecards(&[card]);
+
+        // 113 / 130 × (90 − 70.0) = 17.38 → 17.4
+        assert_eq!(calc.differentials[0].differential, 17.4);
+    }
+
+    #[test]
+    fn hard_cap_limits_increase_to_five() {
+        let player_id = Uuid::new_v4();
+        let mut cards: Vec<Scorecard> = (0..20).map(|day| round(player_id, 10, day)).collect();
+        cards.extend((20..40).map(|day| round(player_id, 30, day)));
+
+        let calc = HandicapCalculation::from_scorecards(&cards);
+
+        assert_eq!(calc.low_index, Some(10.0));
+        assert_eq!(calc.uncapped_index, Some(30.0));
+        assert!(calc.soft_cap_applied);
+        assert!(calc.hard_cap_applied);
+        assert_eq!(calc.handicap_index, Some(15.0));
+    }
+
+    #[test]
+    fn soft_cap_halves_increase_beyond_three() {
+        let player_id = Uuid::new_v4();
+        let mut cards: Vec<Scorecard> = (0..20).map(|day| round(player_id, 10, day)).collect();
+        cards.extend((20..40).map(|day| round(player_id, 17, day)));
+
+        let calc = HandicapCalculation::from_scorecards(&cards);
+
+        // 17.0 is 7.0 above the low of 10.0: 10 + 3 + 4 / 2 = 15.0
+        assert!(calc.soft_cap_applied);
+        assert!(!calc.hard_cap_applied);
+        assert_eq!(calc.handicap_index, Some(15.0));
+    }
+
+    #[test]
+    fn low_index_only_looks_back_one_year() {
+        let player_id = Uuid::new_v4();
+        let mut cards: Vec<Scorecard> = (0..20).map(|day| round(player_id, 10, day)).collect();
+        cards.extend((20..40).map(|day| round(player_id, 14, day)));
+        cards.extend((800..820).map(|day| round(player_id, 14, day)));
+
+        let calc = HandicapCalculation::from_scorecards(&cards);
+
+        // The 10.0 index is more than a year old, so the 14.0 is not capped
+        assert_eq!(calc.low_index, Some(14.0));
+        assert!(!calc.soft_cap_applied);
+        assert_eq!(calc.handicap_index, Some(14.0));
+    }
+
+    #[test]
+    fn strokes_are_allocated_by_stroke_index() {
+        assert_eq!(strokes_received(0, 1, 18), 0);
+        assert_eq!(strokes_received(10, 10, 18), 1);
+        assert_eq!(strokes_received(10, 11, 18), 0);
+        assert_eq!(strokes_received(20, 2, 18), 2);
+        assert_eq!(strokes_received(20, 3, 18), 1);
+        assert_eq!(strokes_received(-2, 17, 18), -1);
+        assert_eq!(strokes_received(-2, 16, 18), 0);
+    }
+
+    #[test]
+    fn incomplete_and_nine_hole_rounds_are_skipped() {
+        let player_id = Uuid::new_v4();
+        let nine = Scorecard::from_pars(player_id, 9, (1..=9).map(|h| (h, 4)).collect()).expect("scorecard");
+        let open = Scorecard::from_pars(player_id, 18, flat_pars()).expect("scorecard");
+
+        let calc = HandicapCalculation::from_scorecards(&[nine, open, round(player_id, 5, 0)]);
+
+        assert_eq!(calc.skipped_rounds, 2);
+        assert_eq!(calc.differentials.len(), 1);
+    }
+}
@@ -0,0 +1,287 @@
+//! Tournament leaderboards.
+//!
+//! A [`Leaderboard`] ranks a tournament field by total strokes relative to
+//! par over every hole played so far, so players part way through a round
+//! are compared fairly with those who have finished. Players level on par
+//! share a position, shown as "T3". Once every player has finished the
+//! cut round, those outside the cut are listed after the players who made
+//! it, without a position.
+//!
+//! # Examples
+//!
+//! ```
+//! use golf_score_tracker::models::Tournament;
+//! use golf_score_tracker::services::Leaderboard;
+//! use golf_score_tracker::utils::create_standard_course;
+//! use uuid::Uuid;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let course = create_standard_course(9);
+//! let mut tournament = Tournament::new("Twilight Nine", &[&course])?;
+//! let (nelly, lilia) = (Uuid::new_v4(), Uuid::new_v4());
+//! tournament.join(nelly)?;
+//! tournament.join(lilia)?;
+//!
+//! let mut cards = Vec::new();
+//! for player in [nelly, lilia] {
+//!     let mut card = tournament.start_round(1, player, &course, course.default_tee())?;
+//!     card.record_score(1, 3)?;
+//!     cards.push(card);
+//! }
+//!
+//! let leaderboard = Leaderboard::new(&tournament, &cards);
+//! assert_eq!(leaderboard.rows[0].position.unwrap().to_string(), "T1");
+//! assert_eq!(leaderboard.rows[1].thru.to_string(), "1");
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::HashMap;
+use std::fmt;
+
+use uuid::Uuid;
+
+use crate::models::{Scorecard, Tournament};
+
+/// A place on the leaderboard, shared with anyone on the same score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub rank: usize,
+    pub tied: bool,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.tied {
+            write!(f, "T{}", self.rank)
+        } else {
+            write!(f, "{}", self.rank)
+        }
+    }
+}
+
+/// How far a player is through their current round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Thru {
+    /// No scorecard started yet.
+    NotStarted,
+    /// Holes scored in a round still in progress.
+    Holes(u8),
+    /// Current round complete.
+    Finished,
+}
+
+impl fmt::Display for Thru {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Thru::NotStarted => f.write_str("-"),
+            Thru::Holes(holes) => write!(f, "{}", holes),
+            Thru::Finished => f.write_str("F"),
+        }
+    }
+}
+
+/// One player's line on the leaderboard.
+///
+/// `rounds` holds the strokes of each completed round, by round number
+/// (`rounds[0]` is round 1); rounds not yet played or still in progress
+/// are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderboardRow {
+    pub player_id: Uuid,
+    /// `None` for players who have not started or missed the cut.
+    pub position: Option<Position>,
+    pub to_par: i32,
+    pub total_strokes: u32,
+    pub rounds: Vec<Option<u16>>,
+    /// Round the player is on or last finished, 0 before they start.
+    pub current_round: u8,
+    pub thru: Thru,
+    pub missed_cut: bool,
+}
+
+/// Standings of a tournament field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub rows: Vec<LeaderboardRow>,
+    /// Whether the cut has been made, i.e. everyone has finished the cut
+    /// round.
+    pub cut_applied: bool,
+}
+
+impl Leaderboard {
+    /// Builds the leaderboard of `tournament` from its players' scorecards.
+    ///
+    /// `scorecards` may contain unrelated scorecards; only those entered in
+    /// the tournament are used.
+    pub fn new(tournament: &Tournament, scorecards: &[Scorecard]) -> Self {
+        let cards: HashMap<Uuid, &Scorecard> = scorecards.iter().map(|card| (card.round_id, card)).collect();
+        let card_for = |round: u8, player_id: &Uuid| {
+            tournament.scorecard_id_for(round, player_id).and_then(|id| cards.get(&id).copied())
+        };
+
+        // Scores through the cut round, once everyone has finished it
+        let through_cut: Option<(usize, Vec<(Uuid, i32)>)> = tournament.cut.and_then(|cut| {
+            let mut standings = Vec::with_capacity(tournament.field.len());
+            for player_id in &tournament.field {
+                let mut to_par = 0;
+                for round in 1..=cut.after_round {
+                    let card = card_for(round, player_id).filter(|card| card.is_complete())?;
+                    to_par += i32::from(card.score_relative_to_par()?);
+                }
+                standings.push((*player_id, to_par));
+            }
+            Some((cut.top, standings))
+        });
+        let cut_applied = through_cut.is_some();
+        let missed_cut: Vec<Uuid> = through_cut
+            .map(|(top, standings)| {
+                let ranks = competition_ranks(&standings.iter().map(|&(_, to_par)| to_par).collect::<Vec<_>>());
+                standings
+                    .into_iter()
+                    .zip(ranks)
+                    .filter(|&(_, (rank, _))| rank > top)
+                    .map(|((player_id, _), _)| player_id)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut rows: Vec<LeaderboardRow> = tournament
+            .field
+            .iter()
+            .map(|player_id| {
+                let mut row = LeaderboardRow {
+                    player_id: *player_id,
+                    position: None,
+                    to_par: 0,
+                    total_strokes: 0,
+                    rounds: vec![None; usize::from(tournament.round_count())],
+                    current_round: 0,
+                    thru: Thru::NotStarted,
+                    missed_cut: missed_cut.contains(player_id),
+                };
+                for round in 1..=tournament.round_count() {
+                    // A scorecard with no scores yet hasn't teed off
+                    let Some(card) = card_for(round, player_id).filter(|card| !card.scores().is_empty()) else {
+                        continue;
+                    };
+                    for (&hole, &strokes) in card.scores() {
+                        row.total_strokes += u32::from(strokes);
+                        row.to_par += i32::from(strokes) - i32::from(card.get_par(hole).unwrap_or(0));
+                    }
+                    row.rounds[usize::from(round - 1)] = card.total_strokes();
+                    row.current_round = round;
+                    row.thru = if card.is_complete() { Thru::Finished } else { Thru::Holes(card.scores().len() as u8) };
+                }
+                row
+            })
+            .collect();
+
+        // Ranked players first, then those yet to start, then the cut.
+        let group = |row: &LeaderboardRow| match (row.missed_cut, row.thru) {
+            (true, _) => 2,
+            (false, Thru::NotStarted) => 1,
+            _ => 0,
+        };
+        rows.sort_by_key(|row| (group(row), row.to_par));
+
+        let ranked = rows.iter().take_while(|row| group(row) == 0).count();
+        let ranks = competition_ranks(&rows[..ranked].iter().map(|row| row.to_par).collect::<Vec<_>>());
+        for (row, (rank, tied)) in rows.iter_mut().zip(ranks) {
+            row.position = Some(Position { rank, tied });
+        }
+
+        Self { rows, cut_applied }
+    }
+
+    pub fn row(&self, player_id: &Uuid) -> Option<&LeaderboardRow> {
+        self.rows.iter().find(|row| &row.player_id == player_id)
+    }
+
+    /// Whether a player may play on. Everyone has made the cut until it is
+    /// applied.
+    pub fn made_cut(&self, player_id: &Uuid) -> bool {
+        self.row(player_id).is_some_and(|row| !row.missed_cut)
+    }
+}
+
+/// Standard competition ranks ("1224") for scores, lower is better, with
+/// whether each rank is shared.
+fn competition_ranks(scores: &[i32]) -> Vec<(usize, bool)> {
+    scores
+        .iter()
+        .map(|score| {
+            let better = scores.iter().filter(|other| *other < score).count();
+            let same = scores.iter().filter(|other| *other == score).count();
+            (better + 1, same > 1)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CutRule, Course};
+    use crate::utils::create_standard_course;
+
+    /// Plays `player`'s round with every hole at par plus `over`, for the
+    /// first `holes` holes.
+    fn play(tournament: &mut Tournament, course: &Course, round: u8, player: Uuid, over: &[u8]) -> Scorecard {
+        let mut card = tournament.start_round(round, player, course, course.default_tee()).expect("start");
+        for (hole, &extra) in (1..).zip(over) {
+            card.record_score(hole, card.get_par(hole).unwrap() + extra).expect("record");
+        }
+        card
+    }
+
+    #[test]
+    fn ties_share_a_position_and_skip_the_next() {
+        let course = create_standard_course(3);
+        let mut tournament = Tournament::new("Tie Break Open", &[&course]).expect("tournament");
+        let players: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        for player in &players {
+            tournament.join(*player).expect("join");
+        }
+        let cards = vec![
+            play(&mut tournament, &course, 1, players[0], &[0, 0, 1]),
+            play(&mut tournament, &course, 1, players[1], &[0, 0, 0]),
+            play(&mut tournament, &course, 1, players[2], &[1]),
+        ];
+
+        let leaderboard = Leaderboard::new(&tournament, &cards);
+        let positions: Vec<String> = leaderboard.rows.iter()
+            .map(|row| row.position.map_or_else(|| "-".to_string(), |p| p.to_string()))
+            .collect();
+
+        assert_eq!(positions, vec!["1", "T2", "T2", "-"]);
+        assert_eq!(leaderboard.row(&players[2]).unwrap().thru, Thru::Holes(1));
+        assert_eq!(leaderboard.row(&players[1]).unwrap().rounds, vec![Some(12)]);
+        assert_eq!(leaderboard.row(&players[3]).unwrap().thru, Thru::NotStarted);
+    }
+
+    #[test]
+    fn cut_keeps_the_top_players_and_ties() {
+        let course = create_standard_course(3);
+        let mut tournament = Tournament::new("Cut Classic", &[&course, &course])
+            .expect("tournament")
+            .with_cut(CutRule { after_round: 1, top: 1 })
+            .expect("cut");
+        let players: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        for player in &players {
+            tournament.join(*player).expect("join");
+        }
+        let mut cards = vec![
+            play(&mut tournament, &course, 1, players[0], &[0, 0, 0]),
+            play(&mut tournament, &course, 1, players[1], &[0, 0, 0]),
+        ];
+
+        assert!(!Leaderboard::new(&tournament, &cards).cut_applied);
+
+        cards.push(play(&mut tournament, &course, 1, players[2], &[1, 0, 0]));
+        let leaderboard = Leaderboard::new(&tournament, &cards);
+
+        assert!(leaderboard.cut_applied);
+        assert!(leaderboard.made_cut(&players[0]) && leaderboard.made_cut(&players[1]));
+        assert!(!leaderboard.made_cut(&players[2]));
+        assert_eq!(leaderboard.rows[2].position, None);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod handicap;
+pub mod leaderboard;
+pub mod scoring;
+
+pub use handicap::{HandicapCalculation, ScoreDifferential};
+pub use leaderboard::{Leaderboard, LeaderboardRow, Position, Thru};
+pub use scoring::{Competitor, FormatResult, MatchResult, ScoringFormat, SkinHole, SkinsResult, Standing};
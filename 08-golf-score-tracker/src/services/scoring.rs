@@ -0,0 +1,483 @@
+//! Scoring a round under different game formats.
+//!
+//! `Scorecard` only knows strokes; how those strokes turn into a result
+//! depends on the game being played. [`ScoringFormat`] names the formats a
+//! group can play and [`ScoringFormat::score`] scores a set of scorecards
+//! from the same round under one of them:
+//!
+//! * **Stroke play** - lowest total strokes wins.
+//! * **Match play** - two players win, lose or halve each hole; the match
+//!   ends once one player leads by more holes than remain ("3&2").
+//! * **Stableford** - points per hole relative to par: 0 for double bogey or
+//!   worse, 1 for bogey, 2 for par, 3 for birdie and so on. Net Stableford
+//!   first deducts the handicap strokes received on the hole.
+//! * **Skins** - the outright lowest score on a hole wins a skin; tied holes
+//!   carry their skin over to the next hole.
+//!
+//! Only holes that have been scored are counted, so a round in progress can
+//! be scored at any time. Match play and skins compare holes in order and
+//! stop at the first hole not yet scored by every player.
+//!
+//! # Examples
+//!
+//! ```
+//! use golf_score_tracker::Scorecard;
+//! use golf_score_tracker::services::{Competitor, FormatResult, ScoringFormat};
+//! use golf_score_tracker::utils::create_standard_pars;
+//! use uuid::Uuid;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut tiger = Scorecard::from_pars(Uuid::new_v4(), 9, create_standard_pars(9))?;
+//! let mut phil = Scorecard::from_pars(Uuid::new_v4(), 9, create_standard_pars(9))?;
+//! for hole in 1..=9 {
+//!     let par = tiger.get_par(hole).unwrap();
+//!     tiger.record_score(hole, par - u8::from(hole <= 4))?;
+//!     phil.record_score(hole, par)?;
+//! }
+//!
+//! let field = [Competitor::new("Tiger", &tiger, 0), Competitor::new("Phil", &phil, 0)];
+//! let FormatResult::MatchPlay(result) = ScoringFormat::MatchPlay.score(&field)? else { unreachable!() };
+//! assert_eq!(result.summary(), "Tiger wins 4&3");
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::error::{GolfError, Result};
+use crate::models::Scorecard;
+use crate::services::handicap::{course_handicap, strokes_received};
+
+/// Stableford points for a net par.
+const STABLEFORD_PAR_POINTS: i32 = 2;
+
+/// Game formats a round can be scored under.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScoringFormat {
+    /// Lowest total strokes.
+    #[default]
+    StrokePlay,
+    /// Hole-by-hole between exactly two players.
+    MatchPlay,
+    /// Points per hole from gross scores.
+    Stableford,
+    /// Points per hole after handicap strokes.
+    NetStableford,
+    /// Outright lowest score on a hole wins it, ties carry over.
+    Skins,
+}
+
+impl ScoringFormat {
+    /// All formats, in the order they are listed in help output.
+    pub const ALL: [ScoringFormat; 5] = [
+        ScoringFormat::StrokePlay,
+        ScoringFormat::MatchPlay,
+        ScoringFormat::Stableford,
+        ScoringFormat::NetStableford,
+        ScoringFormat::Skins,
+    ];
+
+    /// Name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            ScoringFormat::StrokePlay => "stroke-play",
+            ScoringFormat::MatchPlay => "match-play",
+            ScoringFormat::Stableford => "stableford",
+            ScoringFormat::NetStableford => "net-stableford",
+            ScoringFormat::Skins => "skins",
+        }
+    }
+
+    /// Scores the competitors' scorecards under this format.
+    ///
+    /// All scorecards must have the same number of holes, and match play
+    /// needs exactly two competitors.
+    pub fn score(self, competitors: &[Competitor<'_>]) -> Result<FormatResult> {
+        if competitors.is_empty() {
+            return Err(GolfError::custom("No scorecards to score"));
+        }
+        let holes = competitors[0].scorecard.max_holes;
+        if competitors.iter().any(|c| c.scorecard.max_holes != holes) {
+            return Err(GolfError::custom("Scorecards in a round must all have the same number of holes"));
+        }
+
+        Ok(match self {
+            ScoringFormat::StrokePlay => FormatResult::StrokePlay(stroke_play(competitors)),
+            ScoringFormat::MatchPlay => match competitors {
+                [first, second] => FormatResult::MatchPlay(match_play(first, second)),
+                _ => {
+                    return Err(GolfError::custom(format!(
+                        "Match play needs exactly 2 players, got {}",
+                        competitors.len()
+                    )));
+                }
+            },
+            ScoringFormat::Stableford => FormatResult::Stableford(stableford(competitors, false)),
+            ScoringFormat::NetStableford => FormatResult::Stableford(stableford(competitors, true)),
+            ScoringFormat::Skins => FormatResult::Skins(skins(competitors)),
+        })
+    }
+}
+
+impl fmt::Display for ScoringFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ScoringFormat {
+    type Err = GolfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized = s.trim().to_ascii_lowercase().replace(['_', ' '], "-");
+        match normalized.as_str() {
+            "stroke" => Ok(ScoringFormat::StrokePlay),
+            "match" => Ok(ScoringFormat::MatchPlay),
+            other => ScoringFormat::ALL
+                .into_iter()
+                .find(|format| format.name() == other)
+                .ok_or_else(|| GolfError::custom(format!(
+                    "Unknown scoring format '{}'. Expected one of: {}",
+                    s,
+                    ScoringFormat::ALL.map(ScoringFormat::name).join(", ")
+                ))),
+        }
+    }
+}
+
+/// A player's scorecard entered into a format, with the course handicap
+/// used for net formats.
+#[derive(Debug, Clone, Copy)]
+pub struct Competitor<'a> {
+    pub name: &'a str,
+    pub scorecard: &'a Scorecard,
+    pub course_handicap: i32,
+}
+
+impl<'a> Competitor<'a> {
+    pub fn new(name: &'a str, scorecard: &'a Scorecard, course_handicap: i32) -> Self {
+        Self { name, scorecard, course_handicap }
+    }
+
+    /// Enters a scorecard with the course handicap for `handicap_index` on
+    /// the scorecard's tees. Players without an index play off scratch.
+    pub fn with_index(name: &'a str, scorecard: &'a Scorecard, handicap_index: Option<f64>) -> Self {
+        let course_handicap = handicap_index
+            .map_or(0, |index| course_handicap(index, scorecard.effective_rating(), scorecard.total_par()));
+        Self::new(name, scorecard, course_handicap)
+    }
+
+    fn player_id(&self) -> Uuid {
+        self.scorecard.player_id
+    }
+
+    /// Handicap strokes received on `hole`.
+    fn strokes_received(&self, hole: u8) -> i32 {
+        let stroke_index = self.scorecard.stroke_index(hole).unwrap_or(hole);
+        strokes_received(self.course_handicap, stroke_index, self.scorecard.max_holes)
+    }
+}
+
+/// Outcome of scoring a round under a [`ScoringFormat`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatResult {
+    StrokePlay(Vec<Standing>),
+    MatchPlay(MatchResult),
+    Stableford(Vec<Standing>),
+    Skins(SkinsResult),
+}
+
+/// A competitor's position in a stroke play or Stableford field.
+///
+/// `score` is total strokes for stroke play and points for Stableford;
+/// `to_par` is the strokes relative to par over the holes played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub player_id: Uuid,
+    pub name: String,
+    pub score: i32,
+    pub to_par: i32,
+    pub thru: u8,
+}
+
+/// Progress and result of a singles match.
+///
+/// `hole_winners` has one entry per hole compared: the index of the player
+/// who won the hole (0 or 1), or `None` when it was halved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchResult {
+    pub players: [String; 2],
+    pub hole_winners: Vec<Option<usize>>,
+    pub holes: u8,
+}
+
+impl MatchResult {
+    /// Holes up for the leading player and who that is, `None` when all
+    /// square.
+    pub fn lead(&self) -> (Option<usize>, u8) {
+        let mut margin = 0i32;
+        for winner in self.hole_winners.iter().flatten() {
+            margin += if *winner == 0 { 1 } else { -1 };
+        }
+        match margin {
+            0 => (None, 0),
+            m if m > 0 => (Some(0), m as u8),
+            m => (Some(1), m.unsigned_abs() as u8),
+        }
+    }
+
+    pub fn holes_played(&self) -> u8 {
+        self.hole_winners.len() as u8
+    }
+
+    pub fn holes_remaining(&self) -> u8 {
+        self.holes - self.holes_played()
+    }
+
+    /// Whether the match is over, either because a player leads by more
+    /// holes than remain or because every hole has been played.
+    pub fn is_decided(&self) -> bool {
+        self.lead().1 > self.holes_remaining() || self.holes_remaining() == 0
+    }
+
+    /// The match result in the usual notation: "Tiger wins 3&2",
+    /// "Tiger wins 1 up", "Halved", or while in progress
+    /// "Tiger 2 up thru 7" and "All square thru 7".
+    pub fn summary(&self) -> String {
+        let (leader, up) = self.lead();
+        let remaining = self.holes_remaining();
+        match leader {
+            None if remaining == 0 => "Halved".to_string(),
+            None => format!("All square thru {}", self.holes_played()),
+            Some(player) if remaining == 0 => format!("{} wins {} up", self.players[player], up),
+            Some(player) if up > remaining => format!("{} wins {}&{}", self.players[player], up, remaining),
+            Some(player) => format!("{} {} up thru {}", self.players[player], up, self.holes_played()),
+        }
+    }
+}
+
+/// Skins won on a single hole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkinHole {
+    pub hole: u8,
+    /// Index of the competitor who won the hole outright, if anyone did.
+    pub winner: Option<usize>,
+    /// Skins at stake on the hole, including any carried over.
+    pub value: u32,
+}
+
+/// Skins game result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkinsResult {
+    pub players: Vec<String>,
+    pub holes: Vec<SkinHole>,
+    /// Skins still carrying over after the last hole compared.
+    pub carryover: u32,
+}
+
+impl SkinsResult {
+    /// Skins won by each competitor, in the order they were entered.
+    pub fn totals(&self) -> Vec<u32> {
+        let mut totals = vec![0; self.players.len()];
+        for hole in &self.holes {
+            if let Some(winner) = hole.winner {
+                totals[winner] += hole.value;
+            }
+        }
+        totals
+    }
+}
+
+fn stroke_play(competitors: &[Competitor<'_>]) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = competitors
+        .iter()
+        .map(|competitor| {
+            let card = competitor.scorecard;
+            let (mut strokes, mut par) = (0, 0);
+            for (&hole, &score) in card.scores() {
+                strokes += i32::from(score);
+                par += i32::from(card.get_par(hole).unwrap_or(0));
+            }
+            Standing {
+                player_id: competitor.player_id(),
+                name: competitor.name.to_string(),
+                score: strokes,
+                to_par: strokes - par,
+                thru: card.scores().len() as u8,
+            }
+        })
+        .collect();
+    standings.sort_by(|a, b| a.to_par.cmp(&b.to_par).then_with(|| b.thru.cmp(&a.thru)));
+    standings
+}
+
+fn stableford(competitors: &[Competitor<'_>], net: bool) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = competitors
+        .iter()
+        .map(|competitor| {
+            let card = competitor.scorecard;
+            let (mut points, mut to_par) = (0, 0);
+            for (&hole, &score) in card.scores() {
+                let par = i32::from(card.get_par(hole).unwrap_or(0));
+                let received = if net { competitor.strokes_received(hole) } else { 0 };
+                let net_score = i32::from(score) - received;
+                points += (STABLEFORD_PAR_POINTS + par - net_score).max(0);
+                to_par += net_score - par;
+            }
+            Standing {
+                player_id: competitor.player_id(),
+                name: competitor.name.to_string(),
+                score: points,
+                to_par,
+                thru: card.scores().len() as u8,
+            }
+        })
+        .collect();
+    standings.sort_by_key(|standing| std::cmp::Reverse(standing.score));
+    standings
+}
+
+/// Holes scored by every competitor, in order up to the first gap.
+fn holes_in_common(competitors: &[Competitor<'_>]) -> impl Iterator<Item = (u8, Vec<u8>)> {
+    (1..=competitors[0].scorecard.max_holes)
+        .map(|hole| (hole, competitors.iter().map(|c| c.scorecard.get_score(hole)).collect::<Option<Vec<u8>>>()))
+        .map_while(|(hole, scores)| scores.map(|scores| (hole, scores)))
+}
+
+fn match_play(first: &Competitor<'_>, second: &Competitor<'_>) -> MatchResult {
+    let mut result = MatchResult {
+        players: [first.name.to_string(), second.name.to_string()],
+        hole_winners: Vec::new(),
+        holes: first.scorecard.max_holes,
+    };
+    for (_, scores) in holes_in_common(&[*first, *second]) {
+        if result.is_decided() {
+            break;
+        }
+        result.hole_winners.push(match scores[0].cmp(&scores[1]) {
+            std::cmp::Ordering::Less => Some(0),
+            std::cmp::Ordering::Greater => Some(1),
+            std::cmp::Ordering::Equal => None,
+        });
+    }
+    result
+}
+
+fn skins(competitors: &[Competitor<'_>]) -> SkinsResult {
+    let mut result = SkinsResult {
+        players: competitors.iter().map(|c| c.name.to_string()).collect(),
+        holes: Vec::new(),
+        carryover: 0,
+    };
+    for (hole, scores) in holes_in_common(competitors) {
+        let value = result.carryover + 1;
+        let best = scores.iter().min().copied();
+        let winners: Vec<usize> = (0..scores.len()).filter(|&i| Some(scores[i]) == best).collect();
+        let winner = match winners[..] {
+            [only] => Some(only),
+            _ => None,
+        };
+        result.carryover = if winner.is_some() { 0 } else { value };
+        result.holes.push(SkinHole { hole, winner, value });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_standard_pars;
+
+    fn card(scores: &[u8]) -> Scorecard {
+        let holes = 9;
+        let mut card = Scorecard::from_pars(Uuid::new_v4(), holes, create_standard_pars(holes)).expect("card");
+        for (hole, &strokes) in (1..).zip(scores) {
+            card.record_score(hole, strokes).expect("record");
+        }
+        card
+    }
+
+    #[test]
+    fn match_play_reports_holes_up_while_in_progress() {
+        // Standard pars 4,3,5,...
+        let a = card(&[4, 3, 5, 4]);
+        let b = card(&[5, 3, 4, 5]);
+        let field = [Competitor::new("Rory", &a, 0), Competitor::new("Shane", &b, 0)];
+
+        let FormatResult::MatchPlay(result) = ScoringFormat::MatchPlay.score(&field).expect("score") else {
+            panic!("expected match play");
+        };
+
+        assert_eq!(result.hole_winners, vec![Some(0), None, Some(1), Some(0)]);
+        assert_eq!(result.summary(), "Rory 1 up thru 4");
+        assert!(!result.is_decided());
+    }
+
+    #[test]
+    fn match_play_decided_on_last_hole_is_won_up() {
+        let a = card(&[4, 3, 5, 4, 3, 5, 4, 3, 4]);
+        let b = card(&[4, 3, 5, 4, 3, 5, 4, 3, 5]);
+        let field = [Competitor::new("Rory", &a, 0), Competitor::new("Shane", &b, 0)];
+
+        let FormatResult::MatchPlay(result) = ScoringFormat::MatchPlay.score(&field).expect("score") else {
+            panic!("expected match play");
+        };
+
+        assert_eq!(result.summary(), "Rory wins 1 up");
+    }
+
+    #[test]
+    fn match_play_needs_two_players() {
+        let a = card(&[]);
+        let field = [Competitor::new("Solo", &a, 0)];
+
+        assert!(ScoringFormat::MatchPlay.score(&field).is_err());
+    }
+
+    #[test]
+    fn net_stableford_applies_strokes_by_stroke_index() {
+        // Par 4, 3, 5: bogey, bogey, double bogey
+        let a = card(&[5, 4, 7]);
+        let gross = [Competitor::new("Viktor", &a, 0)];
+        // A 9 handicap gets a stroke on every hole of a 9-hole card
+        let net = [Competitor::new("Viktor", &a, 9)];
+
+        let FormatResult::Stableford(gross) = ScoringFormat::Stableford.score(&gross).expect("score") else {
+            panic!("expected stableford");
+        };
+        let FormatResult::Stableford(net) = ScoringFormat::NetStableford.score(&net).expect("score") else {
+            panic!("expected stableford");
+        };
+
+        assert_eq!(gross[0].score, 1 + 1);
+        assert_eq!(net[0].score, 2 + 2 + 1);
+        assert_eq!(net[0].thru, 3);
+    }
+
+    #[test]
+    fn tied_skins_carry_over_to_the_next_winner() {
+        let a = card(&[4, 3, 4, 5]);
+        let b = card(&[4, 3, 5, 4]);
+        let c = card(&[5, 3, 5, 5]);
+        let field = [Competitor::new("A", &a, 0), Competitor::new("B", &b, 0), Competitor::new("C", &c, 0)];
+
+        let FormatResult::Skins(result) = ScoringFormat::Skins.score(&field).expect("score") else {
+            panic!("expected skins");
+        };
+
+        assert_eq!(result.holes[2], SkinHole { hole: 3, winner: Some(0), value: 3 });
+        assert_eq!(result.totals(), vec![3, 1, 0]);
+        assert_eq!(result.carryover, 0);
+    }
+
+    #[test]
+    fn format_names_round_trip() {
+        for format in ScoringFormat::ALL {
+            assert_eq!(format.name().parse::<ScoringFormat>().expect("parse"), format);
+        }
+        assert_eq!("Net Stableford".parse::<ScoringFormat>().expect("parse"), ScoringFormat::NetStableford);
+        assert!("bingo-bango-bongo".parse::<ScoringFormat>().is_err());
+    }
+}
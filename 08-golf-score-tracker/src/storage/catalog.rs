@@ -0,0 +1,361 @@
+//! Course catalog combining the built-in courses with user-defined ones.
+//!
+//! User courses live in a `courses/` directory inside the data directory,
+//! one file per course, written as TOML (`<key>.toml`) or JSON
+//! (`<key>.json`). The file stem is the key used to look the course up, so
+//! a local course is registered by dropping a file in that directory or with
+//! [`CourseCatalog::add`]. A user course whose key matches a built-in one
+//! replaces it.
+//!
+//! # Examples
+//!
+//! ```toml
+//! name = "Riverside Nine"
+//!
+//! [[holes]]
+//! number = 1
+//! par = 4
+//! yardage = 385
+//! stroke_index = 3
+//!
+//! # ... one [[holes]] table per hole
+//!
+//! [[tees]]
+//! name = "White"
+//! course_rating = 35.9
+//! slope_rating = 121
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::{GolfError, Result};
+use crate::models::Course;
+use crate::storage::repository::write_atomically;
+use crate::storage::{JsonSerializer, Serializer, TomlSerializer};
+use crate::utils::course::{get_course_catalog, resolve_course};
+
+/// Directory inside the data directory that holds user course files.
+pub const COURSES_DIR: &str = "courses";
+
+/// Where a catalog course came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CourseSource {
+    /// Compiled into the program.
+    BuiltIn,
+    /// Loaded from a course file.
+    File(PathBuf),
+}
+
+/// A course in the catalog together with its lookup key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogEntry {
+    pub key: String,
+    pub course: Course,
+    pub source: CourseSource,
+}
+
+/// Built-in and user-defined courses, keyed by catalog key.
+///
+/// # Examples
+///
+/// ```no_run
+/// use golf_score_tracker::storage::CourseCatalog;
+///
+/// let catalog = CourseCatalog::load("./golf_data")?;
+/// for entry in catalog.entries() {
+///     println!("{}: par {}", entry.key, entry.course.total_par());
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct CourseCatalog {
+    dir: PathBuf,
+    entries: BTreeMap<String, CatalogEntry>,
+}
+
+impl CourseCatalog {
+    /// Loads the built-in courses and every course file under
+    /// `data_dir/courses`.
+    ///
+    /// A missing directory simply means there are no user courses. Files
+    /// that cannot be decoded or fail validation are reported as
+    /// `GolfError::InvalidCourseFile` rather than skipped, so a broken
+    /// definition is never silently ignored.
+    pub fn load(data_dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = data_dir.as_ref().join(COURSES_DIR);
+        let mut entries: BTreeMap<String, CatalogEntry> = get_course_catalog()
+            .into_iter()
+            .map(|(key, generator)| {
+                let entry = CatalogEntry { key: key.clone(), course: generator(), source: CourseSource::BuiltIn };
+                (key, entry)
+            })
+            .collect();
+
+        if dir.is_dir() {
+            let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<std::io::Result<_>>()?;
+            paths.sort();
+            for path in paths {
+                let Some(key) = course_key_of(&path) else { continue };
+                if let Some(existing) = entries.get(&key)
+                    && existing.source != CourseSource::BuiltIn
+                {
+                    return Err(GolfError::InvalidCourseFile {
+                        path,
+                        reason: format!("course '{}' is also defined in another file", key),
+                    });
+                }
+                let course = read_course_file(&path)?;
+                entries.insert(key.clone(), CatalogEntry { key, course, source: CourseSource::File(path) });
+            }
+        }
+
+        Ok(Self { dir, entries })
+    }
+
+    /// Directory user course files are read from and written to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Looks up a course by its catalog key.
+    pub fn get(&self, key: &str) -> Option<&CatalogEntry> {
+        self.entries.get(key)
+    }
+
+    /// Looks up a course by key, name or alias, ignoring case and
+    /// punctuation. See [`resolve_course`] for the matching rules.
+    ///
+    /// # Errors
+    ///
+    /// `GolfError::CourseNotFound` with suggestions for close matches.
+    pub fn find(&self, query: &str) -> Result<&CatalogEntry> {
+        let key = resolve_course(query, self.entries.iter().map(|(key, entry)| (key.as_str(), &entry.course)))?;
+        Ok(&self.entries[key])
+    }
+
+    /// Every course in the catalog, ordered by key.
+    pub fn entries(&self) -> impl Iterator<Item = &CatalogEntry> {
+        self.entries.values()
+    }
+
+    /// Validates `course` and writes it to `courses/<key>.toml`.
+    ///
+    /// The key is derived from the course name with [`course_key`]. An
+    /// existing course with the same key is only overwritten (or, for a
+    /// built-in course, shadowed) when `replace` is set.
+    pub fn add(&mut self, course: Course, replace: bool) -> Result<&CatalogEntry> {
+        course.validate()?;
+        let key = course_key(&course.name)?;
+        let mut replaced = None;
+        if let Some(existing) = self.entries.get(&key) {
+            if !replace {
+                return Err(GolfError::custom(format!(
+                    "Course '{}' already exists. Use --replace to overwrite it.",
+                    key
+                )));
+            }
+            if let CourseSource::File(path) = &existing.source {
+                replaced = Some(path.clone());
+            }
+        }
+
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!("{}.{}", key, TomlSerializer.extension()));
+        write_atomically(&path, &TomlSerializer.serialize(&course)?)?;
+        // The rename replaces a file of the same name; one saved under
+        // another name, such as a hand-written JSON file, goes once the new
+        // file is in place
+        if let Some(replaced) = replaced
+            && replaced != path
+        {
+            std::fs::remove_file(replaced)?;
+        }
+
+        let entry = CatalogEntry { key: key.clone(), course, source: CourseSource::File(path) };
+        self.entries.insert(key.clone(), entry);
+        Ok(&self.entries[&key])
+    }
+
+    /// Deletes a user-defined course and returns it.
+    ///
+    /// Built-in courses cannot be removed. Removing a user course that
+    /// shadowed a built-in one brings the built-in course back.
+    pub fn remove(&mut self, key: &str) -> Result<Course> {
+        let entry = self.entries.get(key).ok_or_else(|| GolfError::CourseNotFound {
+            name: key.to_string(),
+            suggestions: Vec::new(),
+        })?;
+        let CourseSource::File(path) = &entry.source else {
+            return Err(GolfError::custom(format!("Course '{}' is built in and cannot be removed", key)));
+        };
+        std::fs::remove_file(path)?;
+
+        let removed = self.entries.remove(key).expect("entry was just found").course;
+        if let Some(generator) = get_course_catalog().get(key) {
+            let entry = CatalogEntry { key: key.to_string(), course: generator(), source: CourseSource::BuiltIn };
+            self.entries.insert(key.to_string(), entry);
+        }
+        Ok(removed)
+    }
+}
+
+/// Derives the catalog key for a course name, e.g. `Riverside Nine`
+/// becomes `Riverside_Nine`.
+///
+/// Keys double as file names, so only letters, digits, `_` and `-` are
+/// allowed once spaces are replaced.
+pub fn course_key(name: &str) -> Result<String> {
+    let key = name.split_whitespace().collect::<Vec<_>>().join("_");
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(GolfError::custom(format!(
+            "Course name '{}' can only contain letters, digits, spaces, '_' and '-'",
+            name
+        )));
+    }
+    Ok(key)
+}
+
+/// Key of a course file, or `None` for files that are not course
+/// definitions (wrong extension, hidden or temporary files).
+fn course_key_of(path: &Path) -> Option<String> {
+    let extension = path.extension()?.to_str()?;
+    let stem = path.file_stem()?.to_str()?;
+    let is_course = extension == TomlSerializer.extension() || extension == JsonSerializer.extension();
+    (is_course && !stem.starts_with('.')).then(|| stem.to_string())
+}
+
+/// Reads and validates a single course definition file (TOML or JSON,
+/// chosen by extension).
+pub fn read_course_file(path: &Path) -> Result<Course> {
+    let bytes = std::fs::read(path)?;
+    let invalid = |error: GolfError| GolfError::InvalidCourseFile { path: path.to_path_buf(), reason: error.to_string() };
+    let course: Course = if path.extension().and_then(|ext| ext.to_str()) == Some(TomlSerializer.extension()) {
+        TomlSerializer.deserialize(&bytes)
+    } else {
+        JsonSerializer.deserialize(&bytes)
+    }
+    .map_err(|error| match error {
+        GolfError::SerializationError(inner) => invalid(GolfError::custom(inner.to_string())),
+        other => invalid(other),
+    })?;
+    course.validate().map_err(invalid)?;
+    Ok(course)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Hole, TeeSet};
+    use crate::test_support::TempDataDir;
+
+    fn riverside() -> Course {
+        let holes = (1..=9).map(|number| Hole::new(number, 4, 9).expect("hole").with_yardage(360)).collect();
+        Course::new("Riverside Nine", holes, vec![TeeSet::new("White", 35.9, 121).expect("tee")]).expect("course")
+    }
+
+    #[test]
+    fn added_courses_are_loaded_back() {
+        let dir = TempDataDir::new();
+        let mut catalog = CourseCatalog::load(&dir.path).expect("load");
+        catalog.add(riverside(), false).expect("add");
+
+        let reloaded = CourseCatalog::load(&dir.path).expect("reload");
+        let entry = reloaded.get("Riverside_Nine").expect("user course");
+        assert_eq!(entry.course, riverside());
+        assert_eq!(entry.source, CourseSource::File(dir.path.join("courses/Riverside_Nine.toml")));
+        assert!(reloaded.get("Pebble_Beach").is_some());
+    }
+
+    #[test]
+    fn json_course_files_are_loaded() {
+        let dir = TempDataDir::new();
+        std::fs::create_dir_all(dir.path.join(COURSES_DIR)).expect("courses dir");
+        let json = serde_json::to_vec(&riverside()).expect("json");
+        std::fs::write(dir.path.join("courses/river.json"), json).expect("write");
+
+        let catalog = CourseCatalog::load(&dir.path).expect("load");
+        assert_eq!(catalog.get("river").expect("course").course, riverside());
+    }
+
+    #[test]
+    fn invalid_course_file_is_reported() {
+        let dir = TempDataDir::new();
+        std::fs::create_dir_all(dir.path.join(COURSES_DIR)).expect("courses dir");
+        let bad = riverside_with_par(7);
+        std::fs::write(dir.path.join("courses/bad.toml"), bad).expect("write");
+
+        let error = CourseCatalog::load(&dir.path).unwrap_err();
+        assert!(matches!(error, GolfError::InvalidCourseFile { .. }), "{:?}", error);
+        assert!(error.to_string().contains("Par 7"), "{}", error);
+    }
+
+    fn riverside_with_par(par: u8) -> String {
+        let toml = String::from_utf8(TomlSerializer.serialize(&riverside()).expect("toml")).expect("utf8");
+        toml.replacen("par = 4", &format!("par = {}", par), 1)
+    }
+
+    #[test]
+    fn existing_courses_need_replace() {
+        let dir = TempDataDir::new();
+        let mut catalog = CourseCatalog::load(&dir.path).expect("load");
+        catalog.add(riverside(), false).expect("add");
+        assert!(catalog.add(riverside(), false).is_err());
+        assert!(catalog.add(riverside(), true).is_ok());
+    }
+
+    #[test]
+    fn replacing_a_json_course_leaves_only_the_new_file() {
+        let dir = TempDataDir::new();
+        std::fs::create_dir_all(dir.path.join(COURSES_DIR)).expect("courses dir");
+        let json = serde_json::to_vec(&riverside()).expect("json");
+        std::fs::write(dir.path.join("courses/Riverside_Nine.json"), json).expect("write");
+        let mut catalog = CourseCatalog::load(&dir.path).expect("load");
+
+        let updated = riverside().with_aliases(["The River"]);
+        catalog.add(updated.clone(), true).expect("replace");
+        let files: Vec<_> = std::fs::read_dir(dir.path.join(COURSES_DIR))
+            .expect("list")
+            .map(|entry| entry.expect("entry").file_name())
+            .collect();
+        assert_eq!(files, ["Riverside_Nine.toml"]);
+        assert_eq!(CourseCatalog::load(&dir.path).expect("reload").get("Riverside_Nine").expect("course").course, updated);
+    }
+
+    #[test]
+    fn removing_a_shadowing_course_restores_the_built_in() {
+        let dir = TempDataDir::new();
+        let mut catalog = CourseCatalog::load(&dir.path).expect("load");
+        let mut local = riverside();
+        local.name = "Pebble Beach".to_string();
+        catalog.add(local.clone(), true).expect("shadow");
+        assert_eq!(catalog.get("Pebble_Beach").expect("course").course, local);
+
+        assert_eq!(catalog.remove("Pebble_Beach").expect("remove"), local);
+        assert_eq!(catalog.get("Pebble_Beach").expect("course").source, CourseSource::BuiltIn);
+        assert!(catalog.remove("Pebble_Beach").is_err());
+    }
+
+    #[test]
+    fn find_matches_user_courses_loosely() {
+        let dir = TempDataDir::new();
+        let mut catalog = CourseCatalog::load(&dir.path).expect("load");
+        catalog.add(riverside().with_aliases(["The River"]), false).expect("add");
+
+        assert_eq!(catalog.find("riverside nine").expect("by name").key, "Riverside_Nine");
+        assert_eq!(catalog.find("the-river").expect("by alias").key, "Riverside_Nine");
+        assert!(matches!(
+            catalog.find("Riverside Nien"),
+            Err(GolfError::CourseNotFound { suggestions, .. }) if suggestions == ["Riverside_Nine"]
+        ));
+    }
+
+    #[test]
+    fn course_keys_replace_spaces() {
+        assert_eq!(course_key("  Riverside   Nine ").unwrap(), "Riverside_Nine");
+        assert!(course_key("../etc").is_err());
+        assert!(course_key("   ").is_err());
+    }
+}
@@ -0,0 +1,297 @@
+//! Where the tracker keeps its data.
+//!
+//! The data directory is chosen, in order of precedence, from:
+//!
+//! 1. the `--data-dir` flag
+//! 2. the `--profile` flag, naming a profile in the config file
+//! 3. the `GOLF_TRACKER_DATA` environment variable
+//! 4. the config file's `default_profile`
+//! 5. the platform data directory, e.g. `~/.local/share/golf-score-tracker`
+//!    on Linux
+//!
+//! A `golf_data/` directory in the working directory, where older versions
+//! kept everything, is still used when the platform data directory has not
+//! been created yet, so existing data is not lost.
+//!
+//! Profiles live in `config.toml` in the platform config directory
+//! (`~/.config/golf-score-tracker/config.toml` on Linux), or wherever
+//! `GOLF_TRACKER_CONFIG` points. Relative profile paths are relative to the
+//! config file and `~` expands to the home directory.
+//!
+//! # Examples
+//!
+//! ```toml
+//! default_profile = "home-club"
+//!
+//! [profiles.home-club]
+//! data_dir = "~/golf/home-club"
+//!
+//! [profiles.travel]
+//! data_dir = "~/Dropbox/golf-travel"
+//! ```
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GolfError, Result};
+use crate::storage::repository::write_atomically;
+use crate::storage::{Serializer, TomlSerializer};
+
+/// Environment variable overriding the data directory.
+pub const DATA_DIR_ENV: &str = "GOLF_TRACKER_DATA";
+
+/// Environment variable overriding the config file location.
+pub const CONFIG_ENV: &str = "GOLF_TRACKER_CONFIG";
+
+/// Data directory used before it became configurable, relative to the
+/// working directory.
+pub const LEGACY_DATA_DIR: &str = "golf_data";
+
+/// Name of the tracker's directory inside the platform data and config
+/// directories.
+const APP_DIR: &str = "golf-score-tracker";
+
+/// Named data directories, read from `config.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    /// Profile used when no data directory is given on the command line or
+    /// in the environment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// A named data directory, such as one per club.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub data_dir: PathBuf,
+}
+
+impl Config {
+    /// Location of the config file: `GOLF_TRACKER_CONFIG` if set, else
+    /// `config.toml` in the platform config directory.
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::var_os(CONFIG_ENV)
+            .map(PathBuf::from)
+            .or_else(|| dirs::config_dir().map(|dir| dir.join(APP_DIR).join("config.toml")))
+    }
+
+    /// Reads a config file. A missing file is an empty config.
+    ///
+    /// Profile directories are made absolute: `~` is expanded and relative
+    /// paths are taken from the directory holding the config file.
+    ///
+    /// # Errors
+    ///
+    /// `GolfError::InvalidConfig` if the file is not valid TOML or a profile
+    /// is incomplete.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let bytes = std::fs::read(path)?;
+        let mut config: Config = TomlSerializer
+            .deserialize(&bytes)
+            .map_err(|error| GolfError::InvalidConfig { path: path.to_path_buf(), reason: error.to_string() })?;
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        for profile in config.profiles.values_mut() {
+            profile.data_dir = absolute(base, &profile.data_dir);
+        }
+        if let Some(name) = &config.default_profile
+            && !config.profiles.contains_key(name)
+        {
+            return Err(GolfError::InvalidConfig {
+                path: path.to_path_buf(),
+                reason: format!("default_profile '{}' is not defined under [profiles]", name),
+            });
+        }
+        Ok(config)
+    }
+
+    /// Writes the config file, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_atomically(path, &TomlSerializer.serialize(self)?)
+    }
+
+    /// Looks up a profile by name.
+    ///
+    /// # Errors
+    ///
+    /// `GolfError::UnknownProfile`, listing the profiles that exist.
+    pub fn profile(&self, name: &str) -> Result<&Profile> {
+        self.profiles.get(name).ok_or_else(|| GolfError::UnknownProfile {
+            name: name.to_string(),
+            available: self.profiles.keys().cloned().collect(),
+        })
+    }
+
+    /// Adds or replaces a profile. Names may contain letters, digits, `_`
+    /// and `-`.
+    pub fn add_profile(&mut self, name: &str, data_dir: PathBuf) -> Result<()> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(GolfError::custom(format!(
+                "Profile name '{}' can only contain letters, digits, '_' and '-'",
+                name
+            )));
+        }
+        self.profiles.insert(name.to_string(), Profile { data_dir });
+        Ok(())
+    }
+
+    /// Removes a profile, and clears the default if it was this one.
+    pub fn remove_profile(&mut self, name: &str) -> Result<Profile> {
+        self.profile(name)?;
+        if self.default_profile.as_deref() == Some(name) {
+            self.default_profile = None;
+        }
+        Ok(self.profiles.remove(name).expect("profile was just found"))
+    }
+}
+
+/// Where a [`DataDir`] came from, so the user can tell why it was chosen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataDirSource {
+    Flag,
+    Profile(String),
+    Environment,
+    DefaultProfile(String),
+    Platform,
+    /// `./golf_data`, kept for data written by older versions.
+    Legacy,
+}
+
+impl fmt::Display for DataDirSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataDirSource::Flag => f.write_str("--data-dir"),
+            DataDirSource::Profile(name) => write!(f, "profile '{}'", name),
+            DataDirSource::Environment => f.write_str(DATA_DIR_ENV),
+            DataDirSource::DefaultProfile(name) => write!(f, "default profile '{}'", name),
+            DataDirSource::Platform => f.write_str("platform data directory"),
+            DataDirSource::Legacy => write!(f, "./{} from an older version", LEGACY_DATA_DIR),
+        }
+    }
+}
+
+/// The data directory in use and how it was chosen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataDir {
+    pub path: PathBuf,
+    pub source: DataDirSource,
+}
+
+/// Chooses the data directory following the precedence described in the
+/// [module documentation](self).
+///
+/// `env` looks up environment variables, so callers and tests can supply
+/// their own.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::{Path, PathBuf};
+/// use golf_score_tracker::storage::{Config, DataDirSource, resolve_data_dir};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut config = Config::default();
+/// config.add_profile("travel", PathBuf::from("/golf/travel"))?;
+/// let env = |name: &str| (name == "GOLF_TRACKER_DATA").then(|| "/golf/env".into());
+///
+/// let chosen = resolve_data_dir(None, Some("travel"), &config, env)?;
+/// assert_eq!(chosen.path, Path::new("/golf/travel"));
+///
+/// let chosen = resolve_data_dir(None, None, &config, env)?;
+/// assert_eq!(chosen.source, DataDirSource::Environment);
+/// # Ok(())
+/// # }
+/// ```
+pub fn resolve_data_dir(
+    flag: Option<&Path>,
+    profile: Option<&str>,
+    config: &Config,
+    env: impl Fn(&str) -> Option<OsString>,
+) -> Result<DataDir> {
+    if let Some(path) = flag {
+        return Ok(DataDir { path: path.to_path_buf(), source: DataDirSource::Flag });
+    }
+    if let Some(name) = profile {
+        let path = config.profile(name)?.data_dir.clone();
+        return Ok(DataDir { path, source: DataDirSource::Profile(name.to_string()) });
+    }
+    if let Some(path) = env(DATA_DIR_ENV).filter(|path| !path.is_empty()) {
+        return Ok(DataDir { path: PathBuf::from(path), source: DataDirSource::Environment });
+    }
+    if let Some(name) = &config.default_profile {
+        let path = config.profile(name)?.data_dir.clone();
+        return Ok(DataDir { path, source: DataDirSource::DefaultProfile(name.clone()) });
+    }
+
+    let legacy = PathBuf::from(LEGACY_DATA_DIR);
+    match dirs::data_dir().map(|dir| dir.join(APP_DIR)) {
+        Some(path) if path.exists() || !legacy.is_dir() => Ok(DataDir { path, source: DataDirSource::Platform }),
+        _ => Ok(DataDir { path: legacy, source: DataDirSource::Legacy }),
+    }
+}
+
+/// Expands `~` and anchors relative paths at `base`.
+fn absolute(base: &Path, path: &Path) -> PathBuf {
+    let path = match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    };
+    if path.is_absolute() { path } else { base.join(path) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDataDir;
+
+    fn no_env(_: &str) -> Option<OsString> {
+        None
+    }
+
+    #[test]
+    fn flag_wins_over_profiles_and_environment() {
+        let mut config = Config::default();
+        config.add_profile("home", PathBuf::from("/golf/home")).expect("profile");
+        config.default_profile = Some("home".to_string());
+        let env = |_: &str| Some(OsString::from("/golf/env"));
+
+        let chosen = resolve_data_dir(Some(Path::new("/golf/flag")), Some("home"), &config, env).expect("resolve");
+        assert_eq!(chosen, DataDir { path: PathBuf::from("/golf/flag"), source: DataDirSource::Flag });
+
+        let chosen = resolve_data_dir(None, None, &config, no_env).expect("resolve");
+        assert_eq!(chosen.source, DataDirSource::DefaultProfile("home".to_string()));
+
+        let error = resolve_data_dir(None, Some("travel"), &config, no_env).unwrap_err();
+        assert!(matches!(error, GolfError::UnknownProfile { ref available, .. } if available == &["home"]));
+    }
+
+    #[test]
+    fn config_paths_are_made_absolute_and_saved_profiles_reload() {
+        let dir = TempDataDir::new();
+        let path = dir.path.join("config.toml");
+        std::fs::write(&path, "default_profile = \"club\"\n\n[profiles.club]\ndata_dir = \"club-data\"\n").expect("write");
+
+        let mut config = Config::load(&path).expect("load");
+        assert_eq!(config.profile("club").expect("club").data_dir, dir.path.join("club-data"));
+
+        config.add_profile("travel", PathBuf::from("/golf/travel")).expect("profile");
+        config.remove_profile("club").expect("remove");
+        config.save(&path).expect("save");
+        let reloaded = Config::load(&path).expect("reload");
+
+        assert_eq!(reloaded, config);
+        assert_eq!(reloaded.default_profile, None);
+    }
+}
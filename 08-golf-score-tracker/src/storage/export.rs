@@ -0,0 +1,568 @@
+//! Export and import of tracker data.
+//!
+//! A [`Bundle`] holds every player and scorecard (and, in JSON, the group
+//! rounds and tournaments linking them) independent of the storage backend,
+//! so data can be moved between data directories or taken into a
+//! spreadsheet. Two formats are supported:
+//!
+//! * JSON - a single document with the whole bundle
+//! * CSV - one row per hole of every scorecard, with the player and round
+//!   details repeated on each row. Players without scorecards get a single
+//!   row with the scorecard columns left empty. The score history is not
+//!   exported.
+//!
+//! Importing a bundle checks for IDs that are already stored; what happens
+//! to them is decided by a [`DuplicatePolicy`].
+//!
+//! # Examples
+//!
+//! ```
+//! use golf_score_tracker::{Player, Repository, Scorecard};
+//! use golf_score_tracker::storage::{Bundle, DuplicatePolicy, ExportFormat, InMemoryRepository};
+//! use golf_score_tracker::utils::create_standard_course;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut repo = InMemoryRepository::new();
+//! let player = Player::new("Ludvig Aberg", Some(0.4))?;
+//! let course = create_standard_course(9);
+//! let mut card = Scorecard::new(player.id, &course, course.default_tee())?;
+//! card.record_score(1, 4)?;
+//! repo.save_player(&player)?;
+//! repo.save_scorecard(&card)?;
+//!
+//! let mut csv = Vec::new();
+//! Bundle::from_repository(&repo)?.write(ExportFormat::Csv, &mut csv)?;
+//! let bundle = Bundle::read(ExportFormat::Csv, csv.as_slice())?;
+//!
+//! let mut copy = InMemoryRepository::new();
+//! bundle.import_into(&mut copy, None)?;
+//! assert_eq!(copy.get_scorecard(&card.round_id)?.unwrap().scores(), card.scores());
+//!
+//! // Importing again finds the same IDs
+//! assert!(bundle.import_into(&mut copy, None).is_err());
+//! assert_eq!(bundle.import_into(&mut copy, Some(DuplicatePolicy::Skip))?.skipped, 2);
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{GolfError, Result};
+use crate::models::{CourseRating, Fairway, HoleResult, Player, Round, Scorecard, Tournament};
+use crate::storage::Repository;
+use crate::utils::validators::validate_hole_result;
+
+/// Version of the JSON bundle layout, bumped on incompatible changes.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// File formats for exported data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// The whole bundle as one JSON document.
+    #[default]
+    Json,
+    /// One row per hole, for spreadsheets.
+    Csv,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 2] = [ExportFormat::Json, ExportFormat::Csv];
+
+    /// Name used on the command line, which is also the file extension.
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+        }
+    }
+
+    /// Format matching a file's extension, if it is a known one.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        extension.parse().ok()
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = GolfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized = s.trim().to_ascii_lowercase();
+        ExportFormat::ALL
+            .into_iter()
+            .find(|format| format.name() == normalized)
+            .ok_or_else(|| GolfError::custom(format!("Unknown export format '{}'. Expected json or csv", s)))
+    }
+}
+
+/// What to do with imported records whose ID is already stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Update the stored record from the import. Scorecards take the
+    /// imported holes and round details and keep holes that were only
+    /// scored locally, and their history.
+    Merge,
+    /// Keep the stored record and ignore the imported one.
+    Skip,
+}
+
+impl DuplicatePolicy {
+    pub const ALL: [DuplicatePolicy; 2] = [DuplicatePolicy::Merge, DuplicatePolicy::Skip];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DuplicatePolicy::Merge => "merge",
+            DuplicatePolicy::Skip => "skip",
+        }
+    }
+}
+
+impl fmt::Display for DuplicatePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for DuplicatePolicy {
+    type Err = GolfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized = s.trim().to_ascii_lowercase();
+        DuplicatePolicy::ALL
+            .into_iter()
+            .find(|policy| policy.name() == normalized)
+            .ok_or_else(|| GolfError::custom(format!("Unknown duplicate policy '{}'. Expected merge or skip", s)))
+    }
+}
+
+/// Counts of what an import did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub added: usize,
+    pub merged: usize,
+    pub skipped: usize,
+}
+
+/// Players and scorecards exported from a repository.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Bundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub players: Vec<Player>,
+    pub scorecards: Vec<Scorecard>,
+    /// Group rounds, only carried by JSON bundles.
+    #[serde(default)]
+    pub rounds: Vec<Round>,
+    /// Tournaments, only carried by JSON bundles.
+    #[serde(default)]
+    pub tournaments: Vec<Tournament>,
+}
+
+impl Bundle {
+    /// Collects everything stored in `repo`. Scorecards are ordered by when
+    /// they were played.
+    pub fn from_repository(repo: &dyn Repository) -> Result<Self> {
+        let mut players = repo.list_players()?;
+        players.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+        let mut scorecards = repo.list_scorecards()?;
+        scorecards.sort_by_key(|card| (card.played_at, card.round_id));
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            players,
+            scorecards,
+            rounds: repo.list_rounds()?,
+            tournaments: repo.list_tournaments()?,
+        })
+    }
+
+    /// Writes the bundle in `format`. CSV leaves out rounds and tournaments.
+    pub fn write(&self, format: ExportFormat, writer: impl Write) -> Result<()> {
+        match format {
+            ExportFormat::Json => {
+                serde_json::to_writer_pretty(writer, self)?;
+                Ok(())
+            }
+            ExportFormat::Csv => self.write_csv(writer),
+        }
+    }
+
+    /// Reads a bundle written by [`Bundle::write`], or a spreadsheet saved
+    /// with the same CSV columns.
+    pub fn read(format: ExportFormat, reader: impl Read) -> Result<Self> {
+        match format {
+            ExportFormat::Json => {
+                let bundle: Bundle = serde_json::from_reader(reader)?;
+                if bundle.version > BUNDLE_VERSION {
+                    return Err(GolfError::custom(format!(
+                        "Bundle version {} is newer than this version of the tracker supports ({})",
+                        bundle.version, BUNDLE_VERSION
+                    )));
+                }
+                Ok(bundle)
+            }
+            ExportFormat::Csv => Self::read_csv(reader),
+        }
+    }
+
+    /// Number of records in the bundle whose ID is already stored in `repo`.
+    pub fn count_duplicates(&self, repo: &dyn Repository) -> Result<usize> {
+        let mut duplicates = 0;
+        for player in &self.players {
+            duplicates += usize::from(repo.get_player(&player.id)?.is_some());
+        }
+        for scorecard in &self.scorecards {
+            duplicates += usize::from(repo.get_scorecard(&scorecard.round_id)?.is_some());
+        }
+        for round in &self.rounds {
+            duplicates += usize::from(repo.get_round(&round.id)?.is_some());
+        }
+        for tournament in &self.tournaments {
+            duplicates += usize::from(repo.get_tournament(&tournament.id)?.is_some());
+        }
+        Ok(duplicates)
+    }
+
+    /// Saves the bundle's records into `repo`.
+    ///
+    /// Without a policy nothing is written if any record is already stored,
+    /// and `GolfError::DuplicateRecords` reports how many were found.
+    pub fn import_into(&self, repo: &mut dyn Repository, on_duplicate: Option<DuplicatePolicy>) -> Result<ImportReport> {
+        let policy = match (self.count_duplicates(repo)?, on_duplicate) {
+            (0, _) => DuplicatePolicy::Skip,
+            (_, Some(policy)) => policy,
+            (duplicates, None) => return Err(GolfError::DuplicateRecords(duplicates)),
+        };
+        let mut report = ImportReport::default();
+        let mut tally = |exists: bool| match (exists, policy) {
+            (false, _) => {
+                report.added += 1;
+                true
+            }
+            (true, DuplicatePolicy::Merge) => {
+                report.merged += 1;
+                true
+            }
+            (true, DuplicatePolicy::Skip) => {
+                report.skipped += 1;
+                false
+            }
+        };
+
+        for player in &self.players {
+            if tally(repo.get_player(&player.id)?.is_some()) {
+                repo.save_player(player)?;
+            }
+        }
+        for scorecard in &self.scorecards {
+            let existing = repo.get_scorecard(&scorecard.round_id)?;
+            if tally(existing.is_some()) {
+                match existing {
+                    Some(mut existing) => {
+                        existing.merge(scorecard, Some("Imported"))?;
+                        repo.save_scorecard(&existing)?;
+                    }
+                    None => repo.save_scorecard(scorecard)?,
+                }
+            }
+        }
+        for round in &self.rounds {
+            if tally(repo.get_round(&round.id)?.is_some()) {
+                repo.save_round(round)?;
+            }
+        }
+        for tournament in &self.tournaments {
+            if tally(repo.get_tournament(&tournament.id)?.is_some()) {
+                repo.save_tournament(tournament)?;
+            }
+        }
+        Ok(report)
+    }
+
+    fn write_csv(&self, writer: impl Write) -> Result<()> {
+        let mut csv = csv::Writer::from_writer(writer);
+        let players: HashMap<Uuid, &Player> = self.players.iter().map(|player| (player.id, player)).collect();
+        let player_row = |player_id: Uuid| {
+            let player = players.get(&player_id);
+            CsvRow {
+                player_id,
+                player_name: player.map(|player| player.name.clone()).unwrap_or_default(),
+                handicap: player.and_then(|player| player.handicap),
+                ..CsvRow::default()
+            }
+        };
+
+        for player in &self.players {
+            if !self.scorecards.iter().any(|card| card.player_id == player.id) {
+                csv.serialize(player_row(player.id))?;
+            }
+        }
+        for card in &self.scorecards {
+            for (&hole, &par) in card.pars() {
+                let result = card.hole_result(hole).copied().unwrap_or_default();
+                csv.serialize(CsvRow {
+                    scorecard_id: Some(card.round_id),
+                    played_at: Some(card.played_at),
+                    course: card.course.clone(),
+                    tee: card.tee.clone(),
+                    course_rating: card.rating.map(|rating| rating.course_rating),
+                    slope_rating: card.rating.map(|rating| rating.slope_rating),
+                    course_handicap: card.course_handicap,
+                    holes: Some(card.max_holes),
+                    hole: Some(hole),
+                    par: Some(par),
+                    stroke_index: card.stroke_index(hole),
+                    strokes: card.get_score(hole),
+                    putts: result.putts,
+                    fairway: result.fairway,
+                    sand_save: result.sand_save,
+                    penalties: (result.penalties > 0).then_some(result.penalties),
+                    ..player_row(card.player_id)
+                })?;
+            }
+        }
+        csv.flush()?;
+        Ok(())
+    }
+
+    fn read_csv(reader: impl Read) -> Result<Self> {
+        let mut players: Vec<Player> = Vec::new();
+        let mut cards: Vec<(Uuid, Vec<(usize, CsvRow)>)> = Vec::new();
+
+        for (index, row) in csv::Reader::from_reader(reader).deserialize::<CsvRow>().enumerate() {
+            // Line 1 is the header
+            let line = index + 2;
+            let row = row?;
+            if !players.iter().any(|player| player.id == row.player_id) {
+                let mut player = Player::new(&row.player_name, row.handicap)
+                    .map_err(|e| GolfError::custom(format!("Line {}: {}", line, e)))?;
+                player.id = row.player_id;
+                players.push(player);
+            }
+            let Some(scorecard_id) = row.scorecard_id else {
+                continue;
+            };
+            match cards.iter_mut().find(|(id, _)| *id == scorecard_id) {
+                Some((_, rows)) => rows.push((line, row)),
+                None => cards.push((scorecard_id, vec![(line, row)])),
+            }
+        }
+
+        let scorecards = cards
+            .into_iter()
+            .map(|(scorecard_id, rows)| {
+                let line = rows[0].0;
+                scorecard_from_rows(scorecard_id, rows)
+                    .map_err(|e| GolfError::custom(format!("Scorecard {} starting on line {}: {}", scorecard_id, line, e)))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            players,
+            scorecards,
+            rounds: Vec::new(),
+            tournaments: Vec::new(),
+        })
+    }
+}
+
+/// One line of a CSV export: a hole of a scorecard, or a player without
+/// scorecards.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CsvRow {
+    player_id: Uuid,
+    player_name: String,
+    handicap: Option<f64>,
+    scorecard_id: Option<Uuid>,
+    played_at: Option<DateTime<Utc>>,
+    course: Option<String>,
+    tee: Option<String>,
+    course_rating: Option<f64>,
+    slope_rating: Option<u16>,
+    course_handicap: Option<i32>,
+    holes: Option<u8>,
+    hole: Option<u8>,
+    par: Option<u8>,
+    stroke_index: Option<u8>,
+    strokes: Option<u8>,
+    putts: Option<u8>,
+    fairway: Option<Fairway>,
+    sand_save: Option<bool>,
+    penalties: Option<u8>,
+}
+
+/// Rebuilds a scorecard from its hole rows. The round details are taken
+/// from the first row.
+fn scorecard_from_rows(scorecard_id: Uuid, rows: Vec<(usize, CsvRow)>) -> Result<Scorecard> {
+    let first = &rows[0].1;
+    let missing = |column: &str| GolfError::custom(format!("missing {}", column));
+    let max_holes = first.holes.ok_or_else(|| missing("holes"))?;
+    let rating = match (first.course_rating, first.slope_rating) {
+        (Some(course_rating), Some(slope_rating)) => Some(CourseRating::new(course_rating, slope_rating)?),
+        _ => None,
+    };
+
+    let mut pars = BTreeMap::new();
+    let mut scores = BTreeMap::new();
+    let mut stroke_indexes = BTreeMap::new();
+    let mut results = BTreeMap::new();
+    for (line, row) in &rows {
+        let hole = row.hole.ok_or_else(|| missing("hole"))?;
+        if row.player_id != first.player_id {
+            return Err(GolfError::custom(format!("line {} is for a different player", line)));
+        }
+        if pars.insert(hole, row.par.ok_or_else(|| missing("par"))?).is_some() {
+            return Err(GolfError::custom(format!("hole {} appears twice", hole)));
+        }
+        if let Some(stroke_index) = row.stroke_index {
+            stroke_indexes.insert(hole, stroke_index);
+        }
+        let result = HoleResult {
+            putts: row.putts,
+            fairway: row.fairway,
+            sand_save: row.sand_save,
+            penalties: row.penalties.unwrap_or(0),
+        };
+        match row.strokes {
+            Some(strokes) => {
+                scores.insert(hole, strokes);
+                validate_hole_result(&result, strokes, hole, pars[&hole])?;
+                if !result.is_empty() {
+                    results.insert(hole, result);
+                }
+            }
+            None if !result.is_empty() => {
+                return Err(GolfError::custom(format!("hole {} has shot detail but no strokes", hole)));
+            }
+            None => {}
+        }
+    }
+    if pars.len() != usize::from(max_holes) {
+        return Err(GolfError::custom(format!("expected {} holes, found {}", max_holes, pars.len())));
+    }
+
+    let mut scorecard = Scorecard::from_parts(scorecard_id, first.player_id, max_holes, pars, scores)?;
+    scorecard.played_at = first.played_at.ok_or_else(|| missing("played_at"))?;
+    scorecard.rating = rating;
+    scorecard.course = first.course.clone();
+    scorecard.tee = first.tee.clone();
+    scorecard.course_handicap = first.course_handicap;
+    scorecard.set_stroke_indexes(stroke_indexes);
+    scorecard.set_hole_results(results);
+    Ok(scorecard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryRepository;
+    use crate::utils::get_course;
+
+    fn sample_repository() -> (InMemoryRepository, Scorecard) {
+        let mut repo = InMemoryRepository::new();
+        let player = Player::new("Nelly Korda, \"NK\"", Some(2.1)).expect("player");
+        let course = get_course("Pebble Beach").expect("course");
+        let mut card = Scorecard::new(player.id, &course, course.tee("Blue").expect("tee"))
+            .expect("scorecard")
+            .with_handicap_index(2.1);
+        card.record_hole(1, 5, HoleResult { putts: Some(2), fairway: Some(Fairway::Left), ..HoleResult::default() })
+            .expect("record");
+        card.record_hole(2, 4, HoleResult { sand_save: Some(true), penalties: 1, ..HoleResult::default() })
+            .expect("record");
+        repo.save_player(&player).expect("save");
+        repo.save_player(&Player::new("Lilia Vu", None).expect("player")).expect("save");
+        repo.save_scorecard(&card).expect("save");
+        (repo, card)
+    }
+
+    #[test]
+    fn csv_round_trip_keeps_every_detail() {
+        let (repo, card) = sample_repository();
+        let bundle = Bundle::from_repository(&repo).expect("bundle");
+
+        let mut csv = Vec::new();
+        bundle.write(ExportFormat::Csv, &mut csv).expect("write");
+        // Header, one row for the player without scorecards, one per hole
+        assert_eq!(String::from_utf8(csv.clone()).unwrap().lines().count(), 1 + 1 + 18);
+
+        let restored = Bundle::read(ExportFormat::Csv, csv.as_slice()).expect("read");
+        let mut expected = card.clone();
+        expected.set_history(Vec::new());
+        assert_eq!(restored.scorecards, vec![expected]);
+        let mut names: Vec<&str> = restored.players.iter().map(|player| player.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Lilia Vu", "Nelly Korda, \"NK\""]);
+    }
+
+    #[test]
+    fn merge_updates_records_and_keeps_local_holes() {
+        let (mut repo, mut card) = sample_repository();
+        let mut bundle = Bundle::from_repository(&repo).expect("bundle");
+
+        // Local play continues after the export...
+        card.record_score(3, 3).expect("record");
+        repo.save_scorecard(&card).expect("save");
+        // ...while the exported copy is edited elsewhere
+        bundle.players[0].handicap = Some(9.9);
+        bundle.scorecards[0].record_score(1, 6).expect("record");
+        bundle.scorecards[0].record_score(4, 6).expect("record");
+
+        assert!(matches!(bundle.import_into(&mut repo, None), Err(GolfError::DuplicateRecords(3))));
+
+        let report = bundle.import_into(&mut repo, Some(DuplicatePolicy::Merge)).expect("import");
+        assert_eq!(report, ImportReport { added: 0, merged: 3, skipped: 0 });
+        let merged = repo.get_scorecard(&card.round_id).expect("get").expect("exists");
+        assert_eq!(merged.scores().keys().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(merged.get_score(1), Some(6));
+        assert_eq!(merged.hole_result(1), None);
+        assert_eq!(merged.hole_result(2), card.hole_result(2));
+        // The local history is kept and the imported holes are added to it
+        assert_eq!(merged.history()[..card.history().len()], *card.history());
+        let imported = merged.history().last().expect("event");
+        assert_eq!((imported.hole, imported.reason.as_deref()), (4, Some("Imported")));
+        assert_eq!(repo.get_player(&bundle.players[0].id).expect("get").unwrap().handicap, Some(9.9));
+    }
+
+    #[test]
+    fn merge_takes_round_details_from_the_import() {
+        let (mut repo, card) = sample_repository();
+        let mut bundle = Bundle::from_repository(&repo).expect("bundle");
+        let imported = &mut bundle.scorecards[0];
+        imported.played_at = "2024-06-08T15:30:00Z".parse().expect("date");
+        imported.rating = Some(CourseRating::new(70.1, 128).expect("rating"));
+        imported.tee = Some("Gold".to_string());
+        imported.course_handicap = Some(4);
+        imported.set_stroke_indexes((1..=18).map(|hole| (hole, 19 - hole)).collect());
+        let imported = imported.clone();
+
+        bundle.import_into(&mut repo, Some(DuplicatePolicy::Merge)).expect("import");
+        let merged = repo.get_scorecard(&card.round_id).expect("get").expect("exists");
+        assert_eq!(merged, imported);
+    }
+
+    #[test]
+    fn invalid_rows_name_the_scorecard() {
+        let (repo, _) = sample_repository();
+        let mut csv = Vec::new();
+        Bundle::from_repository(&repo).expect("bundle").write(ExportFormat::Csv, &mut csv).expect("write");
+        let csv = String::from_utf8(csv).unwrap().replacen(",5,2,left,", ",5,9,left,", 1);
+
+        let error = Bundle::read(ExportFormat::Csv, csv.as_bytes()).unwrap_err();
+        assert!(error.to_string().contains("starting on line"), "{}", error);
+    }
+}
@@ -0,0 +1,404 @@
+//! Import of rounds from golf app CSV exports.
+//!
+//! Phone apps such as GolfGameBook and 18Birdies export one row per round,
+//! with the course, the date and a strokes (and often putts) column for
+//! every hole. A [`ColumnMapping`] names those columns; presets cover the
+//! common layouts and anything else can be described in a TOML file:
+//!
+//! ```toml
+//! course = "Course"
+//! date = "Date"
+//! date_format = "%d/%m/%Y"
+//! # `{hole}` is replaced by the hole number
+//! strokes = "Hole {hole}"
+//! putts = "Putts {hole}"
+//! par = "Par {hole}"
+//! tee = "Tee"
+//! ```
+//!
+//! Courses are matched against the [`CourseCatalog`]. When none matches
+//! and the file has par columns, the course is created from them and added
+//! to the catalog. Rows that cannot be imported, for instance because a
+//! score fails validation, are reported and skipped; the rest of the file
+//! is still imported.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use golf_score_tracker::storage::{ColumnMapping, CourseCatalog, import_app_rounds};
+//! use uuid::Uuid;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut catalog = CourseCatalog::load("./golf_data")?;
+//! let file = std::fs::File::open("rounds.csv")?;
+//! let import = import_app_rounds(file, &ColumnMapping::golf_game_book(), Uuid::new_v4(), &mut catalog)?;
+//! for rejected in &import.rejected {
+//!     println!("line {}: {}", rejected.line, rejected.reason);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{GolfError, Result};
+use crate::models::{Course, Hole, HoleResult, Scorecard, TeeSet};
+use crate::storage::{CourseCatalog, Serializer, TomlSerializer};
+
+/// Placeholder for the hole number in per-hole column names.
+pub const HOLE_PLACEHOLDER: &str = "{hole}";
+
+/// Most holes looked for in a file.
+const MAX_HOLES: u8 = 18;
+
+/// Apps with a built-in [`ColumnMapping`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum App {
+    GolfGameBook,
+    EighteenBirdies,
+}
+
+impl App {
+    pub const ALL: [App; 2] = [App::GolfGameBook, App::EighteenBirdies];
+
+    /// Name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            App::GolfGameBook => "golfgamebook",
+            App::EighteenBirdies => "18birdies",
+        }
+    }
+
+    pub fn mapping(self) -> ColumnMapping {
+        match self {
+            App::GolfGameBook => ColumnMapping::golf_game_book(),
+            App::EighteenBirdies => ColumnMapping::eighteen_birdies(),
+        }
+    }
+}
+
+impl fmt::Display for App {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for App {
+    type Err = GolfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized: String = s.chars().filter(char::is_ascii_alphanumeric).map(|c| c.to_ascii_lowercase()).collect();
+        match normalized.as_str() {
+            "eighteenbirdies" => Ok(App::EighteenBirdies),
+            other => App::ALL.into_iter().find(|app| app.name() == other).ok_or_else(|| {
+                GolfError::custom(format!(
+                    "Unknown app '{}'. Expected one of: {}",
+                    s,
+                    App::ALL.map(App::name).join(", ")
+                ))
+            }),
+        }
+    }
+}
+
+/// Names of the columns holding each part of a round.
+///
+/// Column names are matched ignoring case and surrounding spaces.
+/// Per-hole columns use [`HOLE_PLACEHOLDER`] for the hole number.
+///
+/// * `date_format` - A `chrono` format string for the date column, with or
+///   without a time of day
+/// * `putts`, `par`, `tee` - Optional columns; `par` is only needed to
+///   create courses that are not in the catalog
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ColumnMapping {
+    pub course: String,
+    pub date: String,
+    pub date_format: String,
+    pub strokes: String,
+    #[serde(default)]
+    pub putts: Option<String>,
+    #[serde(default)]
+    pub par: Option<String>,
+    #[serde(default)]
+    pub tee: Option<String>,
+}
+
+impl ColumnMapping {
+    /// GolfGameBook's layout: `Course`, `Date`, `Tee`, then `H1`, `P1`...
+    /// for strokes and putts.
+    pub fn golf_game_book() -> Self {
+        Self {
+            course: "Course".to_string(),
+            date: "Date".to_string(),
+            date_format: "%Y-%m-%d".to_string(),
+            strokes: "H{hole}".to_string(),
+            putts: Some("P{hole}".to_string()),
+            par: None,
+            tee: Some("Tee".to_string()),
+        }
+    }
+
+    /// 18Birdies' layout: `Course Name`, a US-style `Date`, and
+    /// `Hole 1 Par`, `Hole 1 Score` and `Hole 1 Putts` for every hole.
+    pub fn eighteen_birdies() -> Self {
+        Self {
+            course: "Course Name".to_string(),
+            date: "Date".to_string(),
+            date_format: "%m/%d/%Y".to_string(),
+            strokes: "Hole {hole} Score".to_string(),
+            putts: Some("Hole {hole} Putts".to_string()),
+            par: Some("Hole {hole} Par".to_string()),
+            tee: Some("Tee".to_string()),
+        }
+    }
+
+    /// Reads a mapping from a TOML file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mapping: Self = TomlSerializer.deserialize(&std::fs::read(path)?)?;
+        for template in [Some(&mapping.strokes), mapping.putts.as_ref(), mapping.par.as_ref()].into_iter().flatten() {
+            if !template.contains(HOLE_PLACEHOLDER) {
+                return Err(GolfError::custom(format!(
+                    "Column '{}' in {} needs a {} placeholder for the hole number",
+                    template,
+                    path.display(),
+                    HOLE_PLACEHOLDER
+                )));
+            }
+        }
+        Ok(mapping)
+    }
+
+    fn parse_date(&self, value: &str) -> Result<DateTime<Utc>> {
+        NaiveDateTime::parse_from_str(value, &self.date_format)
+            .or_else(|_| NaiveDate::parse_from_str(value, &self.date_format).map(|date| date.and_time(NaiveTime::MIN)))
+            .map(|date| date.and_utc())
+            .map_err(|_| GolfError::custom(format!("date '{}' does not match {}", value, self.date_format)))
+    }
+}
+
+/// A row that was not imported, with its line in the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedRow {
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Outcome of [`import_app_rounds`].
+///
+/// The scorecards are not saved yet; courses in `created_courses` have
+/// already been added to the catalog.
+#[derive(Debug, Clone, Default)]
+pub struct AppImport {
+    pub scorecards: Vec<Scorecard>,
+    pub created_courses: Vec<String>,
+    pub rejected: Vec<RejectedRow>,
+}
+
+/// Column positions of one per-hole field, by hole number.
+type HoleColumns = Vec<(u8, usize)>;
+
+/// Turns every row of an app export into a scorecard for `player_id`.
+///
+/// # Errors
+///
+/// Fails only when the file cannot be read as CSV or lacks the course,
+/// date or strokes columns of the mapping. Problems with individual rows
+/// are collected in [`AppImport::rejected`].
+pub fn import_app_rounds(
+    reader: impl Read,
+    mapping: &ColumnMapping,
+    player_id: Uuid,
+    catalog: &mut CourseCatalog,
+) -> Result<AppImport> {
+    let mut csv = csv::ReaderBuilder::new().flexible(true).trim(csv::Trim::All).from_reader(reader);
+    let headers: Vec<String> = csv.headers()?.iter().map(|header| header.to_ascii_lowercase()).collect();
+    let column = |name: &str| headers.iter().position(|header| *header == name.trim().to_ascii_lowercase());
+    let hole_columns = |template: &str| -> HoleColumns {
+        (1..=MAX_HOLES)
+            .filter_map(|hole| Some((hole, column(&template.replace(HOLE_PLACEHOLDER, &hole.to_string()))?)))
+            .collect()
+    };
+    let required = |name: &str| {
+        column(name).ok_or_else(|| GolfError::custom(format!("The file has no '{}' column", name)))
+    };
+
+    let course_column = required(&mapping.course)?;
+    let date_column = required(&mapping.date)?;
+    let strokes = hole_columns(&mapping.strokes);
+    if strokes.is_empty() {
+        return Err(GolfError::custom(format!(
+            "The file has no '{}' column",
+            mapping.strokes.replace(HOLE_PLACEHOLDER, "1")
+        )));
+    }
+    let columns = Columns {
+        course: course_column,
+        date: date_column,
+        tee: mapping.tee.as_deref().and_then(column),
+        strokes,
+        putts: mapping.putts.as_deref().map(hole_columns).unwrap_or_default(),
+        pars: mapping.par.as_deref().map(hole_columns).unwrap_or_default(),
+    };
+
+    let mut import = AppImport::default();
+    for (index, record) in csv.records().enumerate() {
+        // Line 1 is the header
+        let line = index + 2;
+        let result = record
+            .map_err(GolfError::from)
+            .and_then(|record| columns.scorecard(&record, mapping, player_id, catalog, &mut import.created_courses));
+        match result {
+            Ok(Some(scorecard)) => import.scorecards.push(scorecard),
+            Ok(None) => {}
+            Err(error) => import.rejected.push(RejectedRow { line, reason: error.to_string() }),
+        }
+    }
+    Ok(import)
+}
+
+/// Where the mapped columns are in a particular file.
+struct Columns {
+    course: usize,
+    date: usize,
+    tee: Option<usize>,
+    strokes: HoleColumns,
+    putts: HoleColumns,
+    pars: HoleColumns,
+}
+
+impl Columns {
+    /// Builds the scorecard of one row, or `None` for a blank row.
+    fn scorecard(
+        &self,
+        record: &csv::StringRecord,
+        mapping: &ColumnMapping,
+        player_id: Uuid,
+        catalog: &mut CourseCatalog,
+        created_courses: &mut Vec<String>,
+    ) -> Result<Option<Scorecard>> {
+        let field = |index: usize| record.get(index).filter(|value| !value.is_empty());
+        let number = |index: usize, what: &str, hole: u8| -> Result<Option<u8>> {
+            field(index)
+                .map(|value| {
+                    value.parse().map_err(|_| GolfError::custom(format!("{} '{}' on hole {} is not a number", what, value, hole)))
+                })
+                .transpose()
+        };
+        if record.iter().all(str::is_empty) {
+            return Ok(None);
+        }
+
+        let course_name = field(self.course).ok_or_else(|| GolfError::custom("missing course name"))?;
+        let played_at = mapping.parse_date(field(self.date).ok_or_else(|| GolfError::custom("missing date"))?)?;
+        // A new course is only added to the catalog once the whole row is valid
+        let (course, created) = match catalog.find(course_name) {
+            Ok(entry) => (entry.course.clone(), false),
+            Err(GolfError::CourseNotFound { .. }) if !self.pars.is_empty() => {
+                let mut holes = Vec::new();
+                for &(hole, index) in &self.pars {
+                    if let Some(par) = number(index, "par", hole)? {
+                        holes.push(Hole::new(hole, par, MAX_HOLES)?);
+                    }
+                }
+                let total_par = holes.iter().map(|hole| u16::from(hole.par)).sum();
+                let tee = TeeSet::unrated(self.tee.and_then(field).unwrap_or("Standard"), total_par);
+                (Course::new(course_name, holes, vec![tee])?, true)
+            }
+            Err(error) => return Err(error),
+        };
+        let tee = match self.tee.and_then(field) {
+            Some(name) => course.tee(name)?,
+            None => course.default_tee(),
+        };
+
+        let mut scorecard = Scorecard::new(player_id, &course, tee)?.with_played_at(played_at);
+        for &(hole, index) in &self.strokes {
+            let Some(strokes) = number(index, "strokes", hole)? else {
+                continue;
+            };
+            if hole > course.hole_count() {
+                return Err(GolfError::InvalidHole { hole, max_holes: course.hole_count() });
+            }
+            let putts = match self.putts.iter().find(|&&(putts_hole, _)| putts_hole == hole) {
+                Some(&(_, index)) => number(index, "putts", hole)?,
+                None => None,
+            };
+            scorecard.record_hole(hole, strokes, HoleResult { putts, ..HoleResult::default() })?;
+        }
+        if scorecard.scores().is_empty() {
+            return Err(GolfError::custom("no hole scores"));
+        }
+        if created {
+            catalog.add(course.clone(), false)?;
+            created_courses.push(course.name);
+        }
+        Ok(Some(scorecard))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDataDir;
+
+    fn catalog() -> (TempDataDir, CourseCatalog) {
+        let dir = TempDataDir::new();
+        let catalog = CourseCatalog::load(&dir.path).expect("catalog");
+        (dir, catalog)
+    }
+
+    #[test]
+    fn bad_rows_are_reported_and_the_rest_imported() {
+        let (_dir, mut catalog) = catalog();
+        let csv = "\
+Date,Course,Tee,H1,P1,H2,P2,H3,P3
+2024-06-01,Pebble Beach,Blue,5,2,4,1,4,2
+2024-06-08,pebble-beach,,5,2,0,1,4,2
+2024-06-15,Pebble Beach,Blue,4,x,4,2,,
+2024-06-22,Bethpage Black,,4,2,4,2,4,2
+";
+        let import = import_app_rounds(csv.as_bytes(), &ColumnMapping::golf_game_book(), Uuid::new_v4(), &mut catalog)
+            .expect("import");
+
+        assert_eq!(import.scorecards.len(), 1);
+        let card = &import.scorecards[0];
+        assert_eq!(card.course.as_deref(), Some("Pebble Beach"));
+        assert_eq!(card.tee.as_deref(), Some("Blue"));
+        assert_eq!(card.played_at.format("%Y-%m-%d").to_string(), "2024-06-01");
+        assert_eq!(card.hole_result(2).and_then(|result| result.putts), Some(1));
+
+        let lines: Vec<usize> = import.rejected.iter().map(|row| row.line).collect();
+        assert_eq!(lines, vec![3, 4, 5]);
+        assert!(import.rejected[0].reason.contains("Invalid score 0"), "{:?}", import.rejected);
+        assert!(import.rejected[2].reason.contains("not found"), "{:?}", import.rejected);
+    }
+
+    #[test]
+    fn unknown_courses_are_created_from_par_columns() {
+        let (dir, mut catalog) = catalog();
+        let csv = "\
+Course Name,Date,Hole 1 Par,Hole 1 Score,Hole 2 Par,Hole 2 Score
+Riverside Nine,06/01/2024,4,5,3,3
+Lakeside Links,06/05/2024,4,0,3,3
+Riverside Nine,06/08/2024,4,4,3,2
+";
+        let import = import_app_rounds(csv.as_bytes(), &ColumnMapping::eighteen_birdies(), Uuid::new_v4(), &mut catalog)
+            .expect("import");
+
+        // The rejected row's course is not created
+        assert_eq!(import.rejected.iter().map(|row| row.line).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(import.created_courses, vec!["Riverside Nine"]);
+        assert_eq!(import.scorecards.len(), 2);
+        assert!(import.scorecards.iter().all(|card| card.is_complete()));
+        let reloaded = CourseCatalog::load(&dir.path).expect("reload");
+        assert_eq!(reloaded.find("Riverside Nine").expect("course").course.total_par(), 7);
+        assert!(reloaded.find("Lakeside Links").is_err());
+    }
+}
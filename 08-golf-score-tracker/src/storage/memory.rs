@@ -0,0 +1,227 @@
+//! In-memory repository.
+//!
+//! Keeps every entity in `HashMap`s, which makes it a hermetic stand-in for
+//! tests and examples, and lets library users run the tracker without
+//! touching the file system. A snapshot can be written to, or restored from,
+//! any other repository such as a `FileRepository`.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::models::{Player, Round, Scorecard, Tournament};
+use crate::storage::Repository;
+
+/// Repository implementation that lives entirely in memory.
+///
+/// # Examples
+///
+/// ```
+/// use golf_score_tracker::{Player, Repository};
+/// use golf_score_tracker::storage::InMemoryRepository;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut repo = InMemoryRepository::new();
+/// let player = Player::new("Min Woo Lee", None)?;
+/// repo.save_player(&player)?;
+/// assert_eq!(repo.list_players()?, vec![player]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryRepository {
+    players: HashMap<Uuid, Player>,
+    scorecards: HashMap<Uuid, Scorecard>,
+    rounds: HashMap<Uuid, Round>,
+    tournaments: HashMap<Uuid, Tournament>,
+}
+
+impl InMemoryRepository {
+    /// Creates an empty repository.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads every player, scorecard, round and tournament from `source`
+    /// into a new in-memory repository.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use golf_score_tracker::storage::{FileRepository, InMemoryRepository};
+    ///
+    /// let files = FileRepository::new(PathBuf::from("./golf_data"))?;
+    /// let repo = InMemoryRepository::restore_from(&files)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn restore_from(source: &dyn Repository) -> Result<Self> {
+        let players = source.list_players()?.into_iter().map(|p| (p.id, p)).collect();
+        let scorecards = source.list_scorecards()?.into_iter().map(|sc| (sc.round_id, sc)).collect();
+        let rounds = source.list_rounds()?.into_iter().map(|round| (round.id, round)).collect();
+        let tournaments = source.list_tournaments()?.into_iter().map(|t| (t.id, t)).collect();
+        Ok(Self { players, scorecards, rounds, tournaments })
+    }
+
+    /// Writes every player, scorecard, round and tournament held in memory
+    /// to `target`.
+    ///
+    /// Existing entities in the target with the same IDs are overwritten;
+    /// entities that only exist in the target are left untouched.
+    pub fn snapshot_to(&self, target: &mut dyn Repository) -> Result<()> {
+        for player in self.players.values() {
+            target.save_player(player)?;
+        }
+        for scorecard in self.scorecards.values() {
+            target.save_scorecard(scorecard)?;
+        }
+        for round in self.rounds.values() {
+            target.save_round(round)?;
+        }
+        for tournament in self.tournaments.values() {
+            target.save_tournament(tournament)?;
+        }
+        Ok(())
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn save_player(&mut self, player: &Player) -> Result<()> {
+        self.players.insert(player.id, player.clone());
+        Ok(())
+    }
+
+    fn get_player(&self, id: &Uuid) -> Result<Option<Player>> {
+        Ok(self.players.get(id).cloned())
+    }
+
+    fn list_players(&self) -> Result<Vec<Player>> {
+        Ok(self.players.values().cloned().collect())
+    }
+
+    fn remove_player(&mut self, id: &Uuid) -> Result<bool> {
+        Ok(self.players.remove(id).is_some())
+    }
+
+    fn save_scorecard(&mut self, scorecard: &Scorecard) -> Result<()> {
+        self.scorecards.insert(scorecard.round_id, scorecard.clone());
+        Ok(())
+    }
+
+    fn get_scorecard(&self, round_id: &Uuid) -> Result<Option<Scorecard>> {
+        Ok(self.scorecards.get(round_id).cloned())
+    }
+
+    fn get_scorecards_by_player(&self, player_id: &Uuid) -> Result<Vec<Scorecard>> {
+        Ok(self.scorecards.values().filter(|sc| &sc.player_id == player_id).cloned().collect())
+    }
+
+    fn list_scorecards(&self) -> Result<Vec<Scorecard>> {
+        Ok(self.scorecards.values().cloned().collect())
+    }
+
+    fn remove_scorecard(&mut self, round_id: &Uuid) -> Result<bool> {
+        Ok(self.scorecards.remove(round_id).is_some())
+    }
+
+    fn save_round(&mut self, round: &Round) -> Result<()> {
+        self.rounds.insert(round.id, round.clone());
+        Ok(())
+    }
+
+    fn get_round(&self, id: &Uuid) -> Result<Option<Round>> {
+        Ok(self.rounds.get(id).cloned())
+    }
+
+    fn list_rounds(&self) -> Result<Vec<Round>> {
+        Ok(self.rounds.values().cloned().collect())
+    }
+
+    fn save_tournament(&mut self, tournament: &Tournament) -> Result<()> {
+        self.tournaments.insert(tournament.id, tournament.clone());
+        Ok(())
+    }
+
+    fn get_tournament(&self, id: &Uuid) -> Result<Option<Tournament>> {
+        Ok(self.tournaments.get(id).cloned())
+    }
+
+    fn list_tournaments(&self) -> Result<Vec<Tournament>> {
+        Ok(self.tournaments.values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::GolfError;
+    use crate::models::{Round, Tournament};
+    use crate::storage::DeleteMode;
+    use crate::utils::{create_standard_course, create_standard_pars};
+
+    #[test]
+    fn saving_again_replaces_the_entity() {
+        let mut repo = InMemoryRepository::new();
+        let mut player = Player::new("Sahith Theegala", None).expect("player");
+        repo.save_player(&player).expect("save");
+        player.handicap = Some(3.1);
+        repo.save_player(&player).expect("save again");
+
+        assert_eq!(repo.list_players().expect("list"), vec![player]);
+    }
+
+    #[test]
+    fn scorecards_are_filtered_by_player() {
+        let mut repo = InMemoryRepository::new();
+        let player_id = Uuid::new_v4();
+        let mine = Scorecard::from_pars(player_id, 9, create_standard_pars(9)).expect("scorecard");
+        let theirs = Scorecard::from_pars(Uuid::new_v4(), 9, create_standard_pars(9)).expect("scorecard");
+        repo.save_scorecard(&mine).expect("save");
+        repo.save_scorecard(&theirs).expect("save");
+
+        assert_eq!(repo.get_scorecards_by_player(&player_id).expect("by player"), vec![mine]);
+        assert_eq!(repo.list_scorecards().expect("list").len(), 2);
+    }
+
+    #[test]
+    fn deleting_a_scorecard_takes_it_out_of_its_round() {
+        let mut repo = InMemoryRepository::new();
+        let course = create_standard_course(9);
+        let mut round = Round::new(&course);
+        let (player_id, partner_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let mine = round.add_player(player_id, &course, course.default_tee()).expect("add");
+        let theirs = round.add_player(partner_id, &course, course.default_tee()).expect("add");
+        repo.save_scorecard(&mine).expect("save");
+        repo.save_scorecard(&theirs).expect("save");
+        repo.save_round(&round).expect("save round");
+
+        repo.delete_scorecard(&mine.round_id).expect("delete");
+
+        let round = repo.get_round(&round.id).expect("get").expect("round");
+        assert!(!round.includes_player(&player_id) && round.includes_player(&partner_id));
+        assert_eq!(repo.list_scorecards().expect("list"), vec![theirs]);
+        assert!(matches!(repo.delete_scorecard(&mine.round_id), Err(GolfError::RoundNotFound(_))));
+    }
+
+    #[test]
+    fn refused_player_deletion_leaves_everything_in_place() {
+        let mut repo = InMemoryRepository::new();
+        let course = create_standard_course(9);
+        let player = Player::new("Tom Kim", None).expect("player");
+        repo.save_player(&player).expect("save");
+        let mut tournament = Tournament::new("Club Championship", &[&course]).expect("tournament");
+        tournament.join(player.id).expect("join");
+        repo.save_tournament(&tournament).expect("save tournament");
+
+        assert!(matches!(
+            repo.delete_player(&player.id, DeleteMode::Refuse),
+            Err(GolfError::PlayerInUse { tournaments: 1, .. })
+        ));
+        assert_eq!(repo.list_players().expect("list"), vec![player.clone()]);
+
+        assert_eq!(repo.delete_player(&player.id, DeleteMode::Cascade).expect("cascade"), 0);
+        assert!(repo.list_players().expect("list").is_empty());
+        assert!(repo.get_tournament(&tournament.id).expect("get").expect("tournament").field.is_empty());
+    }
+}
@@ -1,4 +1,21 @@
+pub mod catalog;
+pub mod config;
+pub mod export;
+pub mod importer;
+pub mod memory;
 pub mod repository;
 pub mod serialization;
+pub mod sqlite;
 
-pub use repository::{Repository, FileRepository};
+pub use catalog::{CatalogEntry, CourseCatalog, CourseSource, course_key, read_course_file};
+pub use config::{Config, DataDir, DataDirSource, Profile, resolve_data_dir};
+pub use export::{Bundle, DuplicatePolicy, ExportFormat, ImportReport};
+pub use importer::{App, AppImport, ColumnMapping, RejectedRow, import_app_rounds};
+pub use memory::InMemoryRepository;
+pub use repository::{
+    DeleteMode, FileRepository, Manifest, RepairReport, Repository, open_file_repository, repair_file_repository,
+};
+pub use serialization::{
+    CborSerializer, CompactJsonSerializer, Format, JsonSerializer, MessagePackSerializer, Serializer, TomlSerializer,
+};
+pub use sqlite::SqliteRepository;
@@ -1,13 +1,26 @@
 //! Repository pattern implementation for golf score tracking.
 //!
 //! This module provides a trait-based abstraction for data persistence,
-//! along with a file system-based implementation.
+//! along with a file system-based implementation whose on-disk encoding is
+//! chosen by a [`Serializer`].
+//!
+//! File writes are crash-safe: every entity is written to a temporary file,
+//! flushed, and renamed over the original, so a reader only ever sees the
+//! old or the new contents. An advisory lock keeps two processes from using
+//! the same data directory at once.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use uuid::Uuid;
 
-use crate::error::Result;
-use crate::models::{Player, Scorecard};
+use crate::error::{GolfError, Result};
+use crate::models::{Player, Round, Scorecard, Tournament};
+use crate::storage::serialization::{
+    CborSerializer, CompactJsonSerializer, Format, JsonSerializer, MessagePackSerializer, Serializer, TomlSerializer,
+};
 
 /// Defines the contract for persisting and retrieving golf score data.
 ///
@@ -38,6 +51,145 @@ pub trait Repository {
     /// * `Ok(None)` if no player with the given ID exists
     /// * `Err` if an I/O or deserialization error occurred
     fn get_player(&self, id: &Uuid) -> Result<Option<Player>>;
+
+    /// Finds a player from how a user would type them: a full ID, a
+    /// unique prefix of an ID (like a short git hash), or a name.
+    ///
+    /// Names are matched ignoring case, first exactly and then as a
+    /// prefix, so "rory" finds "Rory McIlroy" when no one else's name
+    /// starts with it.
+    ///
+    /// # Errors
+    ///
+    /// * `GolfError::PlayerNotFound` if nothing matches
+    /// * `GolfError::AmbiguousPlayer` with the candidates if several
+    ///   players match equally well
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::{GolfError, Player, Repository};
+    /// use golf_score_tracker::storage::InMemoryRepository;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut repo = InMemoryRepository::new();
+    /// let rory = Player::new("Rory McIlroy", Some(-3.2))?;
+    /// repo.save_player(&rory)?;
+    /// repo.save_player(&Player::new("Rory Sabbatini", None)?)?;
+    ///
+    /// assert_eq!(repo.find_player("rory mcilroy")?, rory);
+    /// assert_eq!(repo.find_player(&rory.id.to_string()[..8])?, rory);
+    /// assert!(matches!(repo.find_player("Rory"), Err(GolfError::AmbiguousPlayer { .. })));
+    /// assert!(matches!(repo.find_player("Tiger"), Err(GolfError::PlayerNotFound(_))));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn find_player(&self, query: &str) -> Result<Player> {
+        let query = query.trim();
+        if let Ok(id) = Uuid::parse_str(query) {
+            return self.get_player(&id)?.ok_or_else(|| GolfError::PlayerNotFound(query.to_string()));
+        }
+
+        let players = self.list_players()?;
+        let wanted = query.to_lowercase();
+        let id_prefix = wanted.replace('-', "");
+        let is_id_prefix = id_prefix.len() >= MIN_ID_PREFIX && id_prefix.chars().all(|c| c.is_ascii_hexdigit());
+        let rules: [&dyn Fn(&Player) -> bool; 3] = [
+            &|player| player.name.to_lowercase() == wanted,
+            &|player| is_id_prefix && player.id.simple().to_string().starts_with(&id_prefix),
+            &|player| !wanted.is_empty() && player.name.to_lowercase().starts_with(&wanted),
+        ];
+        for rule in rules {
+            let mut matches: Vec<&Player> = players.iter().filter(|player| rule(player)).collect();
+            match matches.len() {
+                0 => continue,
+                1 => return Ok(matches[0].clone()),
+                _ => {
+                    matches.sort_by(|a, b| a.name.cmp(&b.name));
+                    return Err(GolfError::AmbiguousPlayer {
+                        query: query.to_string(),
+                        candidates: matches.iter().map(|player| format!("{} ({})", player.name, player.id)).collect(),
+                    });
+                }
+            }
+        }
+        Err(GolfError::PlayerNotFound(query.to_string()))
+    }
+
+    /// Saves changes to a player that is already stored.
+    ///
+    /// # Errors
+    ///
+    /// `GolfError::PlayerNotFound` if no player has the same ID, so a
+    /// mistyped ID never creates a new player.
+    fn update_player(&mut self, player: &Player) -> Result<()> {
+        if self.get_player(&player.id)?.is_none() {
+            return Err(GolfError::PlayerNotFound(player.id.to_string()));
+        }
+        self.save_player(player)
+    }
+
+    /// Deletes the stored player record only, returning whether it existed.
+    ///
+    /// Backends implement this; callers should use
+    /// [`Repository::delete_player`], which also deals with the player's
+    /// scorecards.
+    fn remove_player(&mut self, id: &Uuid) -> Result<bool>;
+
+    /// Deletes a player.
+    ///
+    /// With [`DeleteMode::Refuse`] a player who has scorecards or is in a
+    /// tournament field is kept and an error lists what is in the way.
+    /// With [`DeleteMode::Cascade`] their scorecards are deleted with
+    /// [`Repository::delete_scorecard`] and they are taken out of every
+    /// tournament field. Returns the number of scorecards deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::{Player, Repository, Scorecard};
+    /// use golf_score_tracker::storage::{DeleteMode, InMemoryRepository};
+    /// use golf_score_tracker::utils::create_standard_pars;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut repo = InMemoryRepository::new();
+    /// let player = Player::new("Sepp Straka", None)?;
+    /// repo.save_player(&player)?;
+    /// repo.save_scorecard(&Scorecard::from_pars(player.id, 9, create_standard_pars(9))?)?;
+    ///
+    /// assert!(repo.delete_player(&player.id, DeleteMode::Refuse).is_err());
+    /// assert_eq!(repo.delete_player(&player.id, DeleteMode::Cascade)?, 1);
+    /// assert!(repo.list_scorecards()?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn delete_player(&mut self, id: &Uuid, mode: DeleteMode) -> Result<usize> {
+        let player = self.get_player(id)?.ok_or_else(|| GolfError::PlayerNotFound(id.to_string()))?;
+        let scorecards = self.get_scorecards_by_player(id)?;
+        let tournaments: Vec<Tournament> =
+            self.list_tournaments()?.into_iter().filter(|tournament| tournament.includes_player(id)).collect();
+
+        if mode == DeleteMode::Refuse && (!scorecards.is_empty() || !tournaments.is_empty()) {
+            return Err(GolfError::PlayerInUse {
+                name: player.name,
+                scorecards: scorecards.len(),
+                tournaments: tournaments.len(),
+            });
+        }
+
+        for scorecard in &scorecards {
+            self.delete_scorecard(&scorecard.round_id)?;
+        }
+        for tournament in tournaments {
+            // Re-read, as deleting scorecards has updated its entries
+            if let Some(mut tournament) = self.get_tournament(&tournament.id)? {
+                tournament.field.retain(|player_id| player_id != id);
+                self.save_tournament(&tournament)?;
+            }
+        }
+        self.remove_player(id)?;
+        Ok(scorecards.len())
+    }
     
     /// Lists all players in the repository.
     ///
@@ -73,32 +225,231 @@ pub trait Repository {
     fn get_scorecard(&self, round_id: &Uuid) -> Result<Option<Scorecard>>;
     fn get_scorecards_by_player( &self, player_id: &Uuid) -> Result<Vec<Scorecard>>;
     fn list_scorecards(&self) -> Result<Vec<Scorecard>>;
+
+    /// Deletes the stored scorecard only, returning whether it existed.
+    ///
+    /// Backends implement this; callers should use
+    /// [`Repository::delete_scorecard`], which also updates the rounds and
+    /// tournaments the scorecard belongs to.
+    fn remove_scorecard(&mut self, round_id: &Uuid) -> Result<bool>;
+
+    /// Deletes a scorecard and takes it out of any group round or
+    /// tournament it was part of.
+    ///
+    /// # Errors
+    ///
+    /// `GolfError::RoundNotFound` if there is no such scorecard.
+    fn delete_scorecard(&mut self, round_id: &Uuid) -> Result<Scorecard> {
+        let scorecard = self.get_scorecard(round_id)?.ok_or(GolfError::RoundNotFound(*round_id))?;
+        for mut round in self.get_rounds_by_player(&scorecard.player_id)? {
+            if round.scorecard_id_for(&scorecard.player_id) == Some(*round_id) {
+                round.entries.retain(|entry| &entry.scorecard_id != round_id);
+                self.save_round(&round)?;
+            }
+        }
+        for mut tournament in self.list_tournaments()? {
+            if tournament.entries.iter().any(|entry| &entry.scorecard_id == round_id) {
+                tournament.entries.retain(|entry| &entry.scorecard_id != round_id);
+                self.save_tournament(&tournament)?;
+            }
+        }
+        self.remove_scorecard(round_id)?;
+        Ok(scorecard)
+    }
+
+    /// Saves a group round, replacing any earlier version with the same ID.
+    ///
+    /// Only the round itself is stored; its scorecards are saved with
+    /// [`Repository::save_scorecard`].
+    fn save_round(&mut self, round: &Round) -> Result<()>;
+
+    /// Retrieves a group round by its identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(round))` if the round exists
+    /// * `Ok(None)` if no round with the given ID exists
+    /// * `Err` if an I/O or deserialization error occurred
+    fn get_round(&self, id: &Uuid) -> Result<Option<Round>>;
+
+    /// Lists every group round.
+    fn list_rounds(&self) -> Result<Vec<Round>>;
+
+    /// Lists the group rounds a player took part in.
+    fn get_rounds_by_player(&self, player_id: &Uuid) -> Result<Vec<Round>> {
+        Ok(self.list_rounds()?.into_iter().filter(|round| round.includes_player(player_id)).collect())
+    }
+
+    /// Saves a tournament, replacing any earlier version with the same ID.
+    ///
+    /// As with rounds, the tournament's scorecards are saved separately.
+    fn save_tournament(&mut self, tournament: &Tournament) -> Result<()>;
+
+    /// Retrieves a tournament by its identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(tournament))` if the tournament exists
+    /// * `Ok(None)` if no tournament with the given ID exists
+    /// * `Err` if an I/O or deserialization error occurred
+    fn get_tournament(&self, id: &Uuid) -> Result<Option<Tournament>>;
+
+    /// Lists every tournament.
+    fn list_tournaments(&self) -> Result<Vec<Tournament>>;
+}
+
+/// What [`Repository::delete_player`] does with a player's scorecards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteMode {
+    /// Keep the player if they have any scorecards or tournament entries.
+    Refuse,
+    /// Delete their scorecards and tournament entries too.
+    Cascade,
+}
+
+/// Shortest ID prefix [`Repository::find_player`] accepts, as with git's
+/// abbreviated hashes.
+pub const MIN_ID_PREFIX: usize = 4;
+
+/// Name of the manifest file written at the root of a file repository.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Name of the lock file held while a file repository is open.
+pub const LOCK_FILE: &str = ".lock";
+
+/// Directory, relative to the repository root, that receives unreadable files.
+pub const QUARANTINE_DIR: &str = "quarantine";
+
+/// Subdirectories holding one file per entity.
+const ENTITY_DIRS: [&str; 4] = ["players", "scorecards", "rounds", "tournaments"];
+
+/// Describes how a file repository is encoded on disk.
+///
+/// The manifest is always stored as JSON, whatever the entity format, so
+/// that any tool can tell how to read the rest of the directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Layout version of the repository directory.
+    pub version: u32,
+    /// Encoding of every player and scorecard file.
+    pub format: Format,
+}
+
+impl Manifest {
+    /// Current repository layout version.
+    pub const CURRENT_VERSION: u32 = 1;
+
+    /// Reads the manifest of the repository at `base_path`.
+    ///
+    /// Returns `Ok(None)` when the directory has no manifest, either because
+    /// it is new or because it predates manifests (such directories always
+    /// hold pretty JSON).
+    pub fn read(base_path: &Path) -> Result<Option<Self>> {
+        let path = base_path.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = std::fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&json)?))
+    }
+
+    fn write(&self, base_path: &Path) -> Result<()> {
+        let json = serde_json::to_vec_pretty(self)?;
+        write_atomically(&base_path.join(MANIFEST_FILE), &json)
+    }
+}
+
+/// Outcome of [`FileRepository::repair`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Unreadable entity files, at their new location under `quarantine/`.
+    pub quarantined: Vec<PathBuf>,
+    /// Leftover temporary files from interrupted writes that were deleted.
+    pub removed_temp_files: Vec<PathBuf>,
+}
+
+impl RepairReport {
+    /// Returns `true` when the repair found nothing to fix.
+    pub fn is_clean(&self) -> bool {
+        self.quarantined.is_empty() && self.removed_temp_files.is_empty()
+    }
+}
+
+/// Writes `bytes` to `path` so that a crash leaves either the old file or
+/// the complete new one, never a truncated mix.
+///
+/// The data goes to a hidden `.tmp` sibling first, is flushed to disk, and
+/// is then renamed over `path`; renames within a directory are atomic.
+pub(crate) fn write_atomically(path: &Path, bytes: &[u8]) -> Result<()> {
+    let dir = path.parent().unwrap();
+    let file_name = path.file_name().unwrap().to_string_lossy();
+    let temp_path = dir.join(format!(".{}.tmp", file_name));
+
+    let mut file = File::create(&temp_path)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    drop(file);
+
+    if let Err(e) = std::fs::rename(&temp_path, path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+
+    // Persist the rename itself; directories cannot be opened for syncing on
+    // every platform, so this is best effort.
+    #[cfg(unix)]
+    if let Ok(dir) = File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+fn is_temp_file(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("tmp")
 }
 
 /// File system-based implementation of the Repository trait.
 ///
-/// Stores players and scorecards as JSON files in separate subdirectories.
-/// Each entity is stored in a file named by its UUID with a `.json` extension.
+/// Stores players and scorecards in separate subdirectories, one file per
+/// entity named by its UUID. The encoding is chosen by the serializer type
+/// parameter (pretty JSON by default) and recorded in `manifest.json`.
+///
+/// The repository holds an exclusive advisory lock on `base_path/.lock`
+/// for as long as it is alive; opening the same directory again, from this
+/// or another process, fails with `GolfError::RepositoryLocked`.
 ///
 /// # Directory Structure
 ///
 /// ```text
 /// base_path/
+/// ├── .lock
+/// ├── manifest.json
 /// ├── players/
-/// │   ├── {uuid}.json
+/// │   ├── {uuid}.{ext}
+/// │   └── ...
+/// ├── scorecards/
+/// │   ├── {uuid}.{ext}
+/// │   └── ...
+/// ├── rounds/
+/// │   ├── {uuid}.{ext}
 /// │   └── ...
-/// └── scorecards/
-///     ├── {uuid}.json
-///     └── ...
+/// ├── tournaments/
+/// │   ├── {uuid}.{ext}
+/// │   └── ...
+/// └── quarantine/          (created by `repair`)
 /// ```
-pub struct FileRepository {
+pub struct FileRepository<S: Serializer = JsonSerializer> {
     base_path: PathBuf,
+    serializer: S,
+    _lock: File,
 }
 
 impl FileRepository {
     /// Creates a new FileRepository with the specified base path.
     ///
     /// This method will create the base directory if it doesn't exist.
+    /// Data is stored as pretty-printed JSON; use
+    /// [`FileRepository::with_serializer`] to pick another format.
     ///
     /// # Arguments
     ///
@@ -107,7 +458,8 @@ impl FileRepository {
     /// # Returns
     ///
     /// * `Ok(FileRepository)` if the repository was created successfully
-    /// * `Err` if the base directory could not be created
+    /// * `Err` if the base directory could not be created, is locked by
+    ///   another repository, or already holds data in another format
     ///
     /// # Examples
     ///
@@ -119,8 +471,78 @@ impl FileRepository {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn new(base_path: PathBuf) -> Result<Self> {
+        Self::with_serializer(base_path, JsonSerializer)
+    }
+}
+
+impl<S: Serializer> FileRepository<S> {
+    /// Creates a FileRepository that encodes entities with `serializer`.
+    ///
+    /// A new directory gets a manifest recording the serializer's format.
+    /// Directories without a manifest that already contain data are treated
+    /// as legacy JSON repositories.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GolfError::RepositoryLocked` if another repository has the
+    /// directory open, and `GolfError::FormatMismatch` if the directory was
+    /// written in a different format.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use golf_score_tracker::storage::{FileRepository, TomlSerializer};
+    ///
+    /// let repo = FileRepository::with_serializer(PathBuf::from("./data"), TomlSerializer)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_serializer(base_path: PathBuf, serializer: S) -> Result<Self> {
         std::fs::create_dir_all(&base_path)?;
-        Ok(Self { base_path })
+        let lock = Self::acquire_lock(&base_path)?;
+
+        let requested = serializer.format();
+        let found = match Manifest::read(&base_path)? {
+            Some(manifest) => Some(manifest.format),
+            None if Self::has_legacy_data(&base_path) => Some(Format::Json),
+            None => None,
+        };
+
+        match found {
+            Some(found) if found != requested => {
+                return Err(GolfError::FormatMismatch { path: base_path, expected: requested, found });
+            }
+            _ => Manifest { version: Manifest::CURRENT_VERSION, format: requested }.write(&base_path)?,
+        }
+
+        Ok(Self { base_path, serializer, _lock: lock })
+    }
+
+    fn acquire_lock(base_path: &Path) -> Result<File> {
+        let file = OpenOptions::new().create(true).truncate(false).write(true).open(base_path.join(LOCK_FILE))?;
+        match file.try_lock() {
+            Ok(()) => Ok(file),
+            Err(TryLockError::WouldBlock) => Err(GolfError::RepositoryLocked(base_path.to_path_buf())),
+            Err(TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+
+    /// Returns the root directory of the repository.
+    pub fn base_path(&self) -> &Path {
+        &self.base_path
+    }
+
+    /// Returns the storage format of the repository.
+    pub fn format(&self) -> Format {
+        self.serializer.format()
+    }
+
+    fn has_legacy_data(base_path: &Path) -> bool {
+        ENTITY_DIRS.iter().any(|dir| {
+            std::fs::read_dir(base_path.join(dir))
+                .map(|mut entries| entries.next().is_some())
+                .unwrap_or(false)
+        })
     }
 
     /// Returns the file system path for a player file.
@@ -131,9 +553,9 @@ impl FileRepository {
     ///
     /// # Returns
     ///
-    /// The path to the player's JSON file
+    /// The path to the player's data file
     fn player_path(&self, id: &Uuid) -> PathBuf {
-        self.base_path.join("players").join(format!("{}.json", id))
+        self.base_path.join("players").join(format!("{}.{}", id, self.serializer.extension()))
     }
 
     /// Returns the file system path for a scorecard file.
@@ -144,79 +566,164 @@ impl FileRepository {
     ///
     /// # Returns
     ///
-    /// The path to the scorecard's JSON file
+    /// The path to the scorecard's data file
     fn scorecard_path(&self, round_id: &Uuid) -> PathBuf {
-        self.base_path.join("scorecards").join(format!("{}.json", round_id))
+        self.base_path.join("scorecards").join(format!("{}.{}", round_id, self.serializer.extension()))
     }
-}
 
-impl Repository for FileRepository {
-    fn save_player(&mut self, player: &Player) -> Result<()> {
-        let path = self.player_path(&player.id);
+    /// Returns the file system path for a group round file.
+    fn round_path(&self, id: &Uuid) -> PathBuf {
+        self.base_path.join("rounds").join(format!("{}.{}", id, self.serializer.extension()))
+    }
+
+    /// Returns the file system path for a tournament file.
+    fn tournament_path(&self, id: &Uuid) -> PathBuf {
+        self.base_path.join("tournaments").join(format!("{}.{}", id, self.serializer.extension()))
+    }
+
+    fn write_entity<T: Serialize>(&self, path: &Path, value: &T) -> Result<()> {
         std::fs::create_dir_all(path.parent().unwrap())?;
-        let json = serde_json::to_string_pretty(player)?;
-        std::fs::write(path, json)?;
-        Ok(())
+        let bytes = self.serializer.serialize(value)?;
+        write_atomically(path, &bytes)
     }
 
-    fn get_player(&self, id: &Uuid) -> Result<Option<Player>> {
-        let path = self.player_path(id);
+    /// Deletes an entity file, returning whether it existed.
+    fn remove_entity(&self, path: &Path) -> Result<bool> {
+        match std::fs::remove_file(path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn read_entity<T: DeserializeOwned>(&self, path: &Path) -> Result<Option<T>> {
         if !path.exists() {
             return Ok(None);
         }
-        let json = std::fs::read_to_string(path)?;
-        let player = serde_json::from_str(&json)?;
-        Ok(Some(player))
+        self.decode_file(path).map(Some)
     }
 
-    fn list_players(&self) -> Result<Vec<Player>> {
-        let dir = self.base_path.join("players");
+    /// Decodes one entity file, naming the file when its contents are bad.
+    fn decode_file<T: DeserializeOwned>(&self, path: &Path) -> Result<T> {
+        let bytes = std::fs::read(path)?;
+        self.serializer.deserialize(&bytes).map_err(|e| {
+            let reason = match e {
+                GolfError::SerializationError(inner) => inner.to_string(),
+                other => other.to_string(),
+            };
+            GolfError::CorruptFile { path: path.to_path_buf(), reason }
+        })
+    }
+
+    /// Lists the entity files of a subdirectory, skipping temporary files
+    /// and files with a foreign extension.
+    fn entity_files(&self, dir: &str) -> Result<Vec<PathBuf>> {
+        let dir = self.base_path.join(dir);
         if !dir.exists() {
             return Ok(vec![]);
         }
 
-        let mut players = Vec::new();
+        let mut files = Vec::new();
         for entry in std::fs::read_dir(dir)? {
-            let entry = entry?;
-            let json = std::fs::read_to_string(entry.path())?;
-            let player: Player = serde_json::from_str(&json)?;
-            players.push(player);
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some(self.serializer.extension()) {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+
+    /// Reads every entity file in a subdirectory.
+    fn read_all<T: DeserializeOwned>(&self, dir: &str) -> Result<Vec<T>> {
+        self.entity_files(dir)?.iter().map(|path| self.decode_file(path)).collect()
+    }
+
+    /// Moves unreadable entity files out of the way and deletes temporary
+    /// files left behind by interrupted writes.
+    ///
+    /// A file that cannot be decoded makes every listing fail with
+    /// `GolfError::CorruptFile`. Repairing moves such files to
+    /// `quarantine/<dir>/` (keeping them for manual recovery) so the rest of
+    /// the repository becomes usable again.
+    pub fn repair(&mut self) -> Result<RepairReport> {
+        let mut report = RepairReport::default();
+
+        for dir in ENTITY_DIRS {
+            let entity_dir = self.base_path.join(dir);
+            if !entity_dir.exists() {
+                continue;
+            }
+
+            for entry in std::fs::read_dir(&entity_dir)? {
+                let path = entry?.path();
+                if is_temp_file(&path) {
+                    std::fs::remove_file(&path)?;
+                    report.removed_temp_files.push(path);
+                }
+            }
+
+            for path in self.entity_files(dir)? {
+                let readable = match dir {
+                    "players" => self.decode_file::<Player>(&path).is_ok(),
+                    "rounds" => self.decode_file::<Round>(&path).is_ok(),
+                    "tournaments" => self.decode_file::<Tournament>(&path).is_ok(),
+                    _ => self.decode_file::<Scorecard>(&path).is_ok(),
+                };
+                if !readable {
+                    report.quarantined.push(self.quarantine(dir, &path)?);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    fn quarantine(&self, dir: &str, path: &Path) -> Result<PathBuf> {
+        let target_dir = self.base_path.join(QUARANTINE_DIR).join(dir);
+        std::fs::create_dir_all(&target_dir)?;
+
+        let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let mut target = target_dir.join(&file_name);
+        let mut attempt = 1;
+        while target.exists() {
+            target = target_dir.join(format!("{}.{}", file_name, attempt));
+            attempt += 1;
         }
-        Ok(players)
+        std::fs::rename(path, &target)?;
+        Ok(target)
+    }
+}
+
+impl<S: Serializer> Repository for FileRepository<S> {
+    fn save_player(&mut self, player: &Player) -> Result<()> {
+        self.write_entity(&self.player_path(&player.id), player)
+    }
+
+    fn get_player(&self, id: &Uuid) -> Result<Option<Player>> {
+        self.read_entity(&self.player_path(id))
+    }
+
+    fn list_players(&self) -> Result<Vec<Player>> {
+        self.read_all("players")
+    }
+
+    fn remove_player(&mut self, id: &Uuid) -> Result<bool> {
+        self.remove_entity(&self.player_path(id))
     }
 
     fn save_scorecard(&mut self, scorecard: &Scorecard) -> Result<()> {
-        let path = self.scorecard_path(&scorecard.round_id);
-        std::fs::create_dir_all(path.parent().unwrap())?;
-        let json = serde_json::to_string_pretty(scorecard)?;
-        std::fs::write(path, json)?;
-        Ok(())
+        self.write_entity(&self.scorecard_path(&scorecard.round_id), scorecard)
     }
 
     fn get_scorecard(&self, round_id: &Uuid) -> Result<Option<Scorecard>> {
-        let path = self.scorecard_path(round_id);
-        if !path.exists() {
-            return Ok(None);
-        }
-        let json = std::fs::read_to_string(path)?;
-        let scorecard = serde_json::from_str(&json)?;
-        Ok(Some(scorecard))
+        self.read_entity(&self.scorecard_path(round_id))
     }
 
     fn list_scorecards(&self) -> Result<Vec<Scorecard>> {
-        let dir = self.base_path.join("scorecards");
-        if !dir.exists() {
-            return Ok(vec![]);
-        }
+        self.read_all("scorecards")
+    }
 
-        let mut scorecards = Vec::new();
-        for entry in std::fs::read_dir(dir)? {
-            let entry = entry?;
-            let json = std::fs::read_to_string(entry.path())?;
-            let scorecard: Scorecard = serde_json::from_str(&json)?;
-            scorecards.push(scorecard);
-        }
-        Ok(scorecards)
+    fn remove_scorecard(&mut self, round_id: &Uuid) -> Result<bool> {
+        self.remove_entity(&self.scorecard_path(round_id))
     }
 
     fn get_scorecards_by_player(&self, player_id: &Uuid) -> Result<Vec<Scorecard>> {
@@ -224,4 +731,72 @@ impl Repository for FileRepository {
         Ok(results.into_iter().filter(|x | &x.player_id == player_id).collect())
     }
 
-}
\ No newline at end of file
+    fn save_round(&mut self, round: &Round) -> Result<()> {
+        self.write_entity(&self.round_path(&round.id), round)
+    }
+
+    fn get_round(&self, id: &Uuid) -> Result<Option<Round>> {
+        self.read_entity(&self.round_path(id))
+    }
+
+    fn list_rounds(&self) -> Result<Vec<Round>> {
+        self.read_all("rounds")
+    }
+
+    fn save_tournament(&mut self, tournament: &Tournament) -> Result<()> {
+        self.write_entity(&self.tournament_path(&tournament.id), tournament)
+    }
+
+    fn get_tournament(&self, id: &Uuid) -> Result<Option<Tournament>> {
+        self.read_entity(&self.tournament_path(id))
+    }
+
+    fn list_tournaments(&self) -> Result<Vec<Tournament>> {
+        self.read_all("tournaments")
+    }
+
+}
+
+/// A [`FileRepository`] with its serializer erased, so the format can be
+/// chosen at runtime.
+trait AnyFileRepository: Repository {
+    fn repair(&mut self) -> Result<RepairReport>;
+}
+
+impl<S: Serializer> AnyFileRepository for FileRepository<S> {
+    fn repair(&mut self) -> Result<RepairReport> {
+        FileRepository::repair(self)
+    }
+}
+
+/// Picks the serializer for `format`, or for the directory's manifest when
+/// `format` is `None`.
+fn open_any_file_repository(base_path: PathBuf, format: Option<Format>) -> Result<Box<dyn AnyFileRepository>> {
+    let format = match format {
+        Some(format) => format,
+        None => Manifest::read(&base_path)?.map(|m| m.format).unwrap_or_default(),
+    };
+
+    Ok(match format {
+        Format::Json => Box::new(FileRepository::with_serializer(base_path, JsonSerializer)?),
+        Format::CompactJson => Box::new(FileRepository::with_serializer(base_path, CompactJsonSerializer)?),
+        Format::Toml => Box::new(FileRepository::with_serializer(base_path, TomlSerializer)?),
+        Format::Cbor => Box::new(FileRepository::with_serializer(base_path, CborSerializer)?),
+        Format::MessagePack => Box::new(FileRepository::with_serializer(base_path, MessagePackSerializer)?),
+    })
+}
+
+/// Runs [`FileRepository::repair`] on a directory whose format is only
+/// known at runtime, following the same rules as [`open_file_repository`].
+pub fn repair_file_repository(base_path: PathBuf, format: Option<Format>) -> Result<RepairReport> {
+    open_any_file_repository(base_path, format)?.repair()
+}
+
+/// Opens a file repository whose format is only known at runtime.
+///
+/// The format recorded in the directory's manifest wins when `format` is
+/// `None`; new directories default to pretty JSON. Asking for a format that
+/// disagrees with the manifest fails with `GolfError::FormatMismatch`.
+pub fn open_file_repository(base_path: PathBuf, format: Option<Format>) -> Result<Box<dyn Repository>> {
+    Ok(open_any_file_repository(base_path, format)?)
+}
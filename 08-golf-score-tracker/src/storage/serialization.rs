@@ -0,0 +1,312 @@
+//! Pluggable storage formats for repository data.
+//!
+//! A [`Serializer`] turns domain types into bytes and back. `FileRepository`
+//! is generic over it, so the same directory layout can hold pretty JSON for
+//! hand inspection, TOML for hand-edited data, or a binary encoding (CBOR,
+//! MessagePack) for large archives.
+//!
+//! # Examples
+//!
+//! ```
+//! use golf_score_tracker::Player;
+//! use golf_score_tracker::storage::{Serializer, TomlSerializer};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let player = Player::new("Lydia Ko", Some(1.4))?;
+//! let bytes = TomlSerializer.serialize(&player)?;
+//! let restored: Player = TomlSerializer.deserialize(&bytes)?;
+//! assert_eq!(restored, player);
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GolfError, Result};
+
+/// Storage formats understood by the repository layer.
+///
+/// The format is recorded in the repository manifest so that a data
+/// directory is always read back with the encoding it was written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    /// Pretty-printed JSON (the historical default).
+    #[default]
+    Json,
+    /// JSON without indentation or newlines.
+    CompactJson,
+    /// TOML, convenient for hand-edited data.
+    Toml,
+    /// Concise Binary Object Representation (RFC 8949).
+    Cbor,
+    /// MessagePack with named struct fields.
+    MessagePack,
+}
+
+impl Format {
+    /// All supported formats, in the order they are listed in help output.
+    pub const ALL: [Format; 5] = [Format::Json, Format::CompactJson, Format::Toml, Format::Cbor, Format::MessagePack];
+
+    /// File extension (without the dot) used for entity files.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Json | Format::CompactJson => "json",
+            Format::Toml => "toml",
+            Format::Cbor => "cbor",
+            Format::MessagePack => "msgpack",
+        }
+    }
+
+    /// Name used in the manifest and on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::CompactJson => "compact-json",
+            Format::Toml => "toml",
+            Format::Cbor => "cbor",
+            Format::MessagePack => "message-pack",
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Format {
+    type Err = GolfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized = s.trim().to_ascii_lowercase().replace('_', "-");
+        match normalized.as_str() {
+            "msgpack" => Ok(Format::MessagePack),
+            other => Format::ALL
+                .into_iter()
+                .find(|format| format.name() == other)
+                .ok_or_else(|| GolfError::custom(format!(
+                    "Unknown storage format '{}'. Expected one of: {}",
+                    s,
+                    Format::ALL.map(Format::name).join(", ")
+                ))),
+        }
+    }
+}
+
+/// Encodes and decodes repository entities.
+///
+/// Implementations are stateless unit structs; the trait uses generic
+/// methods, so repositories take the serializer as a type parameter rather
+/// than a trait object.
+pub trait Serializer {
+    /// The format this serializer produces.
+    fn format(&self) -> Format;
+
+    /// Encodes a value into bytes.
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>>;
+
+    /// Decodes a value previously produced by [`Serializer::serialize`].
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T>;
+
+    /// File extension (without the dot) for entity files.
+    fn extension(&self) -> &'static str {
+        self.format().extension()
+    }
+}
+
+/// Pretty-printed JSON.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonSerializer;
+
+impl Serializer for JsonSerializer {
+    fn format(&self) -> Format {
+        Format::Json
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec_pretty(value)?)
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+/// Single-line JSON, smaller on disk but still readable by any JSON tool.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompactJsonSerializer;
+
+impl Serializer for CompactJsonSerializer {
+    fn format(&self) -> Format {
+        Format::CompactJson
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(value)?)
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+/// TOML documents.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TomlSerializer;
+
+impl Serializer for TomlSerializer {
+    fn format(&self) -> Format {
+        Format::Toml
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        toml::to_string_pretty(value)
+            .map(String::into_bytes)
+            .map_err(|e| encoding_error(Format::Toml, e))
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        let text = std::str::from_utf8(bytes).map_err(|e| encoding_error(Format::Toml, e))?;
+        toml::from_str(text).map_err(|e| encoding_error(Format::Toml, e))
+    }
+}
+
+/// CBOR binary encoding.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CborSerializer;
+
+impl Serializer for CborSerializer {
+    fn format(&self) -> Format {
+        Format::Cbor
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(value, &mut bytes).map_err(|e| encoding_error(Format::Cbor, e))?;
+        Ok(bytes)
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        ciborium::from_reader(bytes).map_err(|e| encoding_error(Format::Cbor, e))
+    }
+}
+
+/// MessagePack binary encoding.
+///
+/// Struct fields are written by name so that adding optional fields later
+/// does not break older archives.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessagePackSerializer;
+
+impl Serializer for MessagePackSerializer {
+    fn format(&self) -> Format {
+        Format::MessagePack
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        rmp_serde::to_vec_named(value).map_err(|e| encoding_error(Format::MessagePack, e))
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        rmp_serde::from_slice(bytes).map_err(|e| encoding_error(Format::MessagePack, e))
+    }
+}
+
+fn encoding_error(format: Format, error: impl fmt::Display) -> GolfError {
+    GolfError::Encoding { format, message: error.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Fairway, HoleResult, Player, Scorecard};
+    use crate::utils::get_course;
+
+    fn sample_scorecard() -> Scorecard {
+        let player = Player::new("Collin Morikawa", None).expect("player");
+        let course = get_course("St_Andrews").expect("course");
+        let mut scorecard = Scorecard::new(player.id, &course, course.default_tee()).expect("scorecard");
+        scorecard.record_score(1, 4).expect("record");
+        let detail = HoleResult { putts: Some(1), fairway: Some(Fairway::Left), sand_save: Some(true), penalties: 0 };
+        scorecard.record_hole(2, 3, detail).expect("record");
+        scorecard
+    }
+
+    fn round_trip<S: Serializer>(serializer: S) {
+        let scorecard = sample_scorecard();
+        let bytes = serializer.serialize(&scorecard).expect("serialize");
+        let restored: Scorecard = serializer.deserialize(&bytes).expect("deserialize");
+        assert_eq!(restored, scorecard);
+    }
+
+    #[test]
+    fn every_format_round_trips_a_scorecard() {
+        round_trip(JsonSerializer);
+        round_trip(CompactJsonSerializer);
+        round_trip(TomlSerializer);
+        round_trip(CborSerializer);
+        round_trip(MessagePackSerializer);
+    }
+
+    /// A scorecard as saved before its corrections became the score history.
+    const CORRECTED_SCORECARD: &str = r#"{
+        "round_id": "3f2c8a9e-5b1d-4e7a-9c3f-2a8b6d4e1f70",
+        "player_id": "9b1e4c2a-7d3f-4a8e-b5c6-1e2f3a4b5c6d",
+        "max_holes": 9,
+        "scores": { "1": 5, "2": 3 },
+        "pars": { "1": 4, "2": 3, "3": 5, "4": 4, "5": 4, "6": 3, "7": 4, "8": 5, "9": 4 },
+        "played_at": "2024-06-08T15:30:00Z",
+        "corrections": [
+            { "hole": 1, "previous": 6, "strokes": 5, "corrected_at": "2024-06-08T18:00:00Z", "reason": "Miscounted" }
+        ]
+    }"#;
+
+    fn load_corrected_scorecard<S: Serializer>(serializer: S) {
+        let fixture: serde_json::Value = serde_json::from_str(CORRECTED_SCORECARD).expect("fixture");
+        let stored = serializer.serialize(&fixture).expect("serialize fixture");
+        let scorecard: Scorecard = serializer.deserialize(&stored).expect("deserialize");
+        assert_eq!(scorecard.get_score(1), Some(5));
+        let event = &scorecard.history()[0];
+        assert_eq!((event.hole, event.previous, event.strokes), (1, Some(6), Some(5)));
+        assert_eq!(event.at.to_rfc3339(), "2024-06-08T18:00:00+00:00");
+        assert_eq!((event.reason.as_deref(), event.undoes), (Some("Miscounted"), None));
+
+        let bytes = serializer.serialize(&scorecard).expect("serialize");
+        let restored: Scorecard = serializer.deserialize(&bytes).expect("deserialize");
+        assert_eq!(restored, scorecard);
+    }
+
+    // The fixture's hole numbers are text keys, which only the text formats
+    // store them as
+    #[test]
+    fn saved_corrections_load_as_score_history() {
+        load_corrected_scorecard(JsonSerializer);
+        load_corrected_scorecard(CompactJsonSerializer);
+        load_corrected_scorecard(TomlSerializer);
+    }
+
+    #[test]
+    fn compact_json_has_no_newlines() {
+        let bytes = CompactJsonSerializer.serialize(&sample_scorecard()).expect("serialize");
+        assert!(!bytes.contains(&b'\n'));
+    }
+
+    #[test]
+    fn format_parses_names_and_aliases() {
+        assert_eq!("compact-json".parse::<Format>().unwrap(), Format::CompactJson);
+        assert_eq!("MSGPACK".parse::<Format>().unwrap(), Format::MessagePack);
+        assert_eq!("message_pack".parse::<Format>().unwrap(), Format::MessagePack);
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn decoding_garbage_reports_the_format() {
+        let result: Result<Scorecard> = CborSerializer.deserialize(b"not cbor");
+        assert!(matches!(result, Err(GolfError::Encoding { format: Format::Cbor, .. })));
+    }
+}
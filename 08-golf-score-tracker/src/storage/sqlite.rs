@@ -0,0 +1,849 @@
+//! SQLite-backed repository.
+//!
+//! Stores players, scorecards and per-hole scores in a single database file
+//! using a bundled SQLite, so no server is needed. Lookups by player go
+//! through an index instead of scanning every scorecard.
+//!
+//! # Schema
+//!
+//! ```text
+//! players      (id PK, name, handicap)
+//! scorecards   (round_id PK, player_id [indexed], max_holes, played_at,
+//!               course_rating, slope_rating, course, tee, course_handicap)
+//! hole_scores  (round_id FK, hole, par, strokes, stroke_index, putts,
+//!               fairway, sand_save, penalties) PK (round_id, hole)
+//! score_events (round_id FK, position, hole, previous, strokes, at,
+//!               reason, undoes) PK (round_id, position)
+//! rounds       (id PK, date, course, max_holes)
+//! round_players (round_id FK, position, player_id [indexed], scorecard_id)
+//!              PK (round_id, position)
+//! tournaments  (id PK, name, created_at, cut_after_round, cut_top)
+//! tournament_courses    (tournament_id FK, round, course) PK (tournament_id, round)
+//! tournament_players    (tournament_id FK, position, player_id)
+//!                       PK (tournament_id, position)
+//! tournament_scorecards (tournament_id FK, position, round, player_id, scorecard_id)
+//!                       PK (tournament_id, position)
+//! ```
+//!
+//! The schema version is tracked with `PRAGMA user_version`; opening a
+//! database applies any migrations it has not seen yet.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, Row, params};
+use uuid::Uuid;
+
+use crate::error::{GolfError, Result};
+use crate::models::{
+    CourseRating, CutRule, Fairway, HoleResult, Player, Round, RoundEntry, ScoreEvent, Scorecard, Tournament,
+    TournamentEntry,
+};
+use crate::storage::Repository;
+
+/// Schema migrations, applied in order. The schema version of a database is
+/// the number of migrations it has run, so entries must never be edited or
+/// reordered once released; add a new entry instead.
+const MIGRATIONS: &[&str] = &[
+    // 1: initial schema
+    "CREATE TABLE players (
+        id TEXT PRIMARY KEY NOT NULL,
+        name TEXT NOT NULL,
+        handicap REAL
+    );
+    CREATE TABLE scorecards (
+        round_id TEXT PRIMARY KEY NOT NULL,
+        player_id TEXT NOT NULL,
+        max_holes INTEGER NOT NULL
+    );
+    CREATE INDEX idx_scorecards_player_id ON scorecards (player_id);
+    CREATE TABLE hole_scores (
+        round_id TEXT NOT NULL REFERENCES scorecards (round_id) ON DELETE CASCADE,
+        hole INTEGER NOT NULL,
+        par INTEGER NOT NULL,
+        strokes INTEGER,
+        PRIMARY KEY (round_id, hole)
+    );",
+    // 2: round date and tee rating for handicap calculations
+    "ALTER TABLE scorecards ADD COLUMN played_at TEXT NOT NULL DEFAULT '1970-01-01 00:00:00+00:00';
+    ALTER TABLE scorecards ADD COLUMN course_rating REAL;
+    ALTER TABLE scorecards ADD COLUMN slope_rating INTEGER;",
+    // 3: course, tees and stroke indexes the round was played with
+    "ALTER TABLE scorecards ADD COLUMN course TEXT;
+    ALTER TABLE scorecards ADD COLUMN tee TEXT;
+    ALTER TABLE hole_scores ADD COLUMN stroke_index INTEGER;",
+    // 4: per-hole shot detail
+    "ALTER TABLE hole_scores ADD COLUMN putts INTEGER;
+    ALTER TABLE hole_scores ADD COLUMN fairway TEXT;
+    ALTER TABLE hole_scores ADD COLUMN sand_save INTEGER;
+    ALTER TABLE hole_scores ADD COLUMN penalties INTEGER NOT NULL DEFAULT 0;",
+    // 5: group rounds
+    "CREATE TABLE rounds (
+        id TEXT PRIMARY KEY NOT NULL,
+        date TEXT NOT NULL,
+        course TEXT NOT NULL,
+        max_holes INTEGER NOT NULL
+    );
+    CREATE TABLE round_players (
+        round_id TEXT NOT NULL REFERENCES rounds (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        player_id TEXT NOT NULL,
+        scorecard_id TEXT NOT NULL,
+        PRIMARY KEY (round_id, position)
+    );
+    CREATE INDEX idx_round_players_player_id ON round_players (player_id);",
+    // 6: tournaments
+    "CREATE TABLE tournaments (
+        id TEXT PRIMARY KEY NOT NULL,
+        name TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        cut_after_round INTEGER,
+        cut_top INTEGER
+    );
+    CREATE TABLE tournament_courses (
+        tournament_id TEXT NOT NULL REFERENCES tournaments (id) ON DELETE CASCADE,
+        round INTEGER NOT NULL,
+        course TEXT NOT NULL,
+        PRIMARY KEY (tournament_id, round)
+    );
+    CREATE TABLE tournament_players (
+        tournament_id TEXT NOT NULL REFERENCES tournaments (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        player_id TEXT NOT NULL,
+        PRIMARY KEY (tournament_id, position)
+    );
+    CREATE TABLE tournament_scorecards (
+        tournament_id TEXT NOT NULL REFERENCES tournaments (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        round INTEGER NOT NULL,
+        player_id TEXT NOT NULL,
+        scorecard_id TEXT NOT NULL,
+        PRIMARY KEY (tournament_id, position)
+    );",
+    // 7: course handicap for net scoring
+    "ALTER TABLE scorecards ADD COLUMN course_handicap INTEGER;",
+    // 8: audit trail of corrected scores
+    "CREATE TABLE score_corrections (
+        round_id TEXT NOT NULL REFERENCES scorecards (round_id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        hole INTEGER NOT NULL,
+        previous INTEGER NOT NULL,
+        strokes INTEGER NOT NULL,
+        corrected_at TEXT NOT NULL,
+        reason TEXT,
+        PRIMARY KEY (round_id, position)
+    );",
+    // 9: full score history with undo, replacing the corrections table
+    "CREATE TABLE score_events (
+        round_id TEXT NOT NULL REFERENCES scorecards (round_id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        hole INTEGER NOT NULL,
+        previous INTEGER,
+        strokes INTEGER,
+        at TEXT NOT NULL,
+        reason TEXT,
+        undoes INTEGER,
+        PRIMARY KEY (round_id, position)
+    );
+    INSERT INTO score_events (round_id, position, hole, previous, strokes, at, reason)
+        SELECT round_id, position, hole, previous, strokes, corrected_at, reason FROM score_corrections;
+    DROP TABLE score_corrections;",
+];
+
+/// Repository implementation backed by a SQLite database.
+///
+/// # Examples
+///
+/// ```
+/// use golf_score_tracker::{Player, Repository};
+/// use golf_score_tracker::storage::SqliteRepository;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut repo = SqliteRepository::open_in_memory()?;
+/// let player = Player::new("Jon Rahm", Some(0.0))?;
+/// repo.save_player(&player)?;
+/// assert_eq!(repo.get_player(&player.id)?, Some(player));
+/// # Ok(())
+/// # }
+/// ```
+pub struct SqliteRepository {
+    conn: Connection,
+}
+
+impl SqliteRepository {
+    /// Opens (or creates) the database at `path` and migrates it to the
+    /// latest schema.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use golf_score_tracker::storage::SqliteRepository;
+    ///
+    /// let repo = SqliteRepository::open("./golf_data/golf.db")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        if let Some(parent) = path.as_ref().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Opens a private in-memory database, mainly useful for tests.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        let mut repo = Self { conn };
+        repo.migrate()?;
+        Ok(repo)
+    }
+
+    /// Returns the schema version the database is currently at.
+    pub fn schema_version(&self) -> Result<usize> {
+        let version: i64 = self.conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        Ok(version as usize)
+    }
+
+    fn migrate(&mut self) -> Result<()> {
+        let current = self.schema_version()?;
+        if current > MIGRATIONS.len() {
+            return Err(GolfError::custom(format!(
+                "Database schema version {} is newer than this program supports ({})",
+                current,
+                MIGRATIONS.len()
+            )));
+        }
+
+        for (index, sql) in MIGRATIONS.iter().enumerate().skip(current) {
+            let tx = self.conn.transaction()?;
+            tx.execute_batch(sql)?;
+            tx.pragma_update(None, "user_version", (index + 1) as i64)?;
+            tx.commit()?;
+        }
+        Ok(())
+    }
+
+    /// Loads scorecards matching `filter` (a SQL condition on the
+    /// `scorecards` table aliased as `s`) together with their hole rows.
+    fn query_scorecards(&self, filter: &str, params: &[&dyn rusqlite::ToSql]) -> Result<Vec<Scorecard>> {
+        let sql = format!(
+            "SELECT s.round_id, s.player_id, s.max_holes, s.played_at, s.course_rating, s.slope_rating,
+                    s.course, s.tee, h.hole, h.par, h.strokes, h.stroke_index,
+                    h.putts, h.fairway, h.sand_save, h.penalties, s.course_handicap
+             FROM scorecards s
+             LEFT JOIN hole_scores h ON h.round_id = s.round_id
+             WHERE {}
+             ORDER BY s.round_id, h.hole",
+            filter
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut rows = stmt.query(params)?;
+
+        let mut scorecards = Vec::new();
+        let mut current: Option<PendingScorecard> = None;
+        while let Some(row) = rows.next()? {
+            let round_id = parse_uuid(row, 0)?;
+            if current.as_ref().is_none_or(|pending| pending.round_id != round_id) {
+                if let Some(pending) = current.take() {
+                    scorecards.push(pending.build()?);
+                }
+                current = Some(PendingScorecard {
+                    round_id,
+                    player_id: parse_uuid(row, 1)?,
+                    max_holes: row.get(2)?,
+                    played_at: row.get(3)?,
+                    rating: match (row.get::<_, Option<f64>>(4)?, row.get::<_, Option<u16>>(5)?) {
+                        (Some(course_rating), Some(slope_rating)) => Some(CourseRating { course_rating, slope_rating }),
+                        _ => None,
+                    },
+                    course: row.get(6)?,
+                    tee: row.get(7)?,
+                    course_handicap: row.get(16)?,
+                    pars: BTreeMap::new(),
+                    scores: BTreeMap::new(),
+                    stroke_indexes: BTreeMap::new(),
+                    results: BTreeMap::new(),
+                });
+            }
+
+            let pending = current.as_mut().expect("pending scorecard was just set");
+            if let Some(hole) = row.get::<_, Option<u8>>(8)? {
+                pending.pars.insert(hole, row.get(9)?);
+                if let Some(strokes) = row.get::<_, Option<u8>>(10)? {
+                    pending.scores.insert(hole, strokes);
+                }
+                if let Some(stroke_index) = row.get::<_, Option<u8>>(11)? {
+                    pending.stroke_indexes.insert(hole, stroke_index);
+                }
+                let result = HoleResult {
+                    putts: row.get(12)?,
+                    fairway: row.get::<_, Option<String>>(13)?.map(|text| text.parse()).transpose()?,
+                    sand_save: row.get(14)?,
+                    penalties: row.get(15)?,
+                };
+                if !result.is_empty() {
+                    pending.results.insert(hole, result);
+                }
+            }
+        }
+        if let Some(pending) = current {
+            scorecards.push(pending.build()?);
+        }
+
+        let sql = format!(
+            "SELECT e.round_id, e.hole, e.previous, e.strokes, e.at, e.reason, e.undoes
+             FROM score_events e
+             JOIN scorecards s ON s.round_id = e.round_id
+             WHERE {}
+             ORDER BY e.round_id, e.position",
+            filter
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut rows = stmt.query(params)?;
+        let mut history: HashMap<Uuid, Vec<ScoreEvent>> = HashMap::new();
+        while let Some(row) = rows.next()? {
+            history.entry(parse_uuid(row, 0)?).or_default().push(ScoreEvent {
+                hole: row.get(1)?,
+                previous: row.get(2)?,
+                strokes: row.get(3)?,
+                at: row.get(4)?,
+                reason: row.get(5)?,
+                undoes: row.get(6)?,
+            });
+        }
+        for scorecard in &mut scorecards {
+            if let Some(history) = history.remove(&scorecard.round_id) {
+                scorecard.set_history(history);
+            }
+        }
+        Ok(scorecards)
+    }
+}
+
+impl SqliteRepository {
+    /// Loads rounds matching `filter` (a SQL condition on the `rounds`
+    /// table aliased as `r`) together with their players.
+    fn query_rounds(&self, filter: &str, params: &[&dyn rusqlite::ToSql]) -> Result<Vec<Round>> {
+        let sql = format!(
+            "SELECT r.id, r.date, r.course, r.max_holes, p.player_id, p.scorecard_id
+             FROM rounds r
+             LEFT JOIN round_players p ON p.round_id = r.id
+             WHERE {}
+             ORDER BY r.date, r.id, p.position",
+            filter
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut rows = stmt.query(params)?;
+
+        let mut rounds: Vec<Round> = Vec::new();
+        while let Some(row) = rows.next()? {
+            let id = parse_uuid(row, 0)?;
+            if rounds.last().is_none_or(|round| round.id != id) {
+                rounds.push(Round {
+                    id,
+                    date: row.get(1)?,
+                    course: row.get(2)?,
+                    max_holes: row.get(3)?,
+                    entries: Vec::new(),
+                });
+            }
+            if row.get::<_, Option<String>>(4)?.is_some() {
+                let entry = RoundEntry { player_id: parse_uuid(row, 4)?, scorecard_id: parse_uuid(row, 5)? };
+                rounds.last_mut().expect("round was just pushed").entries.push(entry);
+            }
+        }
+        Ok(rounds)
+    }
+
+    /// Loads tournaments matching `filter` (a SQL condition on the
+    /// `tournaments` table aliased as `t`) with their courses, field and
+    /// scorecards.
+    fn query_tournaments(&self, filter: &str, params: &[&dyn rusqlite::ToSql]) -> Result<Vec<Tournament>> {
+        let sql = format!(
+            "SELECT t.id, t.name, t.created_at, t.cut_after_round, t.cut_top
+             FROM tournaments t
+             WHERE {}
+             ORDER BY t.created_at, t.id",
+            filter
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut tournaments = Vec::new();
+        let mut rows = stmt.query(params)?;
+        while let Some(row) = rows.next()? {
+            let cut = match (row.get::<_, Option<u8>>(3)?, row.get::<_, Option<usize>>(4)?) {
+                (Some(after_round), Some(top)) => Some(CutRule { after_round, top }),
+                _ => None,
+            };
+            tournaments.push(Tournament {
+                id: parse_uuid(row, 0)?,
+                name: row.get(1)?,
+                created_at: row.get(2)?,
+                courses: Vec::new(),
+                cut,
+                field: Vec::new(),
+                entries: Vec::new(),
+            });
+        }
+
+        for tournament in &mut tournaments {
+            let id = tournament.id.to_string();
+            let mut courses = self.conn.prepare(
+                "SELECT course FROM tournament_courses WHERE tournament_id = ?1 ORDER BY round",
+            )?;
+            tournament.courses = courses.query_map(params![id], |row| row.get(0))?.collect::<rusqlite::Result<_>>()?;
+
+            let mut players = self.conn.prepare(
+                "SELECT player_id FROM tournament_players WHERE tournament_id = ?1 ORDER BY position",
+            )?;
+            let mut rows = players.query(params![id])?;
+            while let Some(row) = rows.next()? {
+                tournament.field.push(parse_uuid(row, 0)?);
+            }
+
+            let mut entries = self.conn.prepare(
+                "SELECT round, player_id, scorecard_id FROM tournament_scorecards
+                 WHERE tournament_id = ?1 ORDER BY position",
+            )?;
+            let mut rows = entries.query(params![id])?;
+            while let Some(row) = rows.next()? {
+                tournament.entries.push(TournamentEntry {
+                    round: row.get(0)?,
+                    player_id: parse_uuid(row, 1)?,
+                    scorecard_id: parse_uuid(row, 2)?,
+                });
+            }
+        }
+        Ok(tournaments)
+    }
+}
+
+/// Scorecard columns collected while walking the joined hole rows.
+struct PendingScorecard {
+    round_id: Uuid,
+    player_id: Uuid,
+    max_holes: u8,
+    played_at: DateTime<Utc>,
+    rating: Option<CourseRating>,
+    course: Option<String>,
+    tee: Option<String>,
+    course_handicap: Option<i32>,
+    pars: BTreeMap<u8, u8>,
+    scores: BTreeMap<u8, u8>,
+    stroke_indexes: BTreeMap<u8, u8>,
+    results: BTreeMap<u8, HoleResult>,
+}
+
+impl PendingScorecard {
+    fn build(self) -> Result<Scorecard> {
+        let mut scorecard =
+            Scorecard::from_parts(self.round_id, self.player_id, self.max_holes, self.pars, self.scores)?;
+        scorecard.played_at = self.played_at;
+        scorecard.rating = self.rating;
+        scorecard.course = self.course;
+        scorecard.tee = self.tee;
+        scorecard.course_handicap = self.course_handicap;
+        scorecard.set_stroke_indexes(self.stroke_indexes);
+        scorecard.set_hole_results(self.results);
+        Ok(scorecard)
+    }
+}
+
+fn parse_uuid(row: &Row<'_>, index: usize) -> Result<Uuid> {
+    let text: String = row.get(index)?;
+    Uuid::parse_str(&text).map_err(|e| GolfError::custom(format!("Corrupt UUID '{}' in database: {}", text, e)))
+}
+
+fn player_from_row(row: &Row<'_>) -> Result<Player> {
+    Ok(Player { id: parse_uuid(row, 0)?, name: row.get(1)?, handicap: row.get(2)? })
+}
+
+impl Repository for SqliteRepository {
+    fn save_player(&mut self, player: &Player) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO players (id, name, handicap) VALUES (?1, ?2, ?3)
+             ON CONFLICT (id) DO UPDATE SET name = excluded.name, handicap = excluded.handicap",
+            params![player.id.to_string(), player.name, player.handicap],
+        )?;
+        Ok(())
+    }
+
+    fn get_player(&self, id: &Uuid) -> Result<Option<Player>> {
+        let mut stmt = self.conn.prepare("SELECT id, name, handicap FROM players WHERE id = ?1")?;
+        let mut rows = stmt.query(params![id.to_string()])?;
+        rows.next()?.map(player_from_row).transpose()
+    }
+
+    fn list_players(&self) -> Result<Vec<Player>> {
+        let mut stmt = self.conn.prepare("SELECT id, name, handicap FROM players ORDER BY name")?;
+        let mut rows = stmt.query([])?;
+        let mut players = Vec::new();
+        while let Some(row) = rows.next()? {
+            players.push(player_from_row(row)?);
+        }
+        Ok(players)
+    }
+
+    fn remove_player(&mut self, id: &Uuid) -> Result<bool> {
+        let deleted = self.conn.execute("DELETE FROM players WHERE id = ?1", params![id.to_string()])?;
+        Ok(deleted > 0)
+    }
+
+    fn save_scorecard(&mut self, scorecard: &Scorecard) -> Result<()> {
+        let round_id = scorecard.round_id.to_string();
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO scorecards
+                (round_id, player_id, max_holes, played_at, course_rating, slope_rating, course, tee, course_handicap)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT (round_id) DO UPDATE SET
+                player_id = excluded.player_id,
+                max_holes = excluded.max_holes,
+                played_at = excluded.played_at,
+                course_rating = excluded.course_rating,
+                slope_rating = excluded.slope_rating,
+                course = excluded.course,
+                tee = excluded.tee,
+                course_handicap = excluded.course_handicap",
+            params![
+                round_id,
+                scorecard.player_id.to_string(),
+                scorecard.max_holes,
+                scorecard.played_at,
+                scorecard.rating.map(|r| r.course_rating),
+                scorecard.rating.map(|r| r.slope_rating),
+                scorecard.course,
+                scorecard.tee,
+                scorecard.course_handicap,
+            ],
+        )?;
+        tx.execute("DELETE FROM hole_scores WHERE round_id = ?1", params![round_id])?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO hole_scores (round_id, hole, par, strokes, stroke_index, putts, fairway, sand_save, penalties)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?;
+            for (&hole, &par) in scorecard.pars() {
+                let result = scorecard.hole_result(hole).copied().unwrap_or_default();
+                insert.execute(params![
+                    round_id,
+                    hole,
+                    par,
+                    scorecard.get_score(hole),
+                    scorecard.stroke_index(hole),
+                    result.putts,
+                    result.fairway.map(Fairway::name),
+                    result.sand_save,
+                    result.penalties,
+                ])?;
+            }
+        }
+        tx.execute("DELETE FROM score_events WHERE round_id = ?1", params![round_id])?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO score_events (round_id, position, hole, previous, strokes, at, reason, undoes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            for (position, event) in scorecard.history().iter().enumerate() {
+                insert.execute(params![
+                    round_id,
+                    position,
+                    event.hole,
+                    event.previous,
+                    event.strokes,
+                    event.at,
+                    event.reason,
+                    event.undoes,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn remove_scorecard(&mut self, round_id: &Uuid) -> Result<bool> {
+        let deleted = self.conn.execute("DELETE FROM scorecards WHERE round_id = ?1", params![round_id.to_string()])?;
+        Ok(deleted > 0)
+    }
+
+    fn get_scorecard(&self, round_id: &Uuid) -> Result<Option<Scorecard>> {
+        Ok(self.query_scorecards("s.round_id = ?1", &[&round_id.to_string()])?.pop())
+    }
+
+    fn get_scorecards_by_player(&self, player_id: &Uuid) -> Result<Vec<Scorecard>> {
+        self.query_scorecards("s.player_id = ?1", &[&player_id.to_string()])
+    }
+
+    fn list_scorecards(&self) -> Result<Vec<Scorecard>> {
+        self.query_scorecards("1 = 1", &[])
+    }
+
+    fn save_round(&mut self, round: &Round) -> Result<()> {
+        let id = round.id.to_string();
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO rounds (id, date, course, max_holes) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (id) DO UPDATE SET
+                date = excluded.date,
+                course = excluded.course,
+                max_holes = excluded.max_holes",
+            params![id, round.date, round.course, round.max_holes],
+        )?;
+        tx.execute("DELETE FROM round_players WHERE round_id = ?1", params![id])?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO round_players (round_id, position, player_id, scorecard_id) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (position, entry) in round.entries.iter().enumerate() {
+                insert.execute(params![id, position, entry.player_id.to_string(), entry.scorecard_id.to_string()])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get_round(&self, id: &Uuid) -> Result<Option<Round>> {
+        Ok(self.query_rounds("r.id = ?1", &[&id.to_string()])?.pop())
+    }
+
+    fn list_rounds(&self) -> Result<Vec<Round>> {
+        self.query_rounds("1 = 1", &[])
+    }
+
+    fn get_rounds_by_player(&self, player_id: &Uuid) -> Result<Vec<Round>> {
+        self.query_rounds(
+            "r.id IN (SELECT round_id FROM round_players WHERE player_id = ?1)",
+            &[&player_id.to_string()],
+        )
+    }
+
+    fn save_tournament(&mut self, tournament: &Tournament) -> Result<()> {
+        let id = tournament.id.to_string();
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO tournaments (id, name, created_at, cut_after_round, cut_top) VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT (id) DO UPDATE SET
+                name = excluded.name,
+                created_at = excluded.created_at,
+                cut_after_round = excluded.cut_after_round,
+                cut_top = excluded.cut_top",
+            params![
+                id,
+                tournament.name,
+                tournament.created_at,
+                tournament.cut.map(|cut| cut.after_round),
+                tournament.cut.map(|cut| cut.top),
+            ],
+        )?;
+        for table in ["tournament_courses", "tournament_players", "tournament_scorecards"] {
+            tx.execute(&format!("DELETE FROM {} WHERE tournament_id = ?1", table), params![id])?;
+        }
+        {
+            let mut insert = tx.prepare("INSERT INTO tournament_courses (tournament_id, round, course) VALUES (?1, ?2, ?3)")?;
+            for (round, course) in (1..).zip(&tournament.courses) {
+                insert.execute(params![id, round, course])?;
+            }
+            let mut insert = tx.prepare("INSERT INTO tournament_players (tournament_id, position, player_id) VALUES (?1, ?2, ?3)")?;
+            for (position, player_id) in tournament.field.iter().enumerate() {
+                insert.execute(params![id, position, player_id.to_string()])?;
+            }
+            let mut insert = tx.prepare(
+                "INSERT INTO tournament_scorecards (tournament_id, position, round, player_id, scorecard_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for (position, entry) in tournament.entries.iter().enumerate() {
+                insert.execute(params![id, position, entry.round, entry.player_id.to_string(), entry.scorecard_id.to_string()])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get_tournament(&self, id: &Uuid) -> Result<Option<Tournament>> {
+        Ok(self.query_tournaments("t.id = ?1", &[&id.to_string()])?.pop())
+    }
+
+    fn list_tournaments(&self) -> Result<Vec<Tournament>> {
+        self.query_tournaments("1 = 1", &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{create_standard_pars, get_course};
+
+    #[test]
+    fn fresh_database_is_fully_migrated() {
+        let repo = SqliteRepository::open_in_memory().expect("open");
+        assert_eq!(repo.schema_version().expect("version"), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn player_update_overwrites_existing_row() {
+        let mut repo = SqliteRepository::open_in_memory().expect("open");
+        let mut player = Player::new("Viktor Hovland", Some(2.0)).expect("player");
+        repo.save_player(&player).expect("save");
+
+        player.handicap = Some(1.5);
+        repo.save_player(&player).expect("save again");
+
+        assert_eq!(repo.list_players().expect("list"), vec![player]);
+    }
+
+    #[test]
+    fn scorecards_round_trip_with_partial_scores() {
+        let mut repo = SqliteRepository::open_in_memory().expect("open");
+        let player_id = Uuid::new_v4();
+        let mut scorecard = Scorecard::from_pars(player_id, 9, create_standard_pars(9))
+            .expect("scorecard")
+            .with_rating(CourseRating::new(35.2, 121).expect("rating"));
+        scorecard.record_score(1, 5).expect("record");
+        scorecard.record_score(4, 3).expect("record");
+        repo.save_scorecard(&scorecard).expect("save");
+
+        scorecard.record_score(2, 3).expect("record");
+        repo.save_scorecard(&scorecard).expect("save again");
+
+        assert_eq!(repo.get_scorecard(&scorecard.round_id).expect("get"), Some(scorecard.clone()));
+        assert_eq!(repo.get_scorecards_by_player(&player_id).expect("by player"), vec![scorecard]);
+        assert_eq!(repo.get_scorecards_by_player(&Uuid::new_v4()).expect("by player"), vec![]);
+    }
+
+    #[test]
+    fn score_history_round_trips_and_goes_with_the_scorecard() {
+        let mut repo = SqliteRepository::open_in_memory().expect("open");
+        let mut scorecard = Scorecard::from_pars(Uuid::new_v4(), 3, create_standard_pars(3)).expect("scorecard");
+        for hole in 1..=3 {
+            scorecard.record_score(hole, 4).expect("record");
+        }
+        scorecard.correct_score(2, 5, Some("marker misread the card")).expect("correct");
+        repo.save_scorecard(&scorecard).expect("save");
+        scorecard.correct_score(3, 3, None).expect("correct");
+        scorecard.undo(Some("hole 3 was right")).expect("undo");
+        repo.save_scorecard(&scorecard).expect("save again");
+
+        let restored = repo.get_scorecard(&scorecard.round_id).expect("get").expect("scorecard");
+        assert_eq!(restored.history(), scorecard.history());
+        assert_eq!(restored.history()[5].undoes, Some(4));
+
+        assert!(repo.remove_scorecard(&scorecard.round_id).expect("remove"));
+        let orphans: i64 = repo.conn.query_row("SELECT COUNT(*) FROM score_events", [], |row| row.get(0)).expect("count");
+        assert_eq!(orphans, 0);
+    }
+
+    #[test]
+    fn corrections_become_score_history() {
+        let conn = Connection::open_in_memory().expect("open");
+        for migration in &MIGRATIONS[..8] {
+            conn.execute_batch(migration).expect("schema");
+        }
+        conn.pragma_update(None, "user_version", 8).expect("version");
+        let round_id = Uuid::new_v4().to_string();
+        conn.execute(
+            "INSERT INTO scorecards (round_id, player_id, max_holes) VALUES (?1, ?2, 1)",
+            params![round_id, Uuid::new_v4().to_string()],
+        )
+        .expect("scorecard");
+        conn.execute(
+            "INSERT INTO hole_scores (round_id, hole, par, strokes) VALUES (?1, 1, 4, 5)",
+            params![round_id],
+        )
+        .expect("hole");
+        conn.execute(
+            "INSERT INTO score_corrections VALUES (?1, 0, 1, 4, 5, '2025-06-01T12:00:00Z', 'Signed for a 4')",
+            params![round_id],
+        )
+        .expect("correction");
+
+        let repo = SqliteRepository::from_connection(conn).expect("migrate");
+
+        let history = repo.list_scorecards().expect("list")[0].history().to_vec();
+        assert_eq!((history[0].previous, history[0].strokes), (Some(4), Some(5)));
+        assert_eq!(history[0].reason.as_deref(), Some("Signed for a 4"));
+    }
+
+    #[test]
+    fn version_one_database_is_migrated_in_place() {
+        let conn = Connection::open_in_memory().expect("open");
+        conn.execute_batch(MIGRATIONS[0]).expect("initial schema");
+        conn.pragma_update(None, "user_version", 1).expect("version");
+        conn.execute(
+            "INSERT INTO scorecards (round_id, player_id, max_holes) VALUES (?1, ?2, 9)",
+            params![Uuid::new_v4().to_string(), Uuid::new_v4().to_string()],
+        )
+        .expect("legacy row");
+
+        let repo = SqliteRepository::from_connection(conn).expect("migrate");
+
+        assert_eq!(repo.schema_version().expect("version"), MIGRATIONS.len());
+        let scorecards = repo.list_scorecards().expect("list");
+        assert_eq!(scorecards[0].played_at, DateTime::<Utc>::default());
+        assert_eq!(scorecards[0].rating, None);
+        assert_eq!(scorecards[0].course, None);
+    }
+
+    #[test]
+    fn course_scorecards_keep_tees_and_stroke_indexes() {
+        let mut repo = SqliteRepository::open_in_memory().expect("open");
+        let course = get_course("Torrey_Pines_South").expect("course");
+        let mut scorecard = Scorecard::new(Uuid::new_v4(), &course, course.tee("Blue").expect("tee")).expect("scorecard");
+        scorecard.record_score(1, 5).expect("record");
+        let detail = HoleResult { putts: Some(2), fairway: Some(Fairway::Right), sand_save: Some(false), penalties: 1 };
+        scorecard.record_hole(2, 6, detail).expect("record detail");
+        repo.save_scorecard(&scorecard).expect("save");
+
+        let restored = repo.get_scorecard(&scorecard.round_id).expect("get").expect("scorecard");
+        assert_eq!(restored.stroke_index(4), Some(1));
+        assert_eq!(restored, scorecard);
+    }
+
+    #[test]
+    fn rounds_keep_player_order() {
+        let mut repo = SqliteRepository::open_in_memory().expect("open");
+        let course = get_course("Pebble Beach").expect("course");
+        let mut round = Round::new(&course);
+        let players: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        for player_id in &players {
+            round.add_player(*player_id, &course, course.default_tee()).expect("add player");
+        }
+        repo.save_round(&round).expect("save");
+
+        assert_eq!(repo.get_round(&round.id).expect("get"), Some(round.clone()));
+        assert_eq!(repo.get_rounds_by_player(&players[2]).expect("by player"), vec![round]);
+        assert_eq!(repo.get_rounds_by_player(&Uuid::new_v4()).expect("by player"), vec![]);
+    }
+
+    #[test]
+    fn tournaments_round_trip_with_cut_and_entries() {
+        let mut repo = SqliteRepository::open_in_memory().expect("open");
+        let course = get_course("Pebble_Beach").expect("course");
+        let mut tournament = Tournament::new("Pro-Am", &[&course, &course, &course])
+            .expect("tournament")
+            .with_cut(CutRule { after_round: 2, top: 60 })
+            .expect("cut");
+        let players = [Uuid::new_v4(), Uuid::new_v4()];
+        for player_id in players {
+            tournament.join(player_id).expect("join");
+        }
+        for player_id in players.iter().rev() {
+            tournament.start_round(1, *player_id, &course, course.default_tee()).expect("start");
+        }
+
+        repo.save_tournament(&tournament).expect("save");
+        tournament.start_round(2, players[0], &course, course.default_tee()).expect("start");
+        repo.save_tournament(&tournament).expect("save again");
+
+        assert_eq!(repo.get_tournament(&tournament.id).expect("get"), Some(tournament.clone()));
+        assert_eq!(repo.list_tournaments().expect("list"), vec![tournament]);
+    }
+
+    #[test]
+    fn missing_scorecard_is_none() {
+        let repo = SqliteRepository::open_in_memory().expect("open");
+        assert_eq!(repo.get_scorecard(&Uuid::new_v4()).expect("get"), None);
+    }
+}
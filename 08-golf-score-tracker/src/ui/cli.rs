@@ -1,14 +1,47 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use clap::{Parser, Subcommand, ValueEnum};
 use uuid::Uuid;
 
+use crate::models::{Fairway, TeeSet};
+use crate::services::ScoringFormat;
+use crate::ui::display::CardFormat;
+use crate::storage::{App, DuplicatePolicy, ExportFormat, Format};
+
 #[derive(Parser)]
 #[command(name = "golf-tracker")]
 #[command(about = "Track golf scores for players", long_about = None)]
 pub struct Cli {
+    /// Where players and scorecards are stored.
+    #[arg(long, global = true, value_enum, default_value_t = Backend::File)]
+    pub backend: Backend,
+
+    /// Storage format for a new data directory: json, compact-json, toml, cbor or message-pack.
+    /// Existing directories keep the format recorded in their manifest.
+    #[arg(long, global = true, value_name = "FORMAT")]
+    pub storage_format: Option<Format>,
+
+    /// Data directory to use, overriding profiles and GOLF_TRACKER_DATA.
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "profile")]
+    pub data_dir: Option<PathBuf>,
+
+    /// Use the data directory of a profile from the config file.
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+/// Storage backends selectable from the command line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Backend {
+    /// One file per player and scorecard under the data directory.
+    #[default]
+    File,
+    /// A single SQLite database (`golf.db`) in the data directory.
+    Sqlite,
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     AddPlayer {
@@ -18,33 +51,342 @@ pub enum Commands {
     },
 
     ListPlayers,
+
+    /// Rename a player or change their handicap index
+    EditPlayer {
+        /// Player name, ID or unique ID prefix
+        player: String,
+        /// New name
+        #[arg(long)]
+        name: Option<String>,
+        /// New handicap index
+        #[arg(short = 'c', long, conflicts_with = "clear_handicap")]
+        handicap: Option<f64>,
+        /// Remove the player's handicap index
+        #[arg(long)]
+        clear_handicap: bool,
+    },
+
+    /// Delete a player, refusing if they have scorecards unless --cascade is given
+    DeletePlayer {
+        /// Player name, ID or unique ID prefix
+        player: String,
+        /// Also delete the player's scorecards and tournament entries
+        #[arg(long)]
+        cascade: bool,
+    },
     
     CreateScorecard {
-        player_id: Uuid,
+        /// Player name, ID or unique ID prefix
+        player: String,
         #[arg(short = 'n', long, default_value = "18")]
         holes: u8,
         #[arg(short = 'c', long, value_name = "COURSE")]
         course: Option<String>,
+        /// Tees played (defaults to the course's first tees)
+        #[arg(short = 't', long, value_name = "TEE")]
+        tee: Option<String>,
+        /// Course rating of the tees played, overriding the tee's rating (e.g. 72.4)
+        #[arg(long, requires = "slope")]
+        course_rating: Option<f64>,
+        /// Slope rating of the tees played (55-155)
+        #[arg(long, requires = "course_rating")]
+        slope: Option<u16>,
     },
     
     RecordScore {
-        player_id: Uuid,
+        /// Player name, ID or unique ID prefix
+        player: String,
         hole: u8,
         strokes: u8,
+        /// Scorecard, or group round, to record on when the player has more than one open
+        #[arg(short, long, value_name = "UUID")]
+        round: Option<Uuid>,
+        /// Putts taken on the green
+        #[arg(long)]
+        putts: Option<u8>,
+        /// Tee shot result on par 4s and 5s: hit, left or right
+        #[arg(long)]
+        fairway: Option<Fairway>,
+        /// Whether an up and down from a greenside bunker was made (true/false)
+        #[arg(long, value_name = "SAVED")]
+        sand_save: Option<bool>,
+        /// Penalty strokes, already included in STROKES
+        #[arg(long, default_value_t = 0)]
+        penalties: u8,
     },
     
+    /// Print a scorecard hole by hole, with birdies circled and bogeys boxed
     ShowScorecard {
         round_id: Uuid,
+        /// text, markdown, html or json
+        #[arg(short, long, default_value_t = CardFormat::Text)]
+        format: CardFormat,
     },
     
+    /// Enter a round hole by hole at an interactive prompt, saving after every hole
+    Play {
+        round_id: Uuid,
+    },
+
+    /// Correct a score already recorded, even on a completed scorecard
+    FixScore {
+        round_id: Uuid,
+        hole: u8,
+        strokes: u8,
+        /// Why the score was changed, kept with the correction
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Revert the latest score change on a scorecard
+    Undo {
+        round_id: Uuid,
+        /// Why the change was reverted, kept in the history
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Show every change made to a scorecard's scores
+    History {
+        round_id: Uuid,
+    },
+
     ListScorecards {
+        /// Player name, ID or unique ID prefix
         #[arg(short, long)]
-        player_id: Option<Uuid>,
+        player: Option<String>,
     },
     
+    /// Start a group round with a scorecard for each player
+    CreateRound {
+        /// Players in the group by name or ID, comma separated (e.g. Rory,Shane,ID3)
+        #[arg(long, value_delimiter = ',', required = true, value_name = "PLAYERS")]
+        players: Vec<String>,
+        #[arg(short = 'n', long, default_value = "18")]
+        holes: u8,
+        #[arg(short = 'c', long, value_name = "COURSE")]
+        course: Option<String>,
+        /// Tees played by the whole group (defaults to the course's first tees)
+        #[arg(short = 't', long, value_name = "TEE")]
+        tee: Option<String>,
+    },
+
+    /// Show everyone's hole-by-hole scores in a group round
+    ShowRound {
+        round_id: Uuid,
+    },
+
+    /// Score a group round under a game format
+    ScoreRound {
+        round_id: Uuid,
+        /// stroke-play, match-play, stableford, net-stableford or skins
+        #[arg(short, long, default_value_t = ScoringFormat::StrokePlay)]
+        format: ScoringFormat,
+    },
+
+    ListRounds {
+        /// Player name, ID or unique ID prefix
+        #[arg(short, long)]
+        player: Option<String>,
+    },
+
+    /// Multi-round tournaments with a cut and leaderboard
+    Tournament {
+        #[command(subcommand)]
+        command: TournamentCommand,
+    },
+
+    /// Browse players, scorecards and statistics in a full-screen terminal UI
+    #[command(visible_alias = "browse")]
+    Tui,
+
+    /// Serve players, scorecards, courses and statistics as a JSON REST API
+    Serve {
+        /// Address to listen on; keep to localhost unless the network is trusted
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        #[arg(short, long, default_value = "8080")]
+        port: u16,
+        /// Origin of a web front end allowed to call the API from a browser
+        #[arg(long, value_name = "ORIGIN")]
+        allow_origin: Option<String>,
+    },
+
+    /// Named data directories, e.g. one per club
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommand,
+    },
+
     ListCourses,
 
+    /// Register a local course in the data directory's course catalog
+    AddCourse {
+        /// Course name, e.g. "Riverside Nine"
+        #[arg(required_unless_present = "file", conflicts_with = "file")]
+        name: Option<String>,
+        /// Par of each hole in playing order, comma separated (e.g. 4,5,3,4)
+        #[arg(long, value_delimiter = ',', required_unless_present = "file", conflicts_with = "file")]
+        pars: Vec<u8>,
+        /// Yardage of each hole in playing order, comma separated
+        #[arg(long, value_delimiter = ',', conflicts_with = "file")]
+        yardages: Vec<u16>,
+        /// Handicap stroke index of each hole in playing order, comma separated
+        #[arg(long, value_delimiter = ',', conflicts_with = "file")]
+        stroke_indexes: Vec<u8>,
+        /// Tees as NAME:RATING:SLOPE, repeatable (e.g. White:70.1:125). Defaults to an unrated tee
+        #[arg(long = "tee", value_name = "NAME:RATING:SLOPE", value_parser = parse_tee, conflicts_with = "file")]
+        tees: Vec<TeeSet>,
+        /// Read the course definition from a TOML or JSON file instead
+        #[arg(long, value_name = "PATH")]
+        file: Option<PathBuf>,
+        /// Overwrite a local course, or shadow a built-in one, with the same name
+        #[arg(long)]
+        replace: bool,
+    },
+
+    /// Show the holes and tees of a course
+    ShowCourse {
+        course: String,
+    },
+
+    /// Delete a local course from the catalog
+    RemoveCourse {
+        course: String,
+    },
+
+    #[command(visible_alias = "stats")]
     ShowPlayerStatistics {
-        player_id: Uuid,
+        /// Player name, ID or unique ID prefix
+        player: String,
+        /// Show scoring trend, consistency and per-hole averages
+        #[arg(long)]
+        trend: bool,
+        /// Rounds in the moving average
+        #[arg(long, value_name = "ROUNDS", default_value_t = 5, requires = "trend")]
+        last: usize,
     },
-}
\ No newline at end of file
+
+    /// Show a player's World Handicap System index and how it was derived
+    Handicap {
+        /// Player name, ID or unique ID prefix
+        player: String,
+        /// Store the calculated index on the player
+        #[arg(long)]
+        update: bool,
+    },
+
+    /// Write every player and scorecard to a JSON bundle or a CSV with one row per hole
+    Export {
+        /// File to write (defaults to standard output)
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// json or csv (defaults to the output file's extension, else json)
+        #[arg(short, long)]
+        format: Option<ExportFormat>,
+    },
+
+    /// Load players and scorecards from a file written by `export`
+    Import {
+        file: PathBuf,
+        /// json or csv (defaults to the file's extension)
+        #[arg(short, long)]
+        format: Option<ExportFormat>,
+        /// What to do with records that already exist: merge or skip
+        #[arg(long, value_name = "POLICY")]
+        on_duplicate: Option<DuplicatePolicy>,
+    },
+
+    /// Import a player's rounds from a golf app's CSV export
+    ImportRounds {
+        file: PathBuf,
+        /// Player name, ID or unique ID prefix
+        #[arg(short, long)]
+        player: String,
+        /// App that wrote the file: golfgamebook or 18birdies
+        #[arg(long, default_value_t = App::GolfGameBook)]
+        app: App,
+        /// TOML file naming the columns, for other apps
+        #[arg(long, value_name = "PATH", conflicts_with = "app")]
+        mapping: Option<PathBuf>,
+    },
+
+    /// Quarantine unreadable data files and clean up interrupted writes
+    Repair,
+}
+
+#[derive(Subcommand)]
+pub enum TournamentCommand {
+    /// Set up a tournament; players join before the first round starts
+    Create {
+        name: String,
+        /// Number of rounds
+        #[arg(short = 'r', long, default_value = "4")]
+        rounds: u8,
+        /// Course for every round, or one per round, comma separated
+        #[arg(short = 'c', long = "course", value_name = "COURSE", value_delimiter = ',', required = true)]
+        courses: Vec<String>,
+        /// Round after which the field is cut
+        #[arg(long, value_name = "ROUND", requires = "cut_top")]
+        cut_after: Option<u8>,
+        /// Players (and ties) who make the cut
+        #[arg(long, value_name = "PLAYERS", requires = "cut_after")]
+        cut_top: Option<usize>,
+    },
+
+    /// Add players to a tournament's field
+    Join {
+        tournament_id: Uuid,
+        /// Player names, IDs or unique ID prefixes
+        #[arg(required = true)]
+        players: Vec<String>,
+    },
+
+    /// Create scorecards for everyone still in the field for a round
+    StartRound {
+        tournament_id: Uuid,
+        round: u8,
+        /// Tees played (defaults to the course's first tees)
+        #[arg(short = 't', long, value_name = "TEE")]
+        tee: Option<String>,
+    },
+
+    /// Show positions, score to par and holes played
+    Leaderboard {
+        tournament_id: Uuid,
+    },
+
+    List,
+}
+
+/// Parses a `NAME:RATING:SLOPE` tee argument.
+fn parse_tee(value: &str) -> Result<TeeSet, String> {
+    let parts: Vec<&str> = value.rsplitn(3, ':').collect();
+    let [slope, rating, name] = parts[..] else {
+        return Err(format!("expected NAME:RATING:SLOPE, got '{}'", value));
+    };
+    let rating = rating.trim().parse().map_err(|_| format!("invalid course rating '{}'", rating))?;
+    let slope = slope.trim().parse().map_err(|_| format!("invalid slope rating '{}'", slope))?;
+    TeeSet::new(name, rating, slope).map_err(|e| e.to_string())
+}
+
+#[derive(Subcommand)]
+pub enum ProfileCommand {
+    /// List profiles and show which data directory is in use
+    List,
+
+    /// Add a profile, or point an existing one at another directory
+    Add {
+        name: String,
+        data_dir: PathBuf,
+        /// Use this profile when no data directory is given
+        #[arg(long)]
+        default: bool,
+    },
+
+    /// Remove a profile from the config file; its data is left in place
+    Remove {
+        name: String,
+    },
+}
@@ -0,0 +1,619 @@
+//! Rendering of scorecards for the terminal and for sharing.
+
+use std::fmt::{self, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::error::{GolfError, Result};
+use crate::models::{Round, Scorecard};
+
+/// Longest player name shown in a grid before it is truncated.
+const MAX_NAME_WIDTH: usize = 16;
+
+/// Renders a group round as a grid with one row per player and one column
+/// per hole, so everyone's scores can be compared side by side.
+///
+/// `players` pairs each display name with that player's scorecard, in the
+/// order the rows should appear. Eighteen-hole rounds get `Out` and `In`
+/// subtotals; every round gets a total and a score relative to par for
+/// completed cards. Holes without a score are shown as `-`.
+///
+/// # Examples
+///
+/// ```
+/// use golf_score_tracker::models::Round;
+/// use golf_score_tracker::ui::display::round_grid;
+/// use golf_score_tracker::utils::create_standard_course;
+/// use uuid::Uuid;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let course = create_standard_course(3);
+/// let mut round = Round::new(&course);
+/// let mut card = round.add_player(Uuid::new_v4(), &course, course.default_tee())?;
+/// card.record_score(1, 5)?;
+///
+/// let grid = round_grid(&round, &[("Ludvig".to_string(), card)]);
+/// assert!(grid.contains("Ludvig   5   -   -    5"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn round_grid(round: &Round, players: &[(String, Scorecard)]) -> String {
+    // Each row with the score to par shown after it
+    let mut rows = vec![(hole_row(round.max_holes), String::new())];
+    if let Some((_, first)) = players.first() {
+        rows.push((par_row(first), String::new()));
+    }
+    for (name, scorecard) in players {
+        let to_par = match scorecard.score_relative_to_par() {
+            Some(0) => "E".to_string(),
+            Some(relative) => format!("{:+}", relative),
+            None => String::new(),
+        };
+        rows.push((score_row(name, scorecard), to_par));
+    }
+    let label_width = rows.iter().map(|(row, _)| row.label.chars().count()).chain(std::iter::once(4)).max().unwrap_or(4);
+    let columns = Column::all(round.max_holes);
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{} - {} - Round {}", round.course, round.date.format("%Y-%m-%d"), round.id);
+    for (row, to_par) in &rows {
+        let _ = write!(out, "{:<width$}", row.label, width = label_width);
+        for (column, (text, _)) in columns.iter().zip(&row.cells) {
+            let width = if matches!(column, Column::Hole(_)) { 3 } else { 4 };
+            let _ = write!(out, " {:>width$}", text, width = width);
+        }
+        if !to_par.is_empty() {
+            let _ = write!(out, " {:>4}", to_par);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Bar characters from lowest to highest, used by [`sparkline`].
+const SPARK_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders values as a one-line bar chart scaled between their minimum
+/// and maximum.
+///
+/// # Examples
+///
+/// ```
+/// use golf_score_tracker::ui::display::sparkline;
+///
+/// assert_eq!(sparkline(&[7.0, 3.0, 0.0, 3.0]), "█▄▁▄");
+/// assert_eq!(sparkline(&[2.0, 2.0]), "▄▄");
+/// ```
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let top = (SPARK_BARS.len() - 1) as f64;
+    values
+        .iter()
+        .map(|&value| {
+            let level = if max > min { ((value - min) / (max - min) * top).round() } else { top / 2.0 };
+            SPARK_BARS[level as usize]
+        })
+        .collect()
+}
+
+/// Output formats for [`render_scorecard`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CardFormat {
+    /// Plain text grid for the terminal.
+    #[default]
+    Text,
+    /// Markdown table.
+    Markdown,
+    /// HTML table with inline styles, so it survives being pasted into an
+    /// email or newsletter.
+    Html,
+    /// The holes and totals as a JSON document.
+    Json,
+}
+
+impl CardFormat {
+    pub const ALL: [CardFormat; 4] = [CardFormat::Text, CardFormat::Markdown, CardFormat::Html, CardFormat::Json];
+
+    /// Name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            CardFormat::Text => "text",
+            CardFormat::Markdown => "markdown",
+            CardFormat::Html => "html",
+            CardFormat::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for CardFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for CardFormat {
+    type Err = GolfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized = s.trim().to_ascii_lowercase();
+        let normalized = if normalized == "md" { "markdown".to_string() } else { normalized };
+        CardFormat::ALL.into_iter().find(|format| format.name() == normalized).ok_or_else(|| {
+            GolfError::custom(format!("Unknown scorecard format '{}'. Expected text, markdown, html or json", s))
+        })
+    }
+}
+
+/// How a hole's score compares with par, shown on a printed scorecard as
+/// circles (under par) and squares (over par).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreMark {
+    /// Two or more under par, double circled.
+    Eagle,
+    /// One under par, circled.
+    Birdie,
+    Par,
+    /// One over par, boxed.
+    Bogey,
+    /// Two or more over par, double boxed.
+    DoubleBogey,
+}
+
+impl ScoreMark {
+    pub fn of(strokes: u8, par: u8) -> Self {
+        match i16::from(strokes) - i16::from(par) {
+            ..=-2 => ScoreMark::Eagle,
+            -1 => ScoreMark::Birdie,
+            0 => ScoreMark::Par,
+            1 => ScoreMark::Bogey,
+            2.. => ScoreMark::DoubleBogey,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ScoreMark::Eagle => "eagle",
+            ScoreMark::Birdie => "birdie",
+            ScoreMark::Par => "par",
+            ScoreMark::Bogey => "bogey",
+            ScoreMark::DoubleBogey => "double-bogey",
+        }
+    }
+
+    /// Surrounds `text` once or twice with the circle or square
+    /// delimiters, e.g. `(3)` and `[[7]]`.
+    fn enclose(self, text: &str, circle: (&str, &str), square: (&str, &str)) -> String {
+        match self {
+            ScoreMark::Eagle => format!("{0}{0}{1}{2}{2}", circle.0, text, circle.1),
+            ScoreMark::Birdie => format!("{}{}{}", circle.0, text, circle.1),
+            ScoreMark::Par => text.to_string(),
+            ScoreMark::Bogey => format!("{}{}{}", square.0, text, square.1),
+            ScoreMark::DoubleBogey => format!("{0}{0}{1}{2}{2}", square.0, text, square.1),
+        }
+    }
+
+    /// ANSI colour for the terminal: red under par, blue over par.
+    fn ansi(self) -> Option<&'static str> {
+        match self {
+            ScoreMark::Eagle => Some("\x1b[1;31m"),
+            ScoreMark::Birdie => Some("\x1b[31m"),
+            ScoreMark::Par => None,
+            ScoreMark::Bogey => Some("\x1b[34m"),
+            ScoreMark::DoubleBogey => Some("\x1b[1;34m"),
+        }
+    }
+
+    /// Inline CSS drawing the circle or square around a score.
+    fn css(self) -> Option<&'static str> {
+        match self {
+            ScoreMark::Eagle => Some("border:3px double;border-radius:50%;padding:0 4px"),
+            ScoreMark::Birdie => Some("border:1px solid;border-radius:50%;padding:0 4px"),
+            ScoreMark::Par => None,
+            ScoreMark::Bogey => Some("border:1px solid;padding:0 4px"),
+            ScoreMark::DoubleBogey => Some("border:3px double;padding:0 4px"),
+        }
+    }
+}
+
+const LEGEND: &str = "((n)) eagle or better, (n) birdie, [n] bogey, [[n]] double bogey or worse";
+
+/// Renders a scorecard the way it would be printed: a row each for the
+/// holes, their stroke indexes (for scorecards created from a course),
+/// par and the player's scores, with `Out` and `In` columns on eighteen
+/// hole rounds and a total. Scores are marked against par with circles
+/// and squares, and a summary of the round follows the grid.
+///
+/// Every format lays out the same grid; `Text` is the one
+/// [`scorecard_grid`] prints without colour.
+///
+/// # Examples
+///
+/// ```
+/// use golf_score_tracker::Scorecard;
+/// use golf_score_tracker::ui::display::{CardFormat, render_scorecard};
+/// use golf_score_tracker::utils::create_standard_pars;
+/// use uuid::Uuid;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut scorecard = Scorecard::from_pars(Uuid::new_v4(), 3, create_standard_pars(3))?;
+/// scorecard.record_score(1, 3)?;
+/// scorecard.record_score(2, 4)?;
+///
+/// let markdown = render_scorecard("Rose", &scorecard, CardFormat::Markdown);
+/// assert!(markdown.contains("| Rose | (3) | \\[4\\] | - | 7 |"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn render_scorecard(player: &str, scorecard: &Scorecard, format: CardFormat) -> String {
+    match format {
+        CardFormat::Text => scorecard_grid(player, scorecard, false),
+        CardFormat::Markdown => scorecard_markdown(player, scorecard),
+        CardFormat::Html => scorecard_html(player, scorecard),
+        CardFormat::Json => scorecard_json(player, scorecard),
+    }
+}
+
+/// Renders a scorecard as a text grid, colouring marked scores with ANSI
+/// escapes when `color` is set.
+pub fn scorecard_grid(player: &str, scorecard: &Scorecard, color: bool) -> String {
+    let rows = card_rows(player, scorecard);
+    let label_width = rows.iter().map(|row| row.label.chars().count()).max().unwrap_or(0);
+    let cell_width = rows
+        .iter()
+        .flat_map(|row| &row.cells)
+        .map(|(text, mark)| mark.map_or(text.len(), |mark| mark.enclose(text, ("(", ")"), ("[", "]")).len()))
+        .max()
+        .unwrap_or(1)
+        .max(3);
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", card_title(player, scorecard));
+    for row in &rows {
+        let mut line = format!("{:<width$}", row.label, width = label_width);
+        for (text, mark) in &row.cells {
+            let cell = mark.map_or_else(|| text.clone(), |mark| mark.enclose(text, ("(", ")"), ("[", "]")));
+            match mark.and_then(ScoreMark::ansi).filter(|_| color) {
+                Some(ansi) => { let _ = write!(line, " {}{:>width$}\x1b[0m", ansi, cell, width = cell_width); }
+                None => { let _ = write!(line, " {:>width$}", cell, width = cell_width); }
+            }
+        }
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+    if let Some(summary) = card_summary(scorecard) {
+        let _ = writeln!(out, "{}", summary);
+    }
+    let _ = writeln!(out, "{}", LEGEND);
+    out
+}
+
+fn scorecard_markdown(player: &str, scorecard: &Scorecard) -> String {
+    let escape = |text: &str| text.replace('|', "\\|");
+    let rows = card_rows(player, scorecard);
+    let mut out = String::new();
+    let _ = writeln!(out, "**{}**\n", escape(&card_title(player, scorecard)));
+    for (i, row) in rows.iter().enumerate() {
+        let _ = write!(out, "| {} |", escape(&row.label));
+        for (text, mark) in &row.cells {
+            let cell = mark.map_or_else(|| text.clone(), |mark| mark.enclose(text, ("(", ")"), ("\\[", "\\]")));
+            let _ = write!(out, " {} |", cell);
+        }
+        out.push('\n');
+        if i == 0 {
+            let _ = writeln!(out, "|:---|{}", "---:|".repeat(row.cells.len()));
+        }
+    }
+    if let Some(summary) = card_summary(scorecard) {
+        let _ = writeln!(out, "\n{}", summary);
+    }
+    let _ = writeln!(out, "\n_{}_", LEGEND.replace('[', "\\[").replace(']', "\\]"));
+    out
+}
+
+fn scorecard_html(player: &str, scorecard: &Scorecard) -> String {
+    let rows = card_rows(player, scorecard);
+    let mut out = String::new();
+    let _ = writeln!(out, "<table class=\"scorecard\" style=\"border-collapse:collapse;text-align:center\">");
+    let _ = writeln!(out, "  <caption>{}</caption>", escape_html(&card_title(player, scorecard)));
+    for (i, row) in rows.iter().enumerate() {
+        let tag = if i == 0 { "th" } else { "td" };
+        let _ = write!(out, "  <tr><th style=\"text-align:left\">{}</th>", escape_html(&row.label));
+        for (text, mark) in &row.cells {
+            match mark.and_then(|mark| mark.css().map(|css| (mark, css))) {
+                Some((mark, css)) => {
+                    let _ = write!(out, "<{0}><span class=\"{1}\" style=\"{2}\">{3}</span></{0}>", tag, mark.name(), css, text);
+                }
+                None => { let _ = write!(out, "<{0}>{1}</{0}>", tag, text); }
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+    if let Some(summary) = card_summary(scorecard) {
+        let _ = writeln!(out, "<p>{}</p>", escape_html(&summary));
+    }
+    out
+}
+
+#[derive(Serialize)]
+struct CardJson<'a> {
+    round_id: String,
+    player: &'a str,
+    course: Option<&'a str>,
+    tee: Option<&'a str>,
+    played_at: String,
+    holes: Vec<HoleJson>,
+    #[serde(rename = "out")]
+    out_strokes: Option<u16>,
+    #[serde(rename = "in")]
+    in_strokes: Option<u16>,
+    total_strokes: Option<u16>,
+    par: u16,
+    to_par: Option<i16>,
+    net_total: Option<i16>,
+}
+
+#[derive(Serialize)]
+struct HoleJson {
+    hole: u8,
+    par: u8,
+    stroke_index: Option<u8>,
+    strokes: Option<u8>,
+    mark: Option<&'static str>,
+}
+
+fn scorecard_json(player: &str, scorecard: &Scorecard) -> String {
+    let sum = |holes: RangeInclusive<u8>| {
+        let strokes: Vec<u16> = holes.filter_map(|hole| scorecard.get_score(hole)).map(u16::from).collect();
+        (!strokes.is_empty()).then(|| strokes.iter().sum())
+    };
+    let split = scorecard.max_holes == 18;
+    let card = CardJson {
+        round_id: scorecard.round_id.to_string(),
+        player,
+        course: scorecard.course.as_deref(),
+        tee: scorecard.tee.as_deref(),
+        played_at: scorecard.played_at.to_rfc3339(),
+        holes: (1..=scorecard.max_holes)
+            .map(|hole| {
+                let par = scorecard.get_par(hole).unwrap_or(0);
+                let strokes = scorecard.get_score(hole);
+                HoleJson {
+                    hole,
+                    par,
+                    stroke_index: scorecard.stroke_index(hole),
+                    strokes,
+                    mark: strokes.map(|strokes| ScoreMark::of(strokes, par).name()),
+                }
+            })
+            .collect(),
+        out_strokes: if split { sum(1..=9) } else { None },
+        in_strokes: if split { sum(10..=18) } else { None },
+        total_strokes: sum(1..=scorecard.max_holes),
+        par: scorecard.total_par(),
+        to_par: scorecard.score_relative_to_par(),
+        net_total: scorecard.net_total().filter(|_| scorecard.course_handicap.is_some()),
+    };
+    let mut json = serde_json::to_string_pretty(&card).expect("scorecard JSON has only string keys");
+    json.push('\n');
+    json
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// A column of a printed scorecard.
+#[derive(Debug, Clone, Copy)]
+enum Column {
+    Hole(u8),
+    Out,
+    In,
+    Total,
+}
+
+impl Column {
+    /// Columns in printed order, with `Out` and `In` only on eighteen
+    /// hole rounds.
+    fn all(max_holes: u8) -> Vec<Column> {
+        let mut columns: Vec<Column> = (1..=max_holes).map(Column::Hole).collect();
+        if max_holes == 18 {
+            columns.insert(9, Column::Out);
+            columns.push(Column::In);
+        }
+        columns.push(Column::Total);
+        columns
+    }
+
+    /// Holes added up in this column.
+    fn holes(self, max_holes: u8) -> RangeInclusive<u8> {
+        match self {
+            Column::Hole(hole) => hole..=hole,
+            Column::Out => 1..=9,
+            Column::In => 10..=18,
+            Column::Total => 1..=max_holes,
+        }
+    }
+}
+
+/// A labelled row of a printed scorecard, with the mark of each score.
+pub(crate) struct CardRow {
+    pub(crate) label: String,
+    pub(crate) cells: Vec<(String, Option<ScoreMark>)>,
+}
+
+/// The rows of a printed scorecard: holes, stroke indexes when the card
+/// has them, par and the player's scores.
+pub(crate) fn card_rows(player: &str, scorecard: &Scorecard) -> Vec<CardRow> {
+    let mut rows = vec![hole_row(scorecard.max_holes)];
+    if !scorecard.stroke_indexes().is_empty() {
+        rows.push(CardRow::new("Hcp", scorecard.max_holes, |column| match column {
+            Column::Hole(hole) => (scorecard.stroke_index(hole).map_or_else(String::new, |index| index.to_string()), None),
+            _ => (String::new(), None),
+        }));
+    }
+    rows.push(par_row(scorecard));
+    rows.push(score_row(player, scorecard));
+    rows
+}
+
+impl CardRow {
+    fn new(label: &str, max_holes: u8, cell: impl Fn(Column) -> (String, Option<ScoreMark>)) -> Self {
+        Self { label: label.to_string(), cells: Column::all(max_holes).into_iter().map(cell).collect() }
+    }
+}
+
+fn hole_row(max_holes: u8) -> CardRow {
+    CardRow::new("Hole", max_holes, |column| {
+        let label = match column {
+            Column::Hole(hole) => hole.to_string(),
+            Column::Out => "Out".to_string(),
+            Column::In => "In".to_string(),
+            Column::Total => "Tot".to_string(),
+        };
+        (label, None)
+    })
+}
+
+fn par_row(scorecard: &Scorecard) -> CardRow {
+    CardRow::new("Par", scorecard.max_holes, |column| (column_sum(scorecard, column, |hole| scorecard.get_par(hole)), None))
+}
+
+/// A player's scores, marked against par, with the name truncated to
+/// [`MAX_NAME_WIDTH`].
+fn score_row(player: &str, scorecard: &Scorecard) -> CardRow {
+    let name: String = player.chars().take(MAX_NAME_WIDTH).collect();
+    CardRow::new(&name, scorecard.max_holes, |column| match column {
+        Column::Hole(hole) => match (scorecard.get_score(hole), scorecard.get_par(hole)) {
+            (Some(strokes), Some(par)) => (strokes.to_string(), Some(ScoreMark::of(strokes, par))),
+            _ => ("-".to_string(), None),
+        },
+        _ => (column_sum(scorecard, column, |hole| scorecard.get_score(hole)), None),
+    })
+}
+
+/// Sum of `value` over the holes of `column`, or `-` when no hole in it
+/// has a value yet.
+fn column_sum(scorecard: &Scorecard, column: Column, value: impl Fn(u8) -> Option<u8>) -> String {
+    let values: Vec<u16> = column.holes(scorecard.max_holes).filter_map(value).map(u16::from).collect();
+    if values.is_empty() { "-".to_string() } else { values.iter().sum::<u16>().to_string() }
+}
+
+/// Player, course, tees and date of a scorecard.
+pub(crate) fn card_title(player: &str, scorecard: &Scorecard) -> String {
+    let mut title = player.to_string();
+    if let Some(course) = &scorecard.course {
+        let _ = write!(title, " - {}", course);
+        if let Some(tee) = &scorecard.tee {
+            let _ = write!(title, " ({} tees)", tee);
+        }
+    }
+    let _ = write!(title, " - {}", scorecard.played_at.format("%Y-%m-%d"));
+    title
+}
+
+/// Gross and, with a course handicap, net score of a complete scorecard,
+/// or the holes played so far.
+pub(crate) fn card_summary(scorecard: &Scorecard) -> Option<String> {
+    let signed = |relative: i16| if relative == 0 { "E".to_string() } else { format!("{:+}", relative) };
+    match (scorecard.total_strokes(), scorecard.score_relative_to_par()) {
+        (Some(total), Some(relative)) => {
+            let mut summary = format!("Total {} ({})", total, signed(relative));
+            if scorecard.course_handicap.is_some()
+                && let (Some(net), Some(net_relative)) = (scorecard.net_total(), scorecard.net_relative_to_par())
+            {
+                let _ = write!(summary, ", net {} ({})", net, signed(net_relative));
+            }
+            Some(summary)
+        }
+        _ if scorecard.scores().is_empty() => None,
+        _ => {
+            let strokes: i16 = scorecard.scores().values().map(|&strokes| i16::from(strokes)).sum();
+            let par: i16 = scorecard.scores().keys().filter_map(|&hole| scorecard.get_par(hole)).map(i16::from).sum();
+            Some(format!(
+                "{} of {} holes played: {} strokes ({})",
+                scorecard.scores().len(),
+                scorecard.max_holes,
+                strokes,
+                signed(strokes - par)
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_standard_course;
+    use uuid::Uuid;
+
+    #[test]
+    fn eighteen_hole_grid_has_nine_hole_subtotals() {
+        let course = create_standard_course(18);
+        let mut round = Round::new(&course);
+        let mut card = round.add_player(Uuid::new_v4(), &course, course.default_tee()).expect("add");
+        for hole in 1..=18 {
+            card.record_score(hole, card.get_par(hole).unwrap()).expect("record");
+        }
+
+        let grid = round_grid(&round, &[("Tommy".to_string(), card)]);
+        let lines: Vec<&str> = grid.lines().collect();
+
+        assert!(lines[1].starts_with("Hole    1   2"), "{}", grid);
+        assert!(lines[1].contains("   9  Out  10"), "{}", grid);
+        assert!(lines[1].ends_with("18   In  Tot"), "{}", grid);
+        assert!(lines[2].ends_with("   5   36   72"), "{}", grid);
+        assert!(lines[3].ends_with("   5   36   72    E"), "{}", grid);
+    }
+
+    #[test]
+    fn long_names_are_truncated() {
+        let course = create_standard_course(3);
+        let mut round = Round::new(&course);
+        let card = round.add_player(Uuid::new_v4(), &course, course.default_tee()).expect("add");
+
+        let grid = round_grid(&round, &[("Christiaan Bezuidenhout".to_string(), card)]);
+
+        assert!(grid.lines().nth(3).unwrap().starts_with("Christiaan Bezui   -"), "{}", grid);
+    }
+
+    #[test]
+    fn printed_scorecard_marks_scores_against_par() {
+        let course = create_standard_course(18);
+        let mut card = Scorecard::new(Uuid::new_v4(), &course, course.default_tee()).expect("scorecard");
+        for hole in 1..=18 {
+            let par = card.get_par(hole).unwrap();
+            let strokes = match hole {
+                1 => par - 2,
+                2 => par - 1,
+                3 => par + 1,
+                4 => par + 3,
+                _ => par,
+            };
+            card.record_score(hole, strokes).expect("record");
+        }
+
+        let grid = scorecard_grid("Scottie", &card, false);
+        let lines: Vec<&str> = grid.lines().collect();
+        assert!(lines[1].starts_with("Hole        1     2     3     4"), "{}", grid);
+        assert!(lines[4].starts_with("Scottie ((2))   (2)   [6] [[7]]     3"), "{}", grid);
+        assert!(lines[4].contains("     5    37     4") && lines[4].ends_with("    36    73"), "{}", grid);
+        assert!(grid.contains("Total 73 (+1)"), "{}", grid);
+
+        let colored = scorecard_grid("Scottie", &card, true);
+        assert!(colored.contains("\x1b[31m  (2)\x1b[0m"), "{}", colored);
+    }
+
+    #[test]
+    fn html_scorecard_escapes_names_and_styles_marks() {
+        let mut card = Scorecard::from_pars(Uuid::new_v4(), 3, crate::utils::create_standard_pars(3)).expect("scorecard");
+        card.record_score(1, 3).expect("record");
+
+        let html = render_scorecard("<Min Woo>", &card, CardFormat::Html);
+
+        assert!(html.contains("<caption>&lt;Min Woo&gt; - "), "{}", html);
+        assert!(html.contains("<td><span class=\"birdie\" style=\"border:1px solid;border-radius:50%"), "{}", html);
+        assert!(html.contains("<p>1 of 3 holes played: 3 strokes (-1)</p>"), "{}", html);
+    }
+}
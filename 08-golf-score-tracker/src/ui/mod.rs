@@ -1,3 +1,7 @@
 pub mod cli;
+pub mod display;
+pub mod play;
+pub mod server;
+pub mod tui;
 
-pub use cli::{Cli, Commands};
\ No newline at end of file
+pub use cli::{Backend, Cli, Commands, ProfileCommand, TournamentCommand};
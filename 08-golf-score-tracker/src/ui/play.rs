@@ -0,0 +1,259 @@
+//! Interactive hole-by-hole entry of a round.
+//!
+//! [`play_round`] prompts for each hole in order, showing its par and the
+//! score to par so far. Each answer is either a number of strokes or one of
+//! the shorthands below; invalid scores are reported with the
+//! [`GolfError`](crate::GolfError) message and the hole is asked again.
+//!
+//! | Input      | Meaning                                   |
+//! |------------|-------------------------------------------|
+//! | `4`        | four strokes                              |
+//! | `p`        | par                                       |
+//! | `b`        | bogey                                     |
+//! | `d`        | double bogey                              |
+//! | `+3`, `-1` | strokes relative to par                   |
+//! | `u`        | undo the last change and go back to it    |
+//! | `s`        | skip the hole and come back to it later   |
+//! | `q`        | stop; scores entered so far are kept      |
+//!
+//! The scorecard is saved after every hole, so stopping part way through,
+//! or losing the terminal, never loses more than the hole being entered.
+//! Playing the same scorecard again resumes at the first hole without a
+//! score.
+
+use std::io::{BufRead, Write};
+
+use crate::error::Result;
+use crate::models::Scorecard;
+use crate::storage::Repository;
+
+/// One answer at the hole prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Entry {
+    Strokes(u8),
+    /// Strokes relative to par.
+    ToPar(i8),
+    Undo,
+    Skip,
+    Quit,
+    Help,
+}
+
+impl Entry {
+    fn parse(input: &str) -> Option<Self> {
+        let input = input.trim().to_ascii_lowercase();
+        let entry = match input.as_str() {
+            "p" | "par" => Entry::ToPar(0),
+            "b" | "bogey" => Entry::ToPar(1),
+            "d" | "double" => Entry::ToPar(2),
+            "u" | "undo" => Entry::Undo,
+            "s" | "skip" => Entry::Skip,
+            "q" | "quit" => Entry::Quit,
+            "?" | "h" | "help" => Entry::Help,
+            relative if relative.starts_with(['+', '-']) => Entry::ToPar(relative.parse().ok()?),
+            strokes => Entry::Strokes(strokes.parse().ok()?),
+        };
+        Some(entry)
+    }
+}
+
+const HELP: &str = "Enter strokes (e.g. 4), p for par, b for bogey, d for double bogey, +N/-N relative to par, \
+                    u to undo, s to skip the hole or q to stop";
+
+/// Walks through the unscored holes of `scorecard`, recording each answer
+/// read from `input` and saving the scorecard to `repo` after every change.
+///
+/// Prompts and messages go to `output`. Reaching the end of `input` is the
+/// same as quitting. Returns the scorecard as it was last saved.
+///
+/// # Examples
+///
+/// ```
+/// use golf_score_tracker::{Repository, Scorecard};
+/// use golf_score_tracker::storage::InMemoryRepository;
+/// use golf_score_tracker::ui::play::play_round;
+/// use golf_score_tracker::utils::create_standard_pars;
+/// use uuid::Uuid;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut repo = InMemoryRepository::new();
+/// let scorecard = Scorecard::from_pars(Uuid::new_v4(), 3, create_standard_pars(3))?;
+///
+/// let mut output = Vec::new();
+/// let played = play_round(&mut repo, scorecard, "5\np\nb\n".as_bytes(), &mut output)?;
+///
+/// assert_eq!(played.total_strokes(), Some(14));
+/// assert_eq!(repo.get_scorecard(&played.round_id)?, Some(played));
+/// # Ok(())
+/// # }
+/// ```
+pub fn play_round<R: BufRead, W: Write>(
+    repo: &mut dyn Repository,
+    mut scorecard: Scorecard,
+    mut input: R,
+    mut output: W,
+) -> Result<Scorecard> {
+    if scorecard.is_complete() {
+        writeln!(output, "Round {} is already complete. Use fix-score to change a hole.", scorecard.round_id)?;
+        return Ok(scorecard);
+    }
+    writeln!(output, "{}", HELP)?;
+
+    let mut hole = first_unscored(&scorecard, 1);
+    let mut line = String::new();
+    while let Some(current) = hole {
+        let par = scorecard.get_par(current).expect("par must exist for each hole");
+        let index = scorecard.stroke_index(current).map_or_else(String::new, |index| format!(", SI {}", index));
+        write!(output, "Hole {} (par {}{}) | {} > ", current, par, index, running_score(&scorecard))?;
+        output.flush()?;
+
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            break;
+        }
+
+        let strokes = match Entry::parse(&line) {
+            Some(Entry::Strokes(strokes)) => strokes,
+            Some(Entry::ToPar(relative)) => match u8::try_from(i16::from(par) + i16::from(relative)) {
+                Ok(strokes) => strokes,
+                Err(_) => {
+                    writeln!(output, "  {:+} is not a score on a par {}", relative, par)?;
+                    continue;
+                }
+            },
+            Some(Entry::Undo) => {
+                match scorecard.undo(None) {
+                    Ok(event) => {
+                        repo.save_scorecard(&scorecard)?;
+                        writeln!(output, "  Undid hole {}", event.hole)?;
+                        hole = Some(event.hole);
+                    }
+                    Err(error) => writeln!(output, "  {}", error)?,
+                }
+                continue;
+            }
+            Some(Entry::Skip) => {
+                hole = next_unscored(&scorecard, current);
+                continue;
+            }
+            Some(Entry::Quit) => break,
+            Some(Entry::Help) | None => {
+                writeln!(output, "  {}", HELP)?;
+                continue;
+            }
+        };
+
+        match scorecard.record_score(current, strokes) {
+            Ok(()) => {
+                repo.save_scorecard(&scorecard)?;
+                hole = next_unscored(&scorecard, current);
+            }
+            Err(error) => writeln!(output, "  {}", error)?,
+        }
+    }
+
+    match (scorecard.total_strokes(), scorecard.score_relative_to_par()) {
+        (Some(total), Some(relative)) => {
+            writeln!(output, "Round complete: {} strokes ({})", total, signed(relative.into()))?;
+        }
+        _ => {
+            let missing: Vec<String> = (1..=scorecard.max_holes)
+                .filter(|&hole| scorecard.get_score(hole).is_none())
+                .map(|hole| hole.to_string())
+                .collect();
+            writeln!(output, "Saved. Holes still to enter: {}", missing.join(", "))?;
+        }
+    }
+    Ok(scorecard)
+}
+
+/// First hole from `from` onwards without a score.
+fn first_unscored(scorecard: &Scorecard, from: u8) -> Option<u8> {
+    (from..=scorecard.max_holes).find(|&hole| scorecard.get_score(hole).is_none())
+}
+
+/// The unscored hole to ask for after `current`, going back round to
+/// skipped holes after the last one. `None` when no other hole is left.
+fn next_unscored(scorecard: &Scorecard, current: u8) -> Option<u8> {
+    first_unscored(scorecard, current + 1)
+        .or_else(|| first_unscored(scorecard, 1))
+        .filter(|&hole| hole != current)
+}
+
+/// Score to par over the holes entered so far, e.g. `+2 thru 5`.
+fn running_score(scorecard: &Scorecard) -> String {
+    let to_par: i32 = scorecard
+        .scores()
+        .iter()
+        .map(|(&hole, &strokes)| i32::from(strokes) - i32::from(scorecard.get_par(hole).unwrap_or(strokes)))
+        .sum();
+    format!("{} thru {}", signed(to_par), scorecard.scores().len())
+}
+
+fn signed(to_par: i32) -> String {
+    if to_par == 0 { "E".to_string() } else { format!("{:+}", to_par) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryRepository;
+    use crate::utils::create_standard_pars;
+    use uuid::Uuid;
+
+    fn play(repo: &mut InMemoryRepository, scorecard: Scorecard, input: &str) -> (Scorecard, String) {
+        let mut output = Vec::new();
+        let played = play_round(repo, scorecard, input.as_bytes(), &mut output).expect("play");
+        (played, String::from_utf8(output).expect("utf-8"))
+    }
+
+    #[test]
+    fn invalid_scores_are_reported_and_asked_again() {
+        let mut repo = InMemoryRepository::new();
+        let scorecard = Scorecard::from_pars(Uuid::new_v4(), 3, create_standard_pars(3)).expect("scorecard");
+
+        let (played, output) = play(&mut repo, scorecard, "0\neagle\n-1\nd\n+11\n");
+
+        assert!(output.contains("Invalid score 0 for hole 1 (par 4)"), "{}", output);
+        assert!(output.contains("Hole 1 (par 4) | E thru 0 > "), "{}", output);
+        assert!(output.contains("Hole 2 (par 3) | -1 thru 1 > "), "{}", output);
+        assert!(output.contains("Invalid score 16 for hole 3"), "{}", output);
+        assert_eq!(played.scores().values().copied().collect::<Vec<_>>(), vec![3, 5]);
+        assert!(output.ends_with("Holes still to enter: 3\n"), "{}", output);
+    }
+
+    #[test]
+    fn undo_and_skip_move_between_holes_and_every_hole_is_saved() {
+        let mut repo = InMemoryRepository::new();
+        let scorecard = Scorecard::from_pars(Uuid::new_v4(), 3, create_standard_pars(3)).expect("scorecard");
+
+        let (played, output) = play(&mut repo, scorecard, "s\n4\nu\n3\nq\n");
+
+        assert!(output.contains("Undid hole 2"), "{}", output);
+        assert_eq!(played.get_score(1), None);
+        assert_eq!(played.get_score(2), Some(3));
+        assert_eq!(repo.get_scorecard(&played.round_id).expect("get"), Some(played.clone()));
+
+        // Playing again resumes at the first hole still to enter
+        let (played, output) = play(&mut repo, played, "p\np\n");
+        assert!(output.contains("Hole 1 (par 4) | E thru 1 > "), "{}", output);
+        assert!(output.ends_with("Round complete: 12 strokes (E)\n"), "{}", output);
+        assert!(played.is_complete());
+    }
+
+    #[test]
+    fn skipped_holes_are_offered_again_after_the_last_hole() {
+        let mut repo = InMemoryRepository::new();
+        let scorecard = Scorecard::from_pars(Uuid::new_v4(), 3, create_standard_pars(3)).expect("scorecard");
+
+        let (played, output) = play(&mut repo, scorecard, "s\np\np\np\n");
+        assert!(output.contains("Hole 1 (par 4) | E thru 2 > "), "{}", output);
+        assert!(played.is_complete());
+
+        // Skipping the only hole left ends the session
+        let scorecard = Scorecard::from_pars(Uuid::new_v4(), 3, create_standard_pars(3)).expect("scorecard");
+        let (_, output) = play(&mut repo, scorecard, "p\np\ns\n");
+        assert!(output.ends_with("Holes still to enter: 3\n"), "{}", output);
+    }
+}
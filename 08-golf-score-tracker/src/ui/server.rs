@@ -0,0 +1,567 @@
+//! JSON REST API over a [`Repository`], for clients that want to read and
+//! write rounds without going through the CLI.
+//!
+//! | Method   | Path                                   | Action                          |
+//! |----------|----------------------------------------|---------------------------------|
+//! | `GET`    | `/players`                             | list players                    |
+//! | `POST`   | `/players`                             | add a player                    |
+//! | `GET`    | `/players/{player}`                    | get a player                    |
+//! | `PATCH`  | `/players/{player}`                    | rename or change the handicap   |
+//! | `DELETE` | `/players/{player}?cascade=true`       | delete a player                 |
+//! | `GET`    | `/players/{player}/statistics`         | the player's statistics         |
+//! | `GET`    | `/scorecards?player=...`               | list scorecards                 |
+//! | `POST`   | `/scorecards`                          | start a scorecard               |
+//! | `GET`    | `/scorecards/{round_id}`               | get a scorecard                 |
+//! | `DELETE` | `/scorecards/{round_id}`               | delete a scorecard              |
+//! | `PUT`    | `/scorecards/{round_id}/holes/{hole}`  | record the score on a hole      |
+//! | `GET`    | `/courses`, `/courses/{name}`          | the course catalog              |
+//! | `GET`    | `/openapi.json`                        | the OpenAPI description         |
+//!
+//! `{player}` is a name, ID or unique ID prefix, as on the command line.
+//! Errors are returned as `{"error": "..."}` with a status from
+//! [`status_for`]: 404 for anything not found and 422 for invalid scores.
+//!
+//! [`Api`] turns a method, URL and body into a [`Response`] without any
+//! networking, so the routes are tested directly; [`serve`] feeds it the
+//! requests arriving at a `tiny_http` server one at a time.
+
+use std::io::Read;
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Value, json};
+use tiny_http::{Header, Method, Server};
+use uuid::Uuid;
+
+use crate::error::{GolfError, Result};
+use crate::models::{Fairway, HoleResult, Player, PlayerStatistics, Scorecard};
+use crate::storage::{CourseCatalog, CourseSource, DeleteMode, Repository};
+use crate::utils::create_standard_course;
+
+/// The OpenAPI 3 description of the routes, served at `/openapi.json`.
+pub const OPENAPI: &str = include_str!("openapi.json");
+
+/// Largest request body accepted, far more than any request needs.
+const MAX_BODY: u64 = 64 * 1024;
+
+/// Status and JSON body of a handled request. `body` is `None` for 204.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Option<Value>,
+}
+
+/// HTTP status for an error from the library.
+///
+/// | Error                                                      | Status |
+/// |------------------------------------------------------------|--------|
+/// | [`is_not_found`](GolfError::is_not_found)                  | 404    |
+/// | [`is_validation_error`](GolfError::is_validation_error)    | 422    |
+/// | ambiguous player, complete scorecard, player still in use  | 409    |
+/// | other input errors, e.g. an empty player name              | 400    |
+/// | storage failures                                           | 500    |
+pub fn status_for(error: &GolfError) -> u16 {
+    match error {
+        error if error.is_not_found() => 404,
+        error if error.is_validation_error() => 422,
+        GolfError::AmbiguousPlayer { .. }
+        | GolfError::ScorecardComplete(_)
+        | GolfError::PlayerInUse { .. }
+        | GolfError::DuplicateRecords(_) => 409,
+        GolfError::Custom(_) => 400,
+        _ => 500,
+    }
+}
+
+/// A request that could not be handled.
+#[derive(Debug)]
+struct Failure {
+    status: u16,
+    message: String,
+}
+
+impl Failure {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+}
+
+impl From<GolfError> for Failure {
+    fn from(error: GolfError) -> Self {
+        Self::new(status_for(&error), error.to_string())
+    }
+}
+
+type Handled = std::result::Result<Response, Failure>;
+
+fn ok(status: u16, body: impl Serialize) -> Handled {
+    let body = serde_json::to_value(body).map_err(GolfError::from)?;
+    Ok(Response { status, body: Some(body) })
+}
+
+/// A scorecard as returned by the API: the stored fields plus its totals.
+#[derive(Serialize)]
+struct ScorecardView<'a> {
+    #[serde(flatten)]
+    scorecard: &'a Scorecard,
+    complete: bool,
+    total_strokes: Option<u16>,
+    to_par: Option<i16>,
+}
+
+impl<'a> ScorecardView<'a> {
+    fn new(scorecard: &'a Scorecard) -> Self {
+        Self {
+            scorecard,
+            complete: scorecard.is_complete(),
+            total_strokes: scorecard.total_strokes(),
+            to_par: scorecard.score_relative_to_par(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewPlayer {
+    name: String,
+    #[serde(default)]
+    handicap: Option<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlayerChanges {
+    #[serde(default)]
+    name: Option<String>,
+    /// Absent leaves the handicap alone; `null` clears it.
+    #[serde(default, deserialize_with = "present")]
+    handicap: Option<Option<f64>>,
+}
+
+/// Tells a field set to `null` apart from a missing one.
+fn present<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<Option<f64>>, D::Error> {
+    Option::deserialize(deserializer).map(Some)
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewScorecard {
+    player: String,
+    #[serde(default)]
+    course: Option<String>,
+    #[serde(default)]
+    tee: Option<String>,
+    #[serde(default = "default_holes")]
+    holes: u8,
+}
+
+fn default_holes() -> u8 {
+    18
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HoleScore {
+    strokes: u8,
+    #[serde(default)]
+    putts: Option<u8>,
+    #[serde(default)]
+    fairway: Option<Fairway>,
+    #[serde(default)]
+    sand_save: Option<bool>,
+    #[serde(default)]
+    penalties: u8,
+}
+
+/// Routes requests to a repository and the course catalog in `data_dir`.
+pub struct Api<'a> {
+    repo: &'a mut dyn Repository,
+    data_dir: PathBuf,
+}
+
+impl<'a> Api<'a> {
+    pub fn new(repo: &'a mut dyn Repository, data_dir: impl Into<PathBuf>) -> Self {
+        Self { repo, data_dir: data_dir.into() }
+    }
+
+    /// Handles one request. `url` is the path with an optional query
+    /// string, as sent on the request line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use golf_score_tracker::storage::InMemoryRepository;
+    /// use golf_score_tracker::ui::server::Api;
+    ///
+    /// let mut repo = InMemoryRepository::new();
+    /// let mut api = Api::new(&mut repo, "golf_data");
+    ///
+    /// let created = api.handle("POST", "/players", r#"{"name": "Nelly Korda", "handicap": 0.4}"#);
+    /// assert_eq!(created.status, 201);
+    ///
+    /// let player = api.handle("GET", "/players/Nelly%20Korda", "");
+    /// assert_eq!(player.body.unwrap()["handicap"], 0.4);
+    /// assert_eq!(api.handle("GET", "/players/Lydia", "").status, 404);
+    /// ```
+    pub fn handle(&mut self, method: &str, url: &str, body: &str) -> Response {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let segments: Vec<String> = path.split('/').filter(|segment| !segment.is_empty()).map(|segment| decode(segment, false)).collect();
+        let query: Vec<(String, String)> = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode(key, true), decode(value, true))
+            })
+            .collect();
+        let query = Query(query);
+
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        self.route(method, &segments, &query, body).unwrap_or_else(|failure| Response {
+            status: failure.status,
+            body: Some(json!({ "error": failure.message })),
+        })
+    }
+
+    fn route(&mut self, method: &str, segments: &[&str], query: &Query, body: &str) -> Handled {
+        match segments {
+            ["openapi.json"] => match method {
+                "GET" => ok(200, serde_json::from_str::<Value>(OPENAPI).expect("embedded OpenAPI document is valid JSON")),
+                _ => not_allowed("GET"),
+            },
+            ["players"] => match method {
+                "GET" => ok(200, self.repo.list_players()?),
+                "POST" => {
+                    let new: NewPlayer = parse(body)?;
+                    let player = Player::new(new.name, new.handicap)?;
+                    self.repo.save_player(&player)?;
+                    ok(201, player)
+                }
+                _ => not_allowed("GET, POST"),
+            },
+            ["players", player] => match method {
+                "GET" => ok(200, self.repo.find_player(player)?),
+                "PATCH" => {
+                    let player = self.repo.find_player(player)?;
+                    let changes: PlayerChanges = parse(body)?;
+                    let handicap = changes.handicap.unwrap_or(player.handicap);
+                    let edited = Player::new(changes.name.as_deref().unwrap_or(&player.name), handicap)?;
+                    let edited = Player { id: player.id, ..edited };
+                    self.repo.update_player(&edited)?;
+                    ok(200, edited)
+                }
+                "DELETE" => {
+                    let player = self.repo.find_player(player)?;
+                    let mode = if query.flag("cascade")? { DeleteMode::Cascade } else { DeleteMode::Refuse };
+                    let deleted = self.repo.delete_player(&player.id, mode)?;
+                    ok(200, json!({ "deleted_scorecards": deleted }))
+                }
+                _ => not_allowed("GET, PATCH, DELETE"),
+            },
+            ["players", player, "statistics"] => match method {
+                "GET" => {
+                    let player = self.repo.find_player(player)?;
+                    let scorecards = self.repo.get_scorecards_by_player(&player.id)?;
+                    ok(200, PlayerStatistics::from_scorecards(&scorecards))
+                }
+                _ => not_allowed("GET"),
+            },
+            ["scorecards"] => match method {
+                "GET" => {
+                    let scorecards = match query.get("player") {
+                        Some(player) => self.repo.get_scorecards_by_player(&self.repo.find_player(player)?.id)?,
+                        None => self.repo.list_scorecards()?,
+                    };
+                    ok(200, scorecards.iter().map(ScorecardView::new).collect::<Vec<_>>())
+                }
+                "POST" => {
+                    let new: NewScorecard = parse(body)?;
+                    let scorecard = self.create_scorecard(new)?;
+                    ok(201, ScorecardView::new(&scorecard))
+                }
+                _ => not_allowed("GET, POST"),
+            },
+            ["scorecards", round_id] => match method {
+                "GET" => ok(200, ScorecardView::new(&self.scorecard(round_id)?)),
+                "DELETE" => {
+                    let deleted = self.repo.delete_scorecard(&round(round_id)?)?;
+                    ok(200, ScorecardView::new(&deleted))
+                }
+                _ => not_allowed("GET, DELETE"),
+            },
+            ["scorecards", round_id, "holes", hole] => match method {
+                "PUT" => {
+                    let mut scorecard = self.scorecard(round_id)?;
+                    let hole: u8 = hole.parse().map_err(|_| Failure::new(404, format!("No hole '{}'", hole)))?;
+                    let score: HoleScore = parse(body)?;
+                    let result = HoleResult {
+                        putts: score.putts,
+                        fairway: score.fairway,
+                        sand_save: score.sand_save,
+                        penalties: score.penalties,
+                    };
+                    scorecard.record_hole(hole, score.strokes, result)?;
+                    self.repo.save_scorecard(&scorecard)?;
+                    ok(200, ScorecardView::new(&scorecard))
+                }
+                _ => not_allowed("PUT"),
+            },
+            ["courses"] => match method {
+                "GET" => {
+                    let catalog = CourseCatalog::load(&self.data_dir)?;
+                    let courses: Vec<Value> = catalog.entries().map(course_json).collect();
+                    ok(200, courses)
+                }
+                _ => not_allowed("GET"),
+            },
+            ["courses", name] => match method {
+                "GET" => {
+                    let catalog = CourseCatalog::load(&self.data_dir)?;
+                    ok(200, course_json(catalog.find(name)?))
+                }
+                _ => not_allowed("GET"),
+            },
+            _ => Err(Failure::new(404, format!("No route for /{}", segments.join("/")))),
+        }
+    }
+
+    fn scorecard(&self, round_id: &str) -> std::result::Result<Scorecard, Failure> {
+        let round_id = round(round_id)?;
+        Ok(self.repo.get_scorecard(&round_id)?.ok_or(GolfError::RoundNotFound(round_id))?)
+    }
+
+    /// Same as `create-scorecard`, without the rating override.
+    fn create_scorecard(&mut self, new: NewScorecard) -> Result<Scorecard> {
+        let player = self.repo.find_player(&new.player)?;
+        let course = match new.course.as_deref() {
+            Some(name) => CourseCatalog::load(&self.data_dir)?.find(name)?.course.clone(),
+            None => create_standard_course(new.holes),
+        };
+        let tee = match new.tee.as_deref() {
+            Some(name) => course.tee(name)?,
+            None => course.default_tee(),
+        };
+        let mut scorecard = Scorecard::new(player.id, &course, tee)?;
+        if let Some(index) = player.handicap {
+            scorecard = scorecard.with_handicap_index(index);
+        }
+        self.repo.save_scorecard(&scorecard)?;
+        Ok(scorecard)
+    }
+}
+
+/// Decoded query string parameters.
+struct Query(Vec<(String, String)>);
+
+impl Query {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str())
+    }
+
+    /// A boolean parameter; present without a value counts as true.
+    fn flag(&self, key: &str) -> std::result::Result<bool, Failure> {
+        match self.get(key) {
+            None | Some("false") | Some("0") => Ok(false),
+            Some("") | Some("true") | Some("1") => Ok(true),
+            Some(other) => Err(Failure::new(400, format!("{} must be true or false, not '{}'", key, other))),
+        }
+    }
+}
+
+fn not_allowed(allowed: &str) -> Handled {
+    Err(Failure::new(405, format!("Method not allowed; use {}", allowed)))
+}
+
+fn parse<T: DeserializeOwned>(body: &str) -> std::result::Result<T, Failure> {
+    serde_json::from_str(body).map_err(|error| Failure::new(400, format!("Invalid request body: {}", error)))
+}
+
+/// A round ID from the path. Anything that is not a UUID names no round.
+fn round(round_id: &str) -> std::result::Result<Uuid, Failure> {
+    round_id.parse().map_err(|_| Failure::new(404, format!("Round with ID {} not found", round_id)))
+}
+
+fn course_json(entry: &crate::storage::CatalogEntry) -> Value {
+    let mut course = serde_json::to_value(&entry.course).expect("courses serialize to JSON");
+    course["key"] = json!(entry.key);
+    course["local"] = json!(entry.source != CourseSource::BuiltIn);
+    course
+}
+
+/// Decodes `%XX` escapes, and `+` as a space in query strings. Malformed
+/// escapes are kept as written.
+fn decode(text: &str, query: bool) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if bytes.len() > i + 2 && bytes[i + 1..i + 3].iter().all(u8::is_ascii_hexdigit) => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).expect("hex digits are ASCII");
+                decoded.push(u8::from_str_radix(hex, 16).expect("two hex digits fit a byte"));
+                i += 3;
+                continue;
+            }
+            b'+' if query => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Answers requests from `server` until it is unblocked. A reply that
+/// cannot be written, e.g. because the client disconnected, is logged to
+/// stderr and the server carries on.
+///
+/// With `allow_origin`, responses carry CORS headers so a web front end
+/// served from that origin can call the API from the browser.
+pub fn serve(api: &mut Api<'_>, server: &Server, allow_origin: Option<&str>) {
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        let response = if request.as_reader().take(MAX_BODY).read_to_string(&mut body).is_err() {
+            Response { status: 400, body: Some(json!({ "error": "Request body must be UTF-8 JSON" })) }
+        } else if *request.method() == Method::Options {
+            Response { status: 204, body: None }
+        } else {
+            api.handle(request.method().as_str(), request.url(), &body)
+        };
+
+        let mut reply = match &response.body {
+            Some(body) => tiny_http::Response::from_string(body.to_string())
+                .with_header(header("Content-Type", "application/json")),
+            None => tiny_http::Response::from_string(String::new()),
+        }
+        .with_status_code(response.status);
+        if let Some(origin) = allow_origin {
+            reply.add_header(header("Access-Control-Allow-Origin", origin));
+            reply.add_header(header("Access-Control-Allow-Methods", "GET, POST, PUT, PATCH, DELETE, OPTIONS"));
+            reply.add_header(header("Access-Control-Allow-Headers", "Content-Type"));
+        }
+        // A client hanging up early only loses its own reply
+        let (method, url) = (request.method().clone(), request.url().to_string());
+        if let Err(error) = request.respond(reply) {
+            eprintln!("Could not reply to {} {}: {}", method, url, error);
+        }
+    }
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("header names and values are ASCII")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryRepository;
+    use crate::test_support::TempDataDir;
+
+    fn body(response: &Response) -> &Value {
+        response.body.as_ref().expect("JSON body")
+    }
+
+    #[test]
+    fn rounds_are_created_and_scored_and_errors_map_to_statuses() {
+        let dir = TempDataDir::new();
+        let mut repo = InMemoryRepository::new();
+        let mut api = Api::new(&mut repo, &dir.path);
+
+        api.handle("POST", "/players", r#"{"name": "Scottie Scheffler"}"#);
+        let created = api.handle("POST", "/scorecards", r#"{"player": "scottie", "holes": 9}"#);
+        assert_eq!(created.status, 201, "{:?}", created);
+        let round_id = body(&created)["round_id"].as_str().expect("round id").to_string();
+        let hole = |hole: u8| format!("/scorecards/{}/holes/{}", round_id, hole);
+
+        let scored = api.handle("PUT", &hole(1), r#"{"strokes": 3, "putts": 1, "fairway": "hit"}"#);
+        assert_eq!(scored.status, 200, "{:?}", scored);
+        assert_eq!(body(&scored)["scores"]["1"], 3);
+        assert_eq!(body(&scored)["complete"], false);
+
+        let invalid = api.handle("PUT", &hole(2), r#"{"strokes": 0}"#);
+        assert_eq!(invalid.status, 422);
+        assert!(body(&invalid)["error"].as_str().unwrap().starts_with("Invalid score 0 for hole 2"));
+        assert_eq!(api.handle("PUT", &hole(10), r#"{"strokes": 4}"#).status, 422);
+
+        assert_eq!(api.handle("GET", &format!("/scorecards/{}", Uuid::new_v4()), "").status, 404);
+        assert_eq!(api.handle("GET", "/players/Rory/statistics", "").status, 404);
+        assert_eq!(api.handle("POST", "/players", r#"{"name": "  "}"#).status, 400);
+        assert_eq!(api.handle("POST", "/players", r#"{"nam": "Rory"}"#).status, 400);
+        assert_eq!(api.handle("DELETE", "/players", "").status, 405);
+
+        // A player with a scorecard is only deleted on request
+        assert_eq!(api.handle("DELETE", "/players/Scottie%20Scheffler", "").status, 409);
+        let deleted = api.handle("DELETE", "/players/Scottie%20Scheffler?cascade", "");
+        assert_eq!(body(&deleted)["deleted_scorecards"], 1);
+        assert_eq!(body(&api.handle("GET", "/scorecards", "")), &json!([]));
+    }
+
+    #[test]
+    fn handicaps_can_be_changed_and_cleared() {
+        let dir = TempDataDir::new();
+        let mut repo = InMemoryRepository::new();
+        let mut api = Api::new(&mut repo, &dir.path);
+        api.handle("POST", "/players", r#"{"name": "Lydia Ko", "handicap": 2.1}"#);
+
+        let renamed = api.handle("PATCH", "/players/Lydia%20Ko", r#"{"name": "Lydia Ko NZ"}"#);
+        assert_eq!(body(&renamed)["handicap"], 2.1);
+        let cleared = api.handle("PATCH", "/players/Lydia%20Ko%20NZ", r#"{"handicap": null}"#);
+        assert_eq!(body(&cleared)["handicap"], Value::Null);
+        assert_eq!(body(&cleared)["name"], "Lydia Ko NZ");
+    }
+
+    #[test]
+    fn every_documented_operation_is_routed() {
+        let document: Value = serde_json::from_str(OPENAPI).expect("valid JSON");
+        let dir = TempDataDir::new();
+        let mut repo = InMemoryRepository::new();
+        let mut api = Api::new(&mut repo, &dir.path);
+
+        for (path, operations) in document["paths"].as_object().expect("paths") {
+            let url = path
+                .replace("{player}", "nobody")
+                .replace("{round_id}", &Uuid::new_v4().to_string())
+                .replace("{hole}", "1")
+                .replace("{name}", "nowhere");
+            for method in operations.as_object().expect("operations").keys().filter(|key| *key != "parameters") {
+                let response = api.handle(&method.to_uppercase(), &url, "{}");
+                let error = response.body.as_ref().and_then(|body| body["error"].as_str()).unwrap_or_default();
+                assert_ne!(response.status, 405, "{} {}", method, path);
+                assert!(!error.starts_with("No route"), "{} {}: {}", method, path, error);
+            }
+        }
+    }
+
+    #[test]
+    fn serves_json_over_http() {
+        let dir = TempDataDir::new();
+        let mut repo = InMemoryRepository::new();
+        let mut api = Api::new(&mut repo, &dir.path);
+        let server = Server::http("127.0.0.1:0").expect("bind");
+        let addr = server.server_addr().to_ip().expect("ip address");
+
+        let reply = std::thread::scope(|scope| {
+            let client = scope.spawn(|| {
+                let json = r#"{"name": "Ann"}"#;
+                let request = format!(
+                    "POST /players HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    json.len(),
+                    json
+                );
+                let mut stream = std::net::TcpStream::connect(addr).expect("connect");
+                std::io::Write::write_all(&mut stream, request.as_bytes()).expect("send");
+                let mut reply = String::new();
+                stream.read_to_string(&mut reply).expect("reply");
+                server.unblock();
+                reply
+            });
+            serve(&mut api, &server, Some("http://kiosk.local"));
+            client.join().expect("client thread")
+        });
+
+        assert!(reply.starts_with("HTTP/1.1 201"), "{}", reply);
+        assert!(reply.contains("Access-Control-Allow-Origin: http://kiosk.local"), "{}", reply);
+        assert!(reply.contains("\"name\":\"Ann\""), "{}", reply);
+        assert_eq!(repo.list_players().expect("players").len(), 1);
+    }
+}
@@ -0,0 +1,446 @@
+//! Full-screen terminal UI for browsing players, scorecards and statistics.
+//!
+//! The screen has a pane listing players, one listing the selected player's
+//! scorecards (newest first), the selected scorecard's grid, and the
+//! player's [`PlayerStatistics`] with a score distribution and a to-par
+//! trend chart.
+//!
+//! | Key                | Action                                   |
+//! |--------------------|------------------------------------------|
+//! | `↑`/`↓`, `k`/`j`   | move through the focused list            |
+//! | `Tab`, `←`/`→`     | switch between players and scorecards    |
+//! | `g`/`G`            | first or last entry                      |
+//! | `r`                | reload from the repository               |
+//! | `q`, `Esc`         | quit                                     |
+//!
+//! [`App`] only reads through the [`Repository`] trait, so it works with
+//! every backend, and it draws to any ratatui backend, which is how the
+//! tests render it headless with a `TestBackend`.
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::symbols::Marker;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Axis, Bar, BarChart, BarGroup, Block, Cell, Chart, Dataset, GraphType, List, ListItem, ListState, Paragraph, Row,
+    Table,
+};
+use ratatui::Frame;
+
+use crate::error::Result;
+use crate::models::{Player, PlayerStatistics, Scorecard};
+use crate::storage::Repository;
+use crate::ui::display::{CardRow, ScoreMark, card_rows, card_summary, card_title};
+
+/// The list that receives movement keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Players,
+    Scorecards,
+}
+
+/// State of the terminal UI.
+pub struct App<'a> {
+    repo: &'a dyn Repository,
+    players: Vec<Player>,
+    player_list: ListState,
+    /// Scorecards of the selected player, newest first.
+    scorecards: Vec<Scorecard>,
+    scorecard_list: ListState,
+    statistics: Option<PlayerStatistics>,
+    focus: Pane,
+    quit: bool,
+}
+
+impl<'a> App<'a> {
+    /// Loads the players from `repo` and selects the first one.
+    pub fn new(repo: &'a dyn Repository) -> Result<Self> {
+        let mut app = Self {
+            repo,
+            players: Vec::new(),
+            player_list: ListState::default(),
+            scorecards: Vec::new(),
+            scorecard_list: ListState::default(),
+            statistics: None,
+            focus: Pane::Players,
+            quit: false,
+        };
+        app.reload()?;
+        Ok(app)
+    }
+
+    pub fn focus(&self) -> Pane {
+        self.focus
+    }
+
+    pub fn selected_player(&self) -> Option<&Player> {
+        self.player_list.selected().and_then(|index| self.players.get(index))
+    }
+
+    pub fn selected_scorecard(&self) -> Option<&Scorecard> {
+        self.scorecard_list.selected().and_then(|index| self.scorecards.get(index))
+    }
+
+    /// Whether the user has asked to leave.
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Reads the players again, keeping the selected player if they still
+    /// exist.
+    pub fn reload(&mut self) -> Result<()> {
+        let selected = self.selected_player().map(|player| player.id);
+        self.players = self.repo.list_players()?;
+        self.players.sort_by_key(|player| player.name.to_lowercase());
+        let index = selected
+            .and_then(|id| self.players.iter().position(|player| player.id == id))
+            .or((!self.players.is_empty()).then_some(0));
+        self.select_player(index)
+    }
+
+    /// Applies a key press.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        if key.kind != KeyEventKind::Press {
+            return Ok(());
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Left | KeyCode::Right | KeyCode::Char('h' | 'l') => {
+                self.focus = match self.focus {
+                    Pane::Players => Pane::Scorecards,
+                    Pane::Scorecards => Pane::Players,
+                };
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1)?,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1)?,
+            KeyCode::Home | KeyCode::Char('g') => self.move_selection(isize::MIN)?,
+            KeyCode::End | KeyCode::Char('G') => self.move_selection(isize::MAX)?,
+            KeyCode::Char('r') => self.reload()?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn move_selection(&mut self, by: isize) -> Result<()> {
+        let (len, current) = match self.focus {
+            Pane::Players => (self.players.len(), self.player_list.selected()),
+            Pane::Scorecards => (self.scorecards.len(), self.scorecard_list.selected()),
+        };
+        if len == 0 {
+            return Ok(());
+        }
+        let next = current.unwrap_or(0).saturating_add_signed(by).min(len - 1);
+        match self.focus {
+            Pane::Players if Some(next) != current => self.select_player(Some(next)),
+            Pane::Players => Ok(()),
+            Pane::Scorecards => {
+                self.scorecard_list.select(Some(next));
+                Ok(())
+            }
+        }
+    }
+
+    fn select_player(&mut self, index: Option<usize>) -> Result<()> {
+        self.player_list.select(index);
+        self.scorecards = match self.selected_player() {
+            Some(player) => self.repo.get_scorecards_by_player(&player.id)?,
+            None => Vec::new(),
+        };
+        self.scorecards.sort_by_key(|scorecard| std::cmp::Reverse(scorecard.played_at));
+        self.scorecard_list.select((!self.scorecards.is_empty()).then_some(0));
+        self.statistics = (!self.scorecards.is_empty()).then(|| PlayerStatistics::from_scorecards(&self.scorecards));
+        Ok(())
+    }
+
+    /// Draws the whole screen.
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [main, help] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [lists, details] = Layout::horizontal([Constraint::Length(36), Constraint::Min(0)]).areas(main);
+        let [players, scorecards] = Layout::vertical([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(lists);
+        let [grid, statistics] = Layout::vertical([Constraint::Length(9), Constraint::Min(0)]).areas(details);
+
+        self.draw_players(frame, players);
+        self.draw_scorecards(frame, scorecards);
+        self.draw_grid(frame, grid);
+        self.draw_statistics(frame, statistics);
+        frame.render_widget(
+            Line::from(" ↑↓ select  Tab switch pane  g/G first/last  r reload  q quit").dark_gray(),
+            help,
+        );
+    }
+
+    fn pane_block(&self, title: &str, pane: Option<Pane>) -> Block<'static> {
+        let block = Block::bordered().title(format!(" {} ", title));
+        if pane == Some(self.focus) {
+            block.border_style(Style::new().fg(Color::Cyan))
+        } else {
+            block
+        }
+    }
+
+    fn draw_players(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .players
+            .iter()
+            .map(|player| {
+                let handicap = player.handicap.map_or_else(String::new, |index| format!(" ({:.1})", index));
+                ListItem::new(format!("{}{}", player.name, handicap))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(self.pane_block("Players", Some(Pane::Players)))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, area, &mut self.player_list);
+    }
+
+    fn draw_scorecards(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .scorecards
+            .iter()
+            .map(|scorecard| {
+                let course = scorecard.course.as_deref().unwrap_or("Standard");
+                let score = match (scorecard.total_strokes(), scorecard.score_relative_to_par()) {
+                    (Some(total), Some(relative)) => format!("{} ({})", total, signed(relative)),
+                    _ => format!("thru {}", scorecard.scores().len()),
+                };
+                ListItem::new(format!("{} {} {}", scorecard.played_at.format("%Y-%m-%d"), course, score))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(self.pane_block("Scorecards", Some(Pane::Scorecards)))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, area, &mut self.scorecard_list);
+    }
+
+    fn draw_grid(&self, frame: &mut Frame, area: Rect) {
+        let (Some(player), Some(scorecard)) = (self.selected_player(), self.selected_scorecard()) else {
+            frame.render_widget(Paragraph::new("No scorecard selected").block(self.pane_block("Scorecard", None)), area);
+            return;
+        };
+
+        let rows = card_rows("Score", scorecard);
+        let widths: Vec<Constraint> = std::iter::once(Constraint::Length(6))
+            .chain(rows[0].cells.iter().map(|(label, _)| Constraint::Length(if label.len() > 2 { 4 } else { 3 })))
+            .collect();
+        let to_row = |row: &CardRow| {
+            let cells = row.cells.iter().map(|(text, mark)| {
+                let style = mark.map_or_else(Style::new, mark_style);
+                Cell::from(Line::from(Span::styled(text.clone(), style)).right_aligned())
+            });
+            Row::new(std::iter::once(Cell::from(row.label.clone())).chain(cells))
+        };
+        let title = card_title(&player.name, scorecard);
+        let mut block = self.pane_block(&title, None);
+        if let Some(summary) = card_summary(scorecard) {
+            block = block.title_bottom(Line::from(format!(" {} ", summary)).right_aligned());
+        }
+        let table = Table::new(rows[1..].iter().map(to_row), widths)
+            .header(to_row(&rows[0]).bold())
+            .column_spacing(0)
+            .block(block);
+        frame.render_widget(table, area);
+    }
+
+    fn draw_statistics(&self, frame: &mut Frame, area: Rect) {
+        let title = match self.selected_player() {
+            Some(player) => format!("Statistics - {}", player.name),
+            None => "Statistics".to_string(),
+        };
+        let block = self.pane_block(&title, None);
+        let Some(stats) = &self.statistics else {
+            frame.render_widget(Paragraph::new("No rounds yet").block(block), area);
+            return;
+        };
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let [summary, charts] = Layout::horizontal([Constraint::Length(30), Constraint::Min(0)]).areas(inner);
+        let [distribution, trend] = Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(charts);
+
+        let decimal = |value: Option<f64>, suffix: &str| value.map_or_else(|| "-".to_string(), |v| format!("{:.1}{}", v, suffix));
+        let lines = vec![
+            Line::from(format!("Rounds       {} ({} complete)", stats.total_rounds, stats.completed_rounds)),
+            Line::from(format!("Average      {}", decimal(stats.average_score, ""))),
+            Line::from(format!("Best / worst {} / {}",
+                stats.best_score.map_or_else(|| "-".to_string(), |s| s.to_string()),
+                stats.worst_score.map_or_else(|| "-".to_string(), |s| s.to_string()))),
+            Line::from(format!("Handicap     {}", decimal(stats.handicap_index, ""))),
+            Line::from(format!("Putts/round  {}", decimal(stats.putts_per_round, ""))),
+            Line::from(format!("GIR          {}", decimal(stats.gir_percentage, "%"))),
+            Line::from(format!("Fairways     {}", decimal(stats.fairway_percentage, "%"))),
+            Line::from(format!("Consistency  {}", decimal(stats.score_std_dev, " sd"))),
+        ];
+        frame.render_widget(Paragraph::new(lines), summary);
+
+        let counts = [
+            ("Eagle", stats.eagles, Color::Red),
+            ("Birdie", stats.birdies, Color::Red),
+            ("Par", stats.pars, Color::Reset),
+            ("Bogey", stats.bogeys, Color::Blue),
+            ("Dbl+", stats.double_bogeys, Color::Blue),
+        ];
+        let bars: Vec<Bar> = counts
+            .iter()
+            .map(|&(label, count, color)| Bar::default().label(label.into()).value(count as u64).style(Style::new().fg(color)))
+            .collect();
+        let chart = BarChart::default()
+            .block(Block::new().title("Holes by score"))
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(6)
+            .bar_gap(1);
+        frame.render_widget(chart, distribution);
+
+        let points: Vec<(f64, f64)> =
+            stats.history.iter().enumerate().map(|(i, round)| (i as f64 + 1.0, f64::from(round.to_par))).collect();
+        if points.is_empty() {
+            frame.render_widget(Paragraph::new("Trend appears once a round is complete"), trend);
+            return;
+        }
+        let (low, high) = points.iter().fold((0.0_f64, 0.0_f64), |(low, high), &(_, y)| (low.min(y), high.max(y)));
+        let dataset = Dataset::default()
+            .name("to par")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::new().fg(Color::Yellow))
+            .data(&points);
+        let chart = Chart::new(vec![dataset])
+            .block(Block::new().title("Score to par by round"))
+            .x_axis(Axis::default().bounds([1.0, (points.len() as f64).max(2.0)]))
+            .y_axis(
+                Axis::default()
+                    .bounds([low - 1.0, high + 1.0])
+                    .labels([signed(low as i16), signed(high as i16)]),
+            );
+        frame.render_widget(chart, trend);
+    }
+}
+
+/// Colours matching the circles and squares of a printed scorecard: red
+/// under par, blue over par.
+fn mark_style(mark: ScoreMark) -> Style {
+    match mark {
+        ScoreMark::Eagle => Style::new().fg(Color::Black).bg(Color::Red),
+        ScoreMark::Birdie => Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ScoreMark::Par => Style::new(),
+        ScoreMark::Bogey => Style::new().fg(Color::Blue),
+        ScoreMark::DoubleBogey => Style::new().fg(Color::White).bg(Color::Blue),
+    }
+}
+
+fn signed(to_par: i16) -> String {
+    if to_par == 0 { "E".to_string() } else { format!("{:+}", to_par) }
+}
+
+/// Runs the terminal UI until the user quits, restoring the terminal
+/// afterwards.
+pub fn run(repo: &dyn Repository) -> Result<()> {
+    let mut app = App::new(repo)?;
+    let mut terminal = ratatui::init();
+    let result = (|| -> Result<()> {
+        while !app.should_quit() {
+            terminal.draw(|frame| app.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                app.handle_key(key)?;
+            }
+        }
+        Ok(())
+    })();
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryRepository;
+    use crate::utils::create_standard_course;
+    use chrono::{Duration, Utc};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn repository() -> InMemoryRepository {
+        let mut repo = InMemoryRepository::new();
+        let course = create_standard_course(9);
+        for (name, over) in [("Ludvig", 0), ("Akshay", 1)] {
+            let player = Player::new(name, None).expect("player");
+            repo.save_player(&player).expect("save");
+            for days in 0..2 {
+                let mut card = Scorecard::new(player.id, &course, course.default_tee())
+                    .expect("scorecard")
+                    .with_played_at(Utc::now() - Duration::days(days));
+                // The older round has an extra bogey on the second hole
+                for hole in 1..=9 {
+                    let extra = u8::from(hole == 2 && days == 1);
+                    card.record_score(hole, card.get_par(hole).unwrap() + over + extra).expect("record");
+                }
+                repo.save_scorecard(&card).expect("save");
+            }
+        }
+        repo
+    }
+
+    fn render(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 32)).expect("terminal");
+        terminal.draw(|frame| app.draw(frame)).expect("draw");
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::from(code)).expect("key");
+    }
+
+    #[test]
+    fn panes_follow_the_selected_player_and_scorecard() {
+        let repo = repository();
+        let mut app = App::new(&repo).expect("app");
+
+        let screen = render(&mut app);
+        assert!(screen.contains("> Akshay"), "{}", screen);
+        assert!(screen.contains("Statistics - Akshay"), "{}", screen);
+        assert!(screen.contains("Total 45 (+9)"), "{}", screen);
+
+        press(&mut app, KeyCode::Down);
+        let screen = render(&mut app);
+        assert!(screen.contains("> Ludvig"), "{}", screen);
+        assert!(screen.contains("Total 36 (E)"), "{}", screen);
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.focus(), Pane::Scorecards);
+        assert!(render(&mut app).contains("Total 37 (+1)"));
+    }
+
+    #[test]
+    fn movement_stops_at_the_ends_and_q_quits() {
+        let repo = repository();
+        let mut app = App::new(&repo).expect("app");
+
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.selected_player().map(|player| player.name.as_str()), Some("Akshay"));
+        press(&mut app, KeyCode::Char('G'));
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.selected_player().map(|player| player.name.as_str()), Some("Ludvig"));
+
+        assert!(!app.should_quit());
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn empty_repository_renders_placeholders() {
+        let repo = InMemoryRepository::new();
+        let mut app = App::new(&repo).expect("app");
+
+        let screen = render(&mut app);
+
+        assert!(screen.contains("No scorecard selected"), "{}", screen);
+        assert!(screen.contains("No rounds yet"), "{}", screen);
+    }
+}
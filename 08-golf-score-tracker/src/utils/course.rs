@@ -1,33 +1,52 @@
 //! Golf course layout definitions and utilities.
 //!
 //! This module provides functions to generate standard and famous golf course
-//! par layouts. Course layouts are represented as `BTreeMap<u8, u8>` where
+//! par layouts. Par layouts are represented as `BTreeMap<u8, u8>` where
 //! the key is the hole number (1-18) and the value is the par for that hole.
+//! The named courses are also available as full [`Course`] values with
+//! yardages, stroke indexes and tees.
+//!
+//! Yardages, stroke indexes and tee ratings of the named courses are
+//! representative values for casual play, not official scorecard data.
 //!
 //! # Examples
 //!
 //! ```
-//! use golf_score_tracker::utils::{create_standard_pars, get_course_pars};
+//! use golf_score_tracker::utils::{create_standard_pars, get_course, get_course_pars};
 //! use std::collections::BTreeMap;
 //!
 //! // Create a standard 18-hole course
 //! let pars = create_standard_pars(18);
 //! assert_eq!(pars.len(), 18);
 //!
-//! // Get a famous course by name
-//! let masters = get_course_pars("masters", 18);
+//! // Get a famous course by name or alias
+//! let masters = get_course_pars("masters").unwrap();
 //! assert_eq!(masters.len(), 18);
+//!
+//! // Or the full course with its tees
+//! let pebble = get_course("pebble-beach").unwrap();
+//! assert_eq!(pebble.tees.len(), 3);
+//!
+//! // Typos are reported instead of falling back to a made-up course
+//! let error = get_course("Peble Beach").unwrap_err();
+//! assert_eq!(error.to_string(), "Course 'Peble Beach' not found. Did you mean Pebble_Beach?");
 //! ```
 
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 
+use crate::error::{GolfError, Result};
+use crate::models::{Course, Hole, TeeSet};
+
 /// Function pointer type for course par generation functions.
 ///
 /// This type alias represents functions that take no arguments and return
 /// a complete course layout as a `BTreeMap`.
 pub type CourseParGenerator = fn() -> BTreeMap<u8, u8>;
 
+/// Function pointer type for functions that build a complete [`Course`].
+pub type CourseGenerator = fn() -> Course;
+
 /// Creates a standard golf course with algorithmically generated pars.
 ///
 /// This function generates a course layout with variety based on hole numbers.
@@ -57,6 +76,16 @@ pub fn create_standard_pars(holes: u8) -> BTreeMap<u8, u8> {
     pars
 }
 
+/// Creates a standard course with the [`create_standard_pars`] layout.
+///
+/// Holes are ranked in playing order and the course is played from a single
+/// unrated "Standard" tee.
+pub fn create_standard_course(holes: u8) -> Course {
+    let pars = create_standard_pars(holes);
+    let tee = TeeSet::unrated("Standard", pars.values().copied().map(u16::from).sum());
+    let holes = pars.into_iter().map(|(number, par)| Hole { number, par, yardage: None, stroke_index: None }).collect();
+    Course { name: "Standard".to_string(), holes, tees: vec![tee], aliases: Vec::new() }
+}
 
 /// Creates the Augusta National (Masters Tournament) course layout.
 ///
@@ -118,49 +147,224 @@ pub fn torrey_pines_south_course_pars() -> BTreeMap<u8, u8> {
     pars.iter().copied().collect()
 }
 
+/// Builds a named 18-hole course from its par layout and per-hole data.
+///
+/// `tees` lists `(name, course rating, slope rating)` for each tee set.
+fn build_course(
+    name: &str,
+    aliases: &[&str],
+    pars: BTreeMap<u8, u8>,
+    yardages: [u16; 18],
+    stroke_indexes: [u8; 18],
+    tees: &[(&str, f64, u16)],
+) -> Course {
+    let holes = pars
+        .into_iter()
+        .zip(yardages.into_iter().zip(stroke_indexes))
+        .map(|((number, par), (yardage, stroke_index))| Hole {
+            number,
+            par,
+            yardage: Some(yardage),
+            stroke_index: Some(stroke_index),
+        })
+        .collect();
+    let tees = tees
+        .iter()
+        .map(|&(tee, rating, slope)| TeeSet::new(tee, rating, slope).expect("built-in tees are valid"))
+        .collect();
+    Course::new(name, holes, tees).expect("built-in courses are valid").with_aliases(aliases)
+}
+
+/// Creates Augusta National with the [`create_august_national_pars`] layout.
+pub fn create_augusta_national_course() -> Course {
+    build_course(
+        "Augusta National",
+        &["Augusta", "Masters"],
+        create_august_national_pars(),
+        [445, 585, 350, 240, 495, 180, 450, 570, 460, 495, 520, 155, 545, 440, 550, 170, 440, 465],
+        [9, 3, 15, 7, 1, 17, 11, 5, 13, 4, 2, 18, 10, 8, 12, 16, 6, 14],
+        &[("Masters", 78.1, 137), ("Member", 74.0, 130)],
+    )
+}
+
+/// Creates Pebble Beach with the [`create_pebble_beach_pars`] layout.
+pub fn create_pebble_beach_course() -> Course {
+    build_course(
+        "Pebble Beach",
+        &["Pebble"],
+        create_pebble_beach_pars(),
+        [380, 516, 404, 195, 380, 523, 106, 428, 505, 446, 390, 202, 445, 580, 397, 178, 403, 543],
+        [13, 15, 9, 17, 11, 3, 7, 1, 5, 10, 8, 16, 12, 2, 14, 18, 4, 6],
+        &[("Gold", 75.5, 145), ("Blue", 74.7, 143), ("White", 72.1, 136)],
+    )
+}
+
+/// Creates the Old Course at St Andrews with the [`create_st_andrews_pars`]
+/// layout.
+pub fn create_st_andrews_course() -> Course {
+    build_course(
+        "St Andrews Old Course",
+        &["Old Course"],
+        create_st_andrews_pars(),
+        [376, 452, 398, 560, 175, 418, 372, 175, 520, 386, 385, 174, 465, 440, 570, 165, 495, 357],
+        [11, 5, 13, 9, 17, 3, 7, 15, 1, 12, 10, 16, 4, 2, 6, 18, 8, 14],
+        &[("Championship", 73.1, 132), ("Medal", 72.1, 129)],
+    )
+}
+
+/// Creates the Torrey Pines North Course with the
+/// [`torrey_pines_north_course_pars`] layout.
+pub fn torrey_pines_north_course() -> Course {
+    build_course(
+        "Torrey Pines North",
+        &["Torrey North"],
+        torrey_pines_north_course_pars(),
+        [448, 388, 175, 420, 533, 392, 464, 168, 553, 522, 402, 187, 435, 453, 168, 383, 520, 413],
+        [5, 11, 17, 1, 9, 13, 3, 15, 7, 12, 6, 16, 8, 2, 18, 14, 10, 4],
+        &[("Black", 75.1, 138), ("Blue", 72.9, 133)],
+    )
+}
+
+/// Creates the Torrey Pines South Course with the
+/// [`torrey_pines_south_course_pars`] layout.
+pub fn torrey_pines_south_course() -> Course {
+    build_course(
+        "Torrey Pines South",
+        &["Torrey South"],
+        torrey_pines_south_course_pars(),
+        [450, 389, 200, 488, 564, 515, 462, 177, 613, 405, 221, 504, 614, 435, 478, 227, 442, 570],
+        [7, 13, 15, 1, 9, 11, 3, 17, 5, 10, 16, 2, 8, 14, 4, 18, 6, 12],
+        &[("Black", 78.1, 144), ("Blue", 75.4, 137)],
+    )
+}
+
 /// Returns a catalog of all available named courses.
 ///
 /// This function provides a lookup table mapping course names to their
-/// par generation functions. Use this to dynamically select courses by name.
+/// course generation functions. Use this to dynamically select courses by name.
 ///
 /// # Returns
 ///
-/// A `HashMap` where keys are course names (lowercase, hyphenated) and
-/// values are function pointers that generate the course layout.
+/// A `HashMap` where keys are course names (e.g. `Pebble_Beach`) and
+/// values are function pointers that build the course.
 /// 
-pub fn get_course_catalog() -> HashMap<String, CourseParGenerator> { 
-    let mut catalog: HashMap<String, CourseParGenerator> = HashMap::new();
-    catalog.insert("Augusta_National".to_string(), create_august_national_pars);
-    catalog.insert("Pebble_Beach".to_string(), create_pebble_beach_pars);
-    catalog.insert("St_Andrews".to_string(), create_st_andrews_pars);
-    catalog.insert("Torrey_Pines_North".to_string(), torrey_pines_north_course_pars);
-    catalog.insert("Torrey_Pines_South".to_string(), torrey_pines_south_course_pars);
+pub fn get_course_catalog() -> HashMap<String, CourseGenerator> { 
+    let mut catalog: HashMap<String, CourseGenerator> = HashMap::new();
+    catalog.insert("Augusta_National".to_string(), create_augusta_national_course);
+    catalog.insert("Pebble_Beach".to_string(), create_pebble_beach_course);
+    catalog.insert("St_Andrews".to_string(), create_st_andrews_course);
+    catalog.insert("Torrey_Pines_North".to_string(), torrey_pines_north_course);
+    catalog.insert("Torrey_Pines_South".to_string(), torrey_pines_south_course);
     catalog
 }
 
-/// Gets course pars by name, falling back to standard layout.
+/// Gets a built-in course by name.
+///
+/// The name is matched with [`resolve_course`], so catalog keys, course
+/// names and aliases all work regardless of case and punctuation.
+///
+/// # Errors
+///
+/// Returns `GolfError::CourseNotFound`, with suggestions for close
+/// matches, when no course matches.
+pub fn get_course(course_name: &str) -> Result<Course> {
+    let catalog: BTreeMap<String, Course> =
+        get_course_catalog().into_iter().map(|(key, generator)| (key, generator())).collect();
+    let key = resolve_course(course_name, catalog.iter().map(|(key, course)| (key.as_str(), course)))?;
+    Ok(catalog[key].clone())
+}
+
+/// Gets course pars by name.
 ///
-/// This is the primary function for retrieving course layouts. It checks
-/// the course catalog for a named course, and if not found, generates a
-/// standard layout with the specified number of holes.
+/// This is the primary function for retrieving course layouts. It looks
+/// the course up with [`get_course`]; use [`create_standard_pars`] for a
+/// generic layout.
 ///
 /// # Arguments
 ///
 /// * `course_name` - Name of the course (e.g., "masters", "pebble-beach")
-/// * `holes` - Number of holes if using standard layout
 ///
 /// # Returns
 ///
-/// Course par layout as a `BTreeMap<u8, u8>`.
+/// Course par layout as a `BTreeMap<u8, u8>`, or
+/// `GolfError::CourseNotFound` for an unknown course.
 ///
-pub fn get_course_pars(course_name: &str, holes: u8) -> BTreeMap<u8, u8> {
-    let catalog = get_course_catalog();
-    
-    if let Some(generator) = catalog.get(course_name) {
-        generator()
-    } else {
-        create_standard_pars(holes)
+pub fn get_course_pars(course_name: &str) -> Result<BTreeMap<u8, u8>> {
+    Ok(get_course(course_name)?.pars())
+}
+
+/// Reduces a course name to lowercase letters and digits, so that
+/// `Pebble_Beach`, `pebble-beach` and `Pebble Beach` compare equal.
+pub fn normalize_course_name(name: &str) -> String {
+    name.chars().filter(char::is_ascii_alphanumeric).map(|c| c.to_ascii_lowercase()).collect()
+}
+
+/// Finds the key of the course `query` refers to among `(key, course)`
+/// pairs.
+///
+/// An exact key match wins. Otherwise the query is compared, after
+/// [`normalize_course_name`], against every key, course name and alias.
+///
+/// # Errors
+///
+/// Returns `GolfError::CourseNotFound` when nothing matches. Its
+/// suggestions hold up to three keys whose names are within a small edit
+/// distance of the query or contain it, closest first.
+pub fn resolve_course<'a>(query: &str, courses: impl IntoIterator<Item = (&'a str, &'a Course)>) -> Result<&'a str> {
+    let courses: Vec<(&str, &Course)> = courses.into_iter().collect();
+    let query = query.trim();
+    if let Some(&(key, _)) = courses.iter().find(|(key, _)| *key == query) {
+        return Ok(key);
+    }
+
+    let wanted = normalize_course_name(query);
+    if !wanted.is_empty()
+        && let Some(&(key, _)) = courses.iter().find(|(key, course)| lookup_names(key, course).any(|name| name == wanted))
+    {
+        return Ok(key);
+    }
+
+    let max_distance = (wanted.chars().count() / 3).max(2);
+    let mut close: Vec<(usize, &str)> = courses
+        .iter()
+        .filter_map(|&(key, course)| {
+            let distance = lookup_names(key, course)
+                .map(|name| {
+                    if !wanted.is_empty() && name.contains(&wanted) { 0 } else { edit_distance(&wanted, &name) }
+                })
+                .min()?;
+            (distance <= max_distance).then_some((distance, key))
+        })
+        .collect();
+    close.sort();
+
+    Err(GolfError::CourseNotFound {
+        name: query.to_string(),
+        suggestions: close.into_iter().take(3).map(|(_, key)| key.to_string()).collect(),
+    })
+}
+
+/// Normalized key, name and aliases of a course.
+fn lookup_names<'a>(key: &'a str, course: &'a Course) -> impl Iterator<Item = String> + 'a {
+    std::iter::once(key)
+        .chain(std::iter::once(course.name.as_str()))
+        .chain(course.aliases.iter().map(String::as_str))
+        .map(normalize_course_name)
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
     }
+    previous[b.len()]
 }
 
 /// Lists all available course names.
@@ -169,7 +373,7 @@ pub fn get_course_pars(course_name: &str, holes: u8) -> BTreeMap<u8, u8> {
 ///
 /// # Returns
 ///
-/// Vector of course name strings (e.g., `["masters", "pebble-beach", ...]`)
+/// Vector of catalog keys (e.g., `["Augusta_National", "Pebble_Beach", ...]`)
 ///
 pub fn list_available_courses() -> Vec<String> {
     get_course_catalog().keys().cloned().collect()
@@ -198,4 +402,54 @@ mod tests {
         assert_eq!(*pars.get(&1).unwrap_or(&0), 4);
         assert_eq!(*pars.get(&12).unwrap_or(&0), 3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn catalog_courses_keep_their_par_layouts() {
+        let course = create_augusta_national_course();
+        assert_eq!(course.pars(), create_august_national_pars());
+        assert_eq!(course.total_par(), 72);
+        assert_eq!(course.total_yardage(), Some(7555));
+    }
+
+    #[test]
+    fn every_catalog_course_is_valid() {
+        for (name, generator) in get_course_catalog() {
+            let course = generator();
+            assert!(course.validate().is_ok(), "{} is invalid", name);
+            assert_eq!(course.hole_count(), 18, "{}", name);
+            assert!(course.holes.iter().all(|hole| hole.yardage.is_some()), "{}", name);
+        }
+    }
+
+    #[test]
+    fn lookup_ignores_case_punctuation_and_uses_aliases() {
+        assert_eq!(get_course("pebble-beach").unwrap().name, "Pebble Beach");
+        assert_eq!(get_course("AUGUSTA NATIONAL").unwrap().name, "Augusta National");
+        assert_eq!(get_course("masters").unwrap().name, "Augusta National");
+        assert_eq!(get_course("St. Andrews").unwrap().name, "St Andrews Old Course");
+        assert_eq!(get_course("Torrey_Pines_South").unwrap().name, "Torrey Pines South");
+    }
+
+    #[test]
+    fn unknown_course_is_an_error_with_suggestions() {
+        match get_course("Torrey Pine Nort") {
+            Err(GolfError::CourseNotFound { name, suggestions }) => {
+                assert_eq!(name, "Torrey Pine Nort");
+                assert_eq!(suggestions[0], "Torrey_Pines_North");
+            }
+            other => panic!("expected CourseNotFound, got {:?}", other),
+        }
+        assert!(matches!(
+            get_course("torrey"),
+            Err(GolfError::CourseNotFound { suggestions, .. }) if suggestions == ["Torrey_Pines_North", "Torrey_Pines_South"]
+        ));
+        assert!(matches!(get_course("Bethpage"), Err(GolfError::CourseNotFound { suggestions, .. }) if suggestions.is_empty()));
+    }
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("pebble", "pebble"), 0);
+    }
+}
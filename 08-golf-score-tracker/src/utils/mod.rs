@@ -2,7 +2,12 @@ pub mod course;
 pub mod validators;
 
 pub use course::{
+    create_standard_course,
     create_standard_pars, 
+    get_course,
+    get_course_catalog,
     get_course_pars,
     list_available_courses,
+    normalize_course_name,
+    resolve_course,
 };
\ No newline at end of file
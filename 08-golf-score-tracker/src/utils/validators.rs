@@ -1,4 +1,5 @@
 use crate::error::{GolfError, Result};
+use crate::models::HoleResult;
 
 pub fn validate_hole_number(hole: u8, max_holes: u8) -> Result<()> {
     if hole == 0 || hole > max_holes {
@@ -19,4 +20,20 @@ pub fn validate_score(strokes: u8, hole: u8, par: u8) -> Result<()> {
         return Err(GolfError::InvalidScore { score: strokes as i32, hole, par });
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn validate_hole_result(result: &HoleResult, strokes: u8, hole: u8, par: u8) -> Result<()> {
+    let invalid = |reason: String| Err(GolfError::InvalidHoleResult { hole, reason });
+    if let Some(putts) = result.putts
+        && putts > strokes
+    {
+        return invalid(format!("{} putts is more than the {} strokes taken", putts, strokes));
+    }
+    if result.penalties >= strokes {
+        return invalid(format!("{} penalty strokes leave no shots in a score of {}", result.penalties, strokes));
+    }
+    if result.fairway.is_some() && par < 4 {
+        return invalid("fairways are only recorded on par 4 and par 5 holes".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,32 @@
+//! Shared fixtures for the integration tests, also built into the unit tests
+//! as `test_support`.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+/// Temporary data directory that is created up front and removed again when
+/// dropped.
+pub struct TempDataDir {
+    pub path: PathBuf,
+}
+
+impl TempDataDir {
+    pub fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("golf-tracker-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&path).expect("create temp data dir");
+        Self { path }
+    }
+}
+
+impl Drop for TempDataDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// A 9-hole layout with every hole a par 4.
+pub fn nine_hole_pars() -> BTreeMap<u8, u8> {
+    (1..=9).map(|hole| (hole, 4)).collect()
+}
@@ -0,0 +1,309 @@
+mod common;
+
+use golf_score_tracker::storage::{
+    CborSerializer, CourseCatalog, Format, Manifest, SqliteRepository, TomlSerializer, open_file_repository,
+};
+use golf_score_tracker::models::Round;
+use golf_score_tracker::utils::create_standard_course;
+use golf_score_tracker::{FileRepository, GolfError, InMemoryRepository, Player, Repository, Scorecard};
+
+use uuid::Uuid;
+
+use common::{TempDataDir, nine_hole_pars};
+
+#[test]
+fn recorded_scores_persist_through_repository() {
+    let mut repo = InMemoryRepository::new();
+
+    let player = Player::new("Nelly Korda", Some(1.0)).expect("player");
+    repo.save_player(&player).expect("save player");
+
+    let scorecard = Scorecard::from_pars(player.id, 9, nine_hole_pars()).expect("scorecard");
+    repo.save_scorecard(&scorecard).expect("save scorecard");
+
+    for hole in 1..=9 {
+        let mut stored = repo.get_scorecard(&scorecard.round_id).expect("load").expect("exists");
+        stored.record_score(hole, 4).expect("record");
+        repo.save_scorecard(&stored).expect("save");
+    }
+
+    let stored = repo.get_scorecard(&scorecard.round_id).expect("load").expect("exists");
+    assert!(stored.is_complete());
+    assert_eq!(stored.total_strokes(), Some(36));
+    assert_eq!(stored.score_relative_to_par(), Some(0));
+}
+
+#[test]
+fn completed_scorecard_refuses_further_scores() {
+    let player = Player::new("Ludvig Aberg", None).expect("player");
+    let mut scorecard = Scorecard::from_pars(player.id, 9, nine_hole_pars()).expect("scorecard");
+    for hole in 1..=9 {
+        scorecard.record_score(hole, 5).expect("record");
+    }
+
+    let result = scorecard.record_score(9, 4);
+
+    assert!(matches!(result, Err(GolfError::ScorecardComplete(id)) if id == scorecard.round_id));
+    assert_eq!(scorecard.get_score(9), Some(5));
+}
+
+#[test]
+fn in_progress_score_can_be_rerecorded() {
+    let player = Player::new("Xander Schauffele", None).expect("player");
+    let mut scorecard = Scorecard::from_pars(player.id, 9, nine_hole_pars()).expect("scorecard");
+
+    scorecard.record_score(1, 6).expect("record");
+    scorecard.record_score(1, 5).expect("re-record");
+
+    assert_eq!(scorecard.get_score(1), Some(5));
+    assert!(!scorecard.is_complete());
+}
+
+#[test]
+fn toml_repository_round_trips_and_records_format() {
+    let dir = TempDataDir::new();
+    let mut repo = FileRepository::with_serializer(dir.path.clone(), TomlSerializer).expect("repository");
+
+    let player = Player::new("Rose Zhang", None).expect("player");
+    let mut scorecard = Scorecard::from_pars(player.id, 9, nine_hole_pars()).expect("scorecard");
+    scorecard.record_score(1, 3).expect("record");
+    repo.save_player(&player).expect("save player");
+    repo.save_scorecard(&scorecard).expect("save scorecard");
+
+    assert!(dir.path.join("players").join(format!("{}.toml", player.id)).exists());
+    assert_eq!(repo.get_player(&player.id).expect("load"), Some(player.clone()));
+    assert_eq!(repo.get_scorecards_by_player(&player.id).expect("load"), vec![scorecard]);
+
+    let manifest = Manifest::read(&dir.path).expect("manifest").expect("exists");
+    assert_eq!(manifest.format, Format::Toml);
+}
+
+#[test]
+fn reopening_with_another_format_is_refused() {
+    let dir = TempDataDir::new();
+    FileRepository::with_serializer(dir.path.clone(), CborSerializer).expect("repository");
+
+    let result = FileRepository::new(dir.path.clone());
+
+    assert!(matches!(
+        result,
+        Err(GolfError::FormatMismatch { expected: Format::Json, found: Format::Cbor, .. })
+    ));
+}
+
+#[test]
+fn runtime_open_uses_manifest_format() {
+    let dir = TempDataDir::new();
+    let player = Player::new("Tommy Fleetwood", Some(0.4)).expect("player");
+    {
+        let mut repo = FileRepository::with_serializer(dir.path.clone(), CborSerializer).expect("repository");
+        repo.save_player(&player).expect("save");
+    }
+
+    let repo = open_file_repository(dir.path.clone(), None).expect("open");
+
+    assert_eq!(repo.list_players().expect("list"), vec![player]);
+}
+
+#[test]
+fn directory_without_manifest_is_read_as_legacy_json() {
+    let dir = TempDataDir::new();
+    let player = Player::new("Justin Thomas", None).expect("player");
+    let players_dir = dir.path.join("players");
+    std::fs::create_dir_all(&players_dir).expect("mkdir");
+    std::fs::write(
+        players_dir.join(format!("{}.json", player.id)),
+        serde_json::to_string_pretty(&player).expect("json"),
+    ).expect("write");
+
+    assert!(matches!(
+        FileRepository::with_serializer(dir.path.clone(), TomlSerializer),
+        Err(GolfError::FormatMismatch { found: Format::Json, .. })
+    ));
+    let repo = FileRepository::new(dir.path.clone()).expect("repository");
+    assert_eq!(repo.list_players().expect("list"), vec![player]);
+}
+
+#[test]
+fn sqlite_repository_persists_across_reopen() {
+    let dir = TempDataDir::new();
+    let db_path = dir.path.join("golf.db");
+    let player = Player::new("Hideki Matsuyama", Some(0.8)).expect("player");
+    let mut scorecard = Scorecard::from_pars(player.id, 9, nine_hole_pars()).expect("scorecard");
+    for hole in 1..=9 {
+        scorecard.record_score(hole, 4).expect("record");
+    }
+    {
+        let mut repo = SqliteRepository::open(&db_path).expect("open");
+        repo.save_player(&player).expect("save player");
+        repo.save_scorecard(&scorecard).expect("save scorecard");
+    }
+
+    let repo = SqliteRepository::open(&db_path).expect("reopen");
+
+    assert_eq!(repo.list_players().expect("list"), vec![player.clone()]);
+    let stored = repo.get_scorecards_by_player(&player.id).expect("by player");
+    assert_eq!(stored, vec![scorecard]);
+    assert_eq!(stored[0].total_strokes(), Some(36));
+}
+
+#[test]
+fn in_memory_snapshot_restores_from_file_repository() {
+    let dir = TempDataDir::new();
+    let mut memory = InMemoryRepository::new();
+    let player = Player::new("Brooks Koepka", Some(0.2)).expect("player");
+    let scorecard = Scorecard::from_pars(player.id, 9, nine_hole_pars()).expect("scorecard");
+    memory.save_player(&player).expect("save player");
+    memory.save_scorecard(&scorecard).expect("save scorecard");
+
+    let mut files = FileRepository::new(dir.path.clone()).expect("repository");
+    memory.snapshot_to(&mut files).expect("snapshot");
+    let restored = InMemoryRepository::restore_from(&files).expect("restore");
+
+    assert_eq!(files.list_players().expect("list"), vec![player.clone()]);
+    assert_eq!(restored.get_player(&player.id).expect("get"), Some(player));
+    assert_eq!(restored.get_scorecard(&scorecard.round_id).expect("get"), Some(scorecard));
+}
+
+#[test]
+fn second_open_of_a_locked_directory_is_refused() {
+    let dir = TempDataDir::new();
+    let repo = FileRepository::new(dir.path.clone()).expect("repository");
+
+    assert!(matches!(FileRepository::new(dir.path.clone()), Err(GolfError::RepositoryLocked(_))));
+
+    drop(repo);
+    assert!(FileRepository::new(dir.path.clone()).is_ok());
+}
+
+#[test]
+fn saves_leave_no_temporary_files_behind() {
+    let dir = TempDataDir::new();
+    let mut repo = FileRepository::new(dir.path.clone()).expect("repository");
+    let player = Player::new("Patrick Cantlay", None).expect("player");
+    repo.save_player(&player).expect("save");
+    repo.save_player(&player).expect("overwrite");
+
+    let files: Vec<_> = std::fs::read_dir(dir.path.join("players"))
+        .expect("read dir")
+        .map(|entry| entry.expect("entry").file_name())
+        .collect();
+
+    assert_eq!(files, vec![std::ffi::OsString::from(format!("{}.json", player.id))]);
+}
+
+#[test]
+fn repair_quarantines_truncated_files() {
+    let dir = TempDataDir::new();
+    let mut repo = FileRepository::new(dir.path.clone()).expect("repository");
+    let player = Player::new("Wyndham Clark", None).expect("player");
+    repo.save_player(&player).expect("save");
+
+    let truncated = dir.path.join("scorecards").join(format!("{}.json", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(truncated.parent().unwrap()).expect("mkdir");
+    std::fs::write(&truncated, "{\"round_id\": \"").expect("write");
+    let leftover = dir.path.join("players").join(".interrupted.json.tmp");
+    std::fs::write(&leftover, "{").expect("write");
+
+    assert!(matches!(repo.list_scorecards(), Err(GolfError::CorruptFile { .. })));
+    assert_eq!(repo.list_players().expect("temp files are ignored"), vec![player.clone()]);
+
+    let report = repo.repair().expect("repair");
+
+    assert_eq!(report.removed_temp_files, vec![leftover.clone()]);
+    assert_eq!(report.quarantined.len(), 1);
+    assert!(report.quarantined[0].starts_with(dir.path.join("quarantine").join("scorecards")));
+    assert!(!truncated.exists() && !leftover.exists());
+    assert_eq!(repo.list_scorecards().expect("list"), vec![]);
+    assert!(repo.repair().expect("repair again").is_clean());
+}
+
+#[test]
+fn group_round_is_queryable_per_player_after_reopen() {
+    let dir = TempDataDir::new();
+    let course = create_standard_course(9);
+    let mut round = Round::new(&course);
+    let players: Vec<Player> = ["Scottie Scheffler", "Collin Morikawa", "Sam Burns"]
+        .into_iter()
+        .map(|name| Player::new(name, None).expect("player"))
+        .collect();
+
+    {
+        let mut repo = open_file_repository(dir.path.clone(), None).expect("repository");
+        for player in &players {
+            let scorecard = round.add_player(player.id, &course, course.default_tee()).expect("add");
+            repo.save_player(player).expect("save player");
+            repo.save_scorecard(&scorecard).expect("save scorecard");
+        }
+        repo.save_round(&round).expect("save round");
+    }
+
+    let repo = open_file_repository(dir.path.clone(), None).expect("reopen");
+    let stored = repo.get_round(&round.id).expect("load").expect("exists");
+    assert_eq!(stored.player_ids(), players.iter().map(|p| p.id).collect::<Vec<_>>());
+    assert_eq!(repo.get_rounds_by_player(&players[1].id).expect("query"), vec![round.clone()]);
+    assert!(repo.get_rounds_by_player(&Uuid::new_v4()).expect("query").is_empty());
+
+    let scorecard_id = stored.scorecard_id_for(&players[2].id).expect("entry");
+    let scorecard = repo.get_scorecard(&scorecard_id).expect("load").expect("exists");
+    assert_eq!(scorecard.played_at, round.date);
+}
+
+#[test]
+fn hand_written_course_file_is_playable() {
+    let dir = TempDataDir::new();
+    std::fs::create_dir_all(dir.path.join("courses")).expect("courses dir");
+    let mut toml = String::from("name = \"Three Oaks\"\n");
+    for (number, (par, stroke_index)) in [(4, 2), (3, 3), (5, 1)].into_iter().enumerate() {
+        toml.push_str(&format!("\n[[holes]]\nnumber = {}\npar = {}\nstroke_index = {}\n", number + 1, par, stroke_index));
+    }
+    toml.push_str("\n[[tees]]\nname = \"Yellow\"\ncourse_rating = 11.9\nslope_rating = 108\n");
+    std::fs::write(dir.path.join("courses/three-oaks.toml"), toml).expect("write course");
+
+    let catalog = CourseCatalog::load(&dir.path).expect("load catalog");
+    let course = &catalog.get("three-oaks").expect("user course").course;
+    let scorecard = Scorecard::new(Uuid::new_v4(), course, course.tee("yellow").expect("tee")).expect("scorecard");
+
+    assert_eq!(scorecard.max_holes, 3);
+    assert_eq!(scorecard.total_par(), 12);
+    assert_eq!(scorecard.stroke_index(3), Some(1));
+    assert!(catalog.get("Augusta_National").is_some());
+}
+
+#[test]
+fn scorecard_json_from_before_hole_detail_still_loads() {
+    let round_id = Uuid::new_v4();
+    let json = format!(
+        r#"{{"round_id":"{}","player_id":"{}","max_holes":3,"scores":{{"1":4,"2":5}},"pars":{{"1":4,"2":4,"3":3}}}}"#,
+        round_id,
+        Uuid::new_v4()
+    );
+
+    let scorecard: Scorecard = serde_json::from_str(&json).expect("legacy scorecard");
+
+    assert_eq!(scorecard.round_id, round_id);
+    assert_eq!(scorecard.get_score(2), Some(5));
+    assert_eq!(scorecard.hole_result(1), None);
+    assert_eq!(scorecard.green_in_regulation(1), None);
+}
+
+#[test]
+fn players_are_found_by_name_or_short_id_in_sqlite() {
+    let dir = TempDataDir::new();
+    let mut repo = SqliteRepository::open(dir.path.join("golf.db")).expect("open");
+    let min_woo = Player::new("Min Woo Lee", None).expect("player");
+    let minjee = Player::new("Minjee Lee", None).expect("player");
+    repo.save_player(&min_woo).expect("save");
+    repo.save_player(&minjee).expect("save");
+
+    assert_eq!(repo.find_player("MIN WOO LEE").expect("exact name"), min_woo);
+    assert_eq!(repo.find_player("minj").expect("name prefix"), minjee);
+    let hyphenated: String = min_woo.id.to_string().chars().take(11).collect();
+    assert_eq!(repo.find_player(&hyphenated).expect("id prefix"), min_woo);
+
+    match repo.find_player("Min") {
+        Err(GolfError::AmbiguousPlayer { candidates, .. }) => assert_eq!(candidates.len(), 2),
+        other => panic!("expected AmbiguousPlayer, got {:?}", other),
+    }
+    assert!(matches!(repo.find_player(&Uuid::new_v4().to_string()), Err(GolfError::PlayerNotFound(_))));
+}